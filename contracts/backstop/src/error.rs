@@ -0,0 +1,29 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BackstopError {
+    /// Invalid parameters
+    InvalidArgument = 1,
+
+    NegativeNotAllowed = 2,
+    /// Unstake exceeds the staker's shares
+    InsufficientShares = 3,
+    /// Unstaking is frozen while an incident is open
+    IncidentOpen = 4,
+    /// Incident does not exist
+    IncidentNotFound = 5,
+    /// Incident is already finalized
+    IncidentFinalized = 6,
+    /// Incident is not finalized yet
+    IncidentNotFinalized = 7,
+    /// Compensation already claimed
+    AlreadyClaimed = 8,
+    /// Only cover tokens can be credited to the stakers; other tokens need a treasury
+    NoTreasury = 9,
+    /// Losses wiped out the pool's assets while shares are still outstanding
+    PoolDepleted = 10,
+    /// No pool shares are outstanding to credit the tokens to
+    NoStakers = 11,
+}
@@ -0,0 +1,42 @@
+use soroban_sdk::{contractevent, Address};
+
+/// Emitted when the admin flags a pool exploit
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IncidentDeclared {
+    #[topic]
+    pub id: u32,
+    pub pool: Address,
+}
+
+/// Emitted when an incident's compensation is set aside
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IncidentFinalized {
+    #[topic]
+    pub id: u32,
+    pub total_losses: i128,
+    pub payout: i128,
+}
+
+/// Emitted when an affected user claims their compensation
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompensationClaimed {
+    #[topic]
+    pub id: u32,
+    #[topic]
+    pub user: Address,
+    pub amount: i128,
+}
+
+/// Emitted when tokens sent outside any entrypoint are skimmed
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonationSkimmed {
+    #[topic]
+    pub token: Address,
+    pub amount: i128,
+    /// Recipient, `None` when the amount was credited to the stakers
+    pub treasury: Option<Address>,
+}
@@ -0,0 +1,392 @@
+#![no_std]
+//! # Backstop - Underwritten Cover for Zap Losses
+//!
+//! Stakers deposit a cover token (e.g. USDC) into this pool to underwrite a backstop for zap
+//! users. If a liquidity pool used by the zap is exploited, users whose swap + deposit realized
+//! losses beyond a tolerance can claim compensation from the pool, pro-rata to their losses.
+//!
+//! ## Incident Lifecycle:
+//! 1. `declare_incident`: the admin (or an oracle holding the admin role) flags the exploited pool;
+//!    unstaking freezes so underwriters cannot leave ahead of the payout
+//! 2. `register_loss`: the admin records each affected user's expected and realized output; only
+//!    the shortfall beyond `loss_tolerance_bps` is covered
+//! 3. `finalize_incident`: the payout is set aside - the covered losses, capped at
+//!    `max_payout_bps` of the pool - and unstaking resumes
+//! 4. `claim`: every affected user receives `payout * loss / total_losses`
+//!
+//! ## Staker Rewards:
+//! Stakers own pool shares. Rewards (a slice of protocol fees) are paid in with `add_rewards`,
+//! which grows the assets behind every share; payouts shrink them.
+//!
+//! ## Donations:
+//! Tokens transferred to the pool directly, outside any entrypoint, are not part of its
+//! accounting. Anyone can `skim` them: depending on the admin's `DonationPolicy` they are
+//! credited to the stakers like rewards (cover tokens only) or forwarded to the treasury.
+
+use common::math::Bps;
+use soroban_sdk::{
+    Address, Env, contract, contractimpl, panic_with_error, token
+};
+
+mod storage;
+mod error;
+mod events;
+
+use storage::{
+    extend_instance_ttl, get_admin, get_claimed, get_config, get_incident, get_incident_count,
+    get_loss, get_open_incidents, get_shares, get_total_assets, get_total_shares, set_admin,
+    set_claimed, set_config, set_incident, set_incident_count, set_loss, set_open_incidents,
+    set_shares, set_total_assets, set_total_shares, Config, Incident, get_donation_policy,
+    get_reserved_payouts, set_donation_policy, set_reserved_payouts, DonationPolicy,
+};
+use error::BackstopError;
+use events::{CompensationClaimed, DonationSkimmed, IncidentDeclared, IncidentFinalized};
+
+/// Validates that the amount is non-negative
+pub fn check_nonnegative_amount(amount: i128) -> Result<(), BackstopError> {
+    if amount < 0 {
+        Err(BackstopError::NegativeNotAllowed)
+    } else {
+        Ok(())
+    }
+}
+
+/// Pulls `amount` of the cover token from `from` into the pool
+fn pull_cover(e: &Env, from: &Address, amount: i128) {
+    token::Client::new(e, &get_config(e).cover_token).transfer(from, e.current_contract_address(), &amount);
+}
+
+/// Pays `amount` of the cover token from the pool to `to`
+fn pay_cover(e: &Env, to: &Address, amount: i128) {
+    token::Client::new(e, &get_config(e).cover_token).transfer(&e.current_contract_address(), to, &amount);
+}
+
+#[contract]
+struct Backstop;
+
+#[contractimpl]
+impl Backstop {
+    /// Initialize the pool with the admin and the cover parameters
+    ///
+    /// ## Parameters:
+    /// - `admin`: Account (or oracle) flagging incidents and registering losses
+    /// - `cover_token`: Token staked and paid out
+    /// - `loss_tolerance_bps`: Shortfall users absorb themselves, in basis points
+    /// - `max_payout_bps`: Largest share of the pool paid for a single incident
+    pub fn __constructor(
+        e: Env,
+        admin: Address,
+        cover_token: Address,
+        loss_tolerance_bps: Bps,
+        max_payout_bps: Bps,
+    ) {
+        if loss_tolerance_bps >= Bps::MAX || !max_payout_bps.is_valid() {
+            panic_with_error!(&e, BackstopError::InvalidArgument);
+        }
+        set_admin(&e, admin);
+        set_config(
+            &e,
+            &Config {
+                cover_token,
+                loss_tolerance_bps,
+                max_payout_bps,
+            },
+        );
+    }
+
+    /// Stake cover tokens, receiving pool shares
+    ///
+    /// The first stake mints shares 1:1. Once losses have wiped out every asset while shares are
+    /// still outstanding, staking fails with `PoolDepleted`: new cover would otherwise be shared
+    /// with the holders of the worthless shares.
+    ///
+    /// ## Returns:
+    /// Pool shares minted
+    pub fn stake(e: Env, staker: Address, amount: i128) -> Result<i128, BackstopError> {
+        staker.require_auth();
+        check_nonnegative_amount(amount)?;
+        extend_instance_ttl(&e);
+
+        let total_shares = get_total_shares(&e);
+        let total_assets = get_total_assets(&e);
+        let shares = if total_shares == 0 {
+            amount
+        } else if total_assets == 0 {
+            return Err(BackstopError::PoolDepleted);
+        } else {
+            amount * total_shares / total_assets
+        };
+        if shares == 0 {
+            return Err(BackstopError::InvalidArgument);
+        }
+
+        pull_cover(&e, &staker, amount);
+        set_shares(&e, &staker, get_shares(&e, &staker) + shares);
+        set_total_shares(&e, total_shares + shares);
+        set_total_assets(&e, total_assets + amount);
+        Ok(shares)
+    }
+
+    /// Burn pool shares for their cover tokens
+    ///
+    /// Frozen with `IncidentOpen` while any incident awaits finalization. Fails with
+    /// `InvalidArgument` when `shares` is zero.
+    ///
+    /// ## Returns:
+    /// Cover tokens paid out
+    pub fn unstake(e: Env, staker: Address, shares: i128) -> Result<i128, BackstopError> {
+        staker.require_auth();
+        check_nonnegative_amount(shares)?;
+        if shares == 0 {
+            return Err(BackstopError::InvalidArgument);
+        }
+        extend_instance_ttl(&e);
+
+        if get_open_incidents(&e) > 0 {
+            return Err(BackstopError::IncidentOpen);
+        }
+        let staker_shares = get_shares(&e, &staker);
+        if shares > staker_shares {
+            return Err(BackstopError::InsufficientShares);
+        }
+
+        let total_shares = get_total_shares(&e);
+        let total_assets = get_total_assets(&e);
+        let amount = shares * total_assets / total_shares;
+
+        set_shares(&e, &staker, staker_shares - shares);
+        set_total_shares(&e, total_shares - shares);
+        set_total_assets(&e, total_assets - amount);
+        pay_cover(&e, &staker, amount);
+        Ok(amount)
+    }
+
+    /// Pay staker rewards into the pool, e.g. a slice of protocol fees
+    ///
+    /// Raises the value of every pool share. Fails with `NoStakers` while no shares are
+    /// outstanding, since the first staker would otherwise take the rewards.
+    pub fn add_rewards(e: Env, from: Address, amount: i128) -> Result<(), BackstopError> {
+        from.require_auth();
+        check_nonnegative_amount(amount)?;
+        extend_instance_ttl(&e);
+        if get_total_shares(&e) == 0 {
+            return Err(BackstopError::NoStakers);
+        }
+
+        pull_cover(&e, &from, amount);
+        set_total_assets(&e, get_total_assets(&e) + amount);
+        Ok(())
+    }
+
+    /// Flag an exploit of `pool` and freeze unstaking until it is finalized (admin only)
+    ///
+    /// ## Returns:
+    /// Id of the new incident
+    pub fn declare_incident(e: Env, pool: Address) -> u32 {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+
+        let id = get_incident_count(&e);
+        set_incident(
+            &e,
+            &Incident {
+                id,
+                pool: pool.clone(),
+                total_losses: 0,
+                payout: 0,
+                finalized: false,
+            },
+        );
+        set_incident_count(&e, id + 1);
+        set_open_incidents(&e, get_open_incidents(&e) + 1);
+
+        IncidentDeclared { id, pool }.publish(&e);
+        id
+    }
+
+    /// Record an affected user's outcome for an open incident (admin only)
+    ///
+    /// `expected_out` is what the swap + deposit should have produced and `realized_out` what it
+    /// did. Only the shortfall beyond the loss tolerance is covered; registering a user again
+    /// replaces their previous figure.
+    ///
+    /// ## Returns:
+    /// Covered loss recorded for the user
+    pub fn register_loss(
+        e: Env,
+        id: u32,
+        user: Address,
+        expected_out: i128,
+        realized_out: i128,
+    ) -> Result<i128, BackstopError> {
+        get_admin(&e).require_auth();
+        check_nonnegative_amount(expected_out)?;
+        check_nonnegative_amount(realized_out)?;
+        extend_instance_ttl(&e);
+
+        let mut incident = get_incident(&e, id).ok_or(BackstopError::IncidentNotFound)?;
+        if incident.finalized {
+            return Err(BackstopError::IncidentFinalized);
+        }
+
+        let tolerated_out = get_config(&e).loss_tolerance_bps.complement().apply(expected_out);
+        let loss = (tolerated_out - realized_out).max(0);
+
+        incident.total_losses += loss - get_loss(&e, id, &user);
+        set_incident(&e, &incident);
+        set_loss(&e, id, &user, loss);
+        Ok(loss)
+    }
+
+    /// Set aside the incident's payout and resume unstaking (admin only)
+    ///
+    /// The payout covers the registered losses in full when the pool allows it, and is capped at
+    /// `max_payout_bps` of the pool otherwise.
+    ///
+    /// ## Returns:
+    /// Cover tokens set aside for the affected users
+    pub fn finalize_incident(e: Env, id: u32) -> Result<i128, BackstopError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+
+        let mut incident = get_incident(&e, id).ok_or(BackstopError::IncidentNotFound)?;
+        if incident.finalized {
+            return Err(BackstopError::IncidentFinalized);
+        }
+
+        let total_assets = get_total_assets(&e);
+        let cap = get_config(&e).max_payout_bps.apply(total_assets);
+        incident.payout = incident.total_losses.min(cap);
+        incident.finalized = true;
+        set_incident(&e, &incident);
+        set_total_assets(&e, total_assets - incident.payout);
+        set_reserved_payouts(&e, get_reserved_payouts(&e) + incident.payout);
+        set_open_incidents(&e, get_open_incidents(&e) - 1);
+
+        IncidentFinalized {
+            id,
+            total_losses: incident.total_losses,
+            payout: incident.payout,
+        }
+        .publish(&e);
+        Ok(incident.payout)
+    }
+
+    /// Claim the user's pro-rata compensation for a finalized incident
+    ///
+    /// ## Returns:
+    /// Cover tokens paid to the user
+    pub fn claim(e: Env, id: u32, user: Address) -> Result<i128, BackstopError> {
+        user.require_auth();
+        extend_instance_ttl(&e);
+
+        let incident = get_incident(&e, id).ok_or(BackstopError::IncidentNotFound)?;
+        if !incident.finalized {
+            return Err(BackstopError::IncidentNotFinalized);
+        }
+        if get_claimed(&e, id, &user) {
+            return Err(BackstopError::AlreadyClaimed);
+        }
+
+        let loss = get_loss(&e, id, &user);
+        if loss == 0 {
+            return Err(BackstopError::InvalidArgument);
+        }
+        let amount = incident.payout * loss / incident.total_losses;
+        set_claimed(&e, id, &user);
+        set_reserved_payouts(&e, get_reserved_payouts(&e) - amount);
+        pay_cover(&e, &user, amount);
+
+        CompensationClaimed { id, user, amount }.publish(&e);
+        Ok(amount)
+    }
+
+    /// Set where skimmed donations go (admin only)
+    pub fn set_donation_policy(e: Env, policy: DonationPolicy) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_donation_policy(&e, &policy);
+    }
+
+    /// Route tokens held beyond the pool's accounting according to the donation policy
+    ///
+    /// For the cover token the excess is the balance above the pool assets and the unclaimed
+    /// payouts; any other token is excess in full. Callable by anyone, since the destination is
+    /// fixed by the policy. Fails with `NoTreasury` for a token other than the cover token
+    /// while donations are credited to the stakers, and with `NoStakers` when they are credited
+    /// to the stakers while no shares are outstanding.
+    ///
+    /// ## Returns:
+    /// Amount skimmed
+    pub fn skim(e: Env, token: Address) -> Result<i128, BackstopError> {
+        extend_instance_ttl(&e);
+
+        let this = e.current_contract_address();
+        let token_client = token::Client::new(&e, &token);
+        let is_cover = token == get_config(&e).cover_token;
+        let mut amount = token_client.balance(&this);
+        if is_cover {
+            amount -= get_total_assets(&e) + get_reserved_payouts(&e);
+        }
+        if amount <= 0 {
+            return Ok(0);
+        }
+
+        let treasury = match get_donation_policy(&e) {
+            DonationPolicy::Stakers if is_cover => {
+                if get_total_shares(&e) == 0 {
+                    return Err(BackstopError::NoStakers);
+                }
+                set_total_assets(&e, get_total_assets(&e) + amount);
+                None
+            }
+            DonationPolicy::Stakers => return Err(BackstopError::NoTreasury),
+            DonationPolicy::Treasury(treasury) => {
+                token_client.transfer(&this, &treasury, &amount);
+                Some(treasury)
+            }
+        };
+
+        DonationSkimmed { token, amount, treasury }.publish(&e);
+        Ok(amount)
+    }
+
+    /// Where skimmed donations go
+    pub fn donation_policy(e: Env) -> DonationPolicy {
+        get_donation_policy(&e)
+    }
+
+    /// Incident details
+    pub fn incident(e: Env, id: u32) -> Option<Incident> {
+        get_incident(&e, id)
+    }
+
+    /// Covered loss registered for a user in an incident
+    pub fn loss(e: Env, id: u32, user: Address) -> i128 {
+        get_loss(&e, id, &user)
+    }
+
+    /// Pool shares of a staker
+    pub fn shares(e: Env, staker: Address) -> i128 {
+        get_shares(&e, &staker)
+    }
+
+    /// Cover tokens a staker's shares are worth right now
+    pub fn staked_value(e: Env, staker: Address) -> i128 {
+        let total_shares = get_total_shares(&e);
+        if total_shares == 0 {
+            return 0;
+        }
+        get_shares(&e, &staker) * get_total_assets(&e) / total_shares
+    }
+
+    /// Cover tokens backing the pool shares
+    pub fn total_assets(e: Env) -> i128 {
+        get_total_assets(&e)
+    }
+
+    /// Cover parameters
+    pub fn config(e: Env) -> Config {
+        get_config(&e)
+    }
+}
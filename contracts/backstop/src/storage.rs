@@ -0,0 +1,249 @@
+use common::math::Bps;
+use soroban_sdk::{contracttype, Address, Env};
+
+#[derive(Clone)]
+#[contracttype]
+
+enum DataKey {
+    Admin,
+    Config,
+    TotalShares,
+    TotalAssets,
+    Shares(Address),
+    OpenIncidents,
+    IncidentCount,
+    Incident(u32),
+    Loss(u32, Address),
+    Claimed(u32, Address),
+    ReservedPayouts,
+    DonationPolicy,
+}
+
+/// Cover parameters, fixed at deployment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// Token staked by underwriters and paid out as compensation
+    pub cover_token: Address,
+    /// Shortfall against the expected output that users absorb themselves, in basis points
+    pub loss_tolerance_bps: Bps,
+    /// Largest share of the pool paid out for a single incident, in basis points
+    pub max_payout_bps: Bps,
+}
+
+/// Pool exploit flagged by the admin, and the compensation it triggers
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Incident {
+    pub id: u32,
+    /// Exploited liquidity pool
+    pub pool: Address,
+    /// Covered losses registered so far
+    pub total_losses: i128,
+    /// Compensation set aside when the incident was finalized
+    pub payout: i128,
+    pub finalized: bool,
+}
+
+/// Destination of tokens sent to the pool outside any entrypoint
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DonationPolicy {
+    /// Cover tokens are added to the pool assets, raising every share pro-rata
+    Stakers,
+    /// Everything is forwarded to the treasury
+    Treasury(Address),
+}
+
+#[allow(dead_code)]
+const DAY_IN_LEDGERS: u32 = 17280;
+#[allow(dead_code)]
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+#[allow(dead_code)]
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+const PERSISTENT_BUMP_AMOUNT: u32 = 60 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+pub fn extend_instance_ttl(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Admin Address (flags incidents and registers losses)
+pub fn set_admin(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Admin, &address);
+}
+
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap()
+}
+
+// Cover parameters
+pub fn set_config(e: &Env, config: &Config) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Config, config);
+}
+
+pub fn get_config(e: &Env) -> Config {
+    e.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .unwrap()
+}
+
+// Pool accounting: staker shares and the cover tokens backing them
+pub fn set_total_shares(e: &Env, shares: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TotalShares, &shares);
+}
+
+pub fn get_total_shares(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::TotalShares)
+        .unwrap_or(0)
+}
+
+pub fn set_total_assets(e: &Env, assets: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TotalAssets, &assets);
+}
+
+pub fn get_total_assets(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::TotalAssets)
+        .unwrap_or(0)
+}
+
+pub fn set_shares(e: &Env, staker: &Address, shares: i128) {
+    let key = DataKey::Shares(staker.clone());
+    e.storage().persistent().set(&key, &shares);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_shares(e: &Env, staker: &Address) -> i128 {
+    let key = DataKey::Shares(staker.clone());
+    let shares = e.storage().persistent().get(&key);
+    if shares.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    shares.unwrap_or(0)
+}
+
+// Finalized payouts not claimed yet (held by the pool but no longer part of its assets)
+pub fn set_reserved_payouts(e: &Env, amount: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ReservedPayouts, &amount);
+}
+
+pub fn get_reserved_payouts(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::ReservedPayouts)
+        .unwrap_or(0)
+}
+
+// Donation policy (defaults to crediting the stakers)
+pub fn set_donation_policy(e: &Env, policy: &DonationPolicy) {
+    e.storage()
+        .instance()
+        .set(&DataKey::DonationPolicy, policy);
+}
+
+pub fn get_donation_policy(e: &Env) -> DonationPolicy {
+    e.storage()
+        .instance()
+        .get(&DataKey::DonationPolicy)
+        .unwrap_or(DonationPolicy::Stakers)
+}
+
+// Number of incidents not finalized yet (unstaking is frozen while non-zero)
+pub fn set_open_incidents(e: &Env, count: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::OpenIncidents, &count);
+}
+
+pub fn get_open_incidents(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::OpenIncidents)
+        .unwrap_or(0)
+}
+
+// Incidents (ids are sequential, starting at 0)
+pub fn set_incident_count(e: &Env, count: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::IncidentCount, &count);
+}
+
+pub fn get_incident_count(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::IncidentCount)
+        .unwrap_or(0)
+}
+
+pub fn set_incident(e: &Env, incident: &Incident) {
+    let key = DataKey::Incident(incident.id);
+    e.storage().persistent().set(&key, incident);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_incident(e: &Env, id: u32) -> Option<Incident> {
+    let key = DataKey::Incident(id);
+    let incident = e.storage().persistent().get(&key);
+    if incident.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    incident
+}
+
+// Covered loss of each affected user, per incident
+pub fn set_loss(e: &Env, id: u32, user: &Address, loss: i128) {
+    let key = DataKey::Loss(id, user.clone());
+    e.storage().persistent().set(&key, &loss);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_loss(e: &Env, id: u32, user: &Address) -> i128 {
+    let key = DataKey::Loss(id, user.clone());
+    let loss = e.storage().persistent().get(&key);
+    if loss.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    loss.unwrap_or(0)
+}
+
+// Whether a user already claimed their compensation for an incident
+pub fn set_claimed(e: &Env, id: u32, user: &Address) {
+    let key = DataKey::Claimed(id, user.clone());
+    e.storage().persistent().set(&key, &true);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_claimed(e: &Env, id: u32, user: &Address) -> bool {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Claimed(id, user.clone()))
+        .unwrap_or(false)
+}
@@ -0,0 +1,6 @@
+#![allow(clippy::too_many_arguments)]
+
+soroban_sdk::contractimport!(file = "../wasms/defindex_vault.wasm");
+
+#[allow(dead_code)]
+pub type DeFindexVaultClient<'a> = Client<'a>;
@@ -0,0 +1,21 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BondError {
+    /// Invalid parameters
+    InvalidArgument = 1,
+
+    NegativeNotAllowed = 2,
+    /// Purchase exceeds the remaining capacity or the shares held for sale
+    CapacityExceeded = 3,
+    /// Purchase would deliver fewer shares than the buyer's minimum
+    SlippageExceeded = 4,
+    /// The vault shares could not be priced in the payment token
+    PriceUnavailable = 5,
+    /// Paying in another token requires an aggregator
+    AggregatorNotSet = 6,
+    /// Converting the buyer's token did not yield the payment amount
+    PaymentConversionFailed = 7,
+}
@@ -0,0 +1,22 @@
+use common::math::Bps;
+use soroban_sdk::{contractevent, Address};
+
+/// Emitted for every bond purchase
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondPurchased {
+    #[topic]
+    pub buyer: Address,
+    pub payment: i128,
+    pub shares: i128,
+    pub discount_bps: Bps,
+}
+
+/// Emitted when a buyer claims vested shares
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondClaimed {
+    #[topic]
+    pub buyer: Address,
+    pub shares: i128,
+}
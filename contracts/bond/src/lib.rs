@@ -0,0 +1,388 @@
+#![no_std]
+//! # Bond - Discounted Vault Share Sale
+//!
+//! An Olympus-style bonding primitive built on top of the zap. The treasury deposits into the
+//! DeFindex vault through the zap and hands the resulting vault shares to this contract, which
+//! sells them at a discount for an asset the treasury wants (e.g. USDC). Bought shares vest
+//! linearly over the `vesting_period` of the terms.
+//!
+//! ## Pricing:
+//! The market price of a share is the underlying asset it redeems for (according to the vault),
+//! valued in the payment token at the prices of a SEP-40 oracle (e.g. Reflector). Pool quotes are
+//! never used, so a manipulated pool cannot cheapen the shares. Buyers get a discount on that price:
+//! - Right after a sale the discount is `base_discount_bps`
+//! - When nobody buys, it grows linearly up to `max_discount_bps` over `discount_growth_period`
+//!   ledgers, so the price decays until demand returns
+//!
+//! ## Capacity:
+//! The admin caps the total shares ever sold with `capacity`; every purchase must also be
+//! covered by shares the contract actually holds beyond those already owed to buyers.
+//!
+//! ## Vesting:
+//! Each buyer has a single position. A new purchase first settles what already vested, then
+//! adds the new shares to the unvested remainder and restarts the vesting period for all of it.
+//!
+//! ## Paying in another token:
+//! With an aggregator set, `bond_with` accepts any token: it is swapped into the payment token
+//! in the buyer's wallet first (see `common::pay`), then the purchase settles as usual.
+
+use common::{math::Bps, pay::convert_for_payment};
+use soroban_sdk::{
+    Address, Env, contract, contractimpl, panic_with_error, token
+};
+
+mod defindex_vault;
+mod oracle;
+mod storage;
+mod error;
+mod events;
+
+use defindex_vault::DeFindexVaultClient;
+use oracle::{Asset, PriceOracleClient};
+use storage::{
+    extend_instance_ttl, get_admin, get_aggregator_address, get_last_sale_ledger,
+    get_oracle, get_outstanding, get_payment_token, get_position, get_terms,
+    get_total_sold, get_treasury, get_underlying_asset_address, get_vault_address, set_admin,
+    set_aggregator_address, set_last_sale_ledger, set_outstanding, set_payment_token,
+    set_oracle, set_position, set_terms, set_total_sold, set_treasury,
+    set_underlying_asset_address, set_vault_address, BondPosition, BondTerms,
+};
+use error::BondError;
+use events::{BondClaimed, BondPurchased};
+
+/// Validates that the amount is non-negative
+pub fn check_nonnegative_amount(amount: i128) -> Result<(), BondError> {
+    if amount < 0 {
+        Err(BondError::NegativeNotAllowed)
+    } else {
+        Ok(())
+    }
+}
+
+/// Amount of vault shares prices are quoted for (one share with 7 decimals)
+const SHARE_UNIT: i128 = 10_000_000;
+
+/// Validates the pricing and capacity controls
+fn check_terms(terms: &BondTerms) -> Result<(), BondError> {
+    if terms.base_discount_bps > terms.max_discount_bps
+        || terms.max_discount_bps >= Bps::MAX
+        || terms.capacity < 0
+    {
+        return Err(BondError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Discount at the current ledger, growing linearly since the latest sale
+fn current_discount_bps(e: &Env, terms: &BondTerms) -> Bps {
+    let elapsed = e.ledger().sequence() - get_last_sale_ledger(e);
+    if elapsed >= terms.discount_growth_period {
+        return terms.max_discount_bps;
+    }
+    let spread = terms.max_discount_bps.value() - terms.base_discount_bps.value();
+    let growth = spread as u64 * elapsed as u64 / terms.discount_growth_period as u64;
+    Bps::new(terms.base_discount_bps.value() + growth as u32).unwrap_or(terms.max_discount_bps)
+}
+
+/// Oldest oracle price accepted, in seconds
+const MAX_PRICE_AGE: u64 = 15 * 60;
+
+/// Oracle price of `asset`, rejected when missing, non-positive or older than `MAX_PRICE_AGE`
+fn oracle_price(e: &Env, oracle: &PriceOracleClient, asset: &Address) -> Result<i128, BondError> {
+    let data = oracle
+        .lastprice(&Asset::Stellar(asset.clone()))
+        .ok_or(BondError::PriceUnavailable)?;
+    if data.price <= 0 || e.ledger().timestamp().saturating_sub(data.timestamp) > MAX_PRICE_AGE {
+        return Err(BondError::PriceUnavailable);
+    }
+    Ok(data.price)
+}
+
+/// Market price of `SHARE_UNIT` vault shares, in payment token units
+fn market_price(e: &Env) -> Result<i128, BondError> {
+    let underlying_amount = DeFindexVaultClient::new(e, &get_vault_address(e))
+        .get_asset_amounts_per_shares(&SHARE_UNIT)
+        .get(0)
+        .ok_or(BondError::PriceUnavailable)?;
+
+    let underlying_asset = get_underlying_asset_address(e);
+    let payment_token = get_payment_token(e);
+    let price = if payment_token == underlying_asset || underlying_amount == 0 {
+        underlying_amount
+    } else {
+        let oracle = PriceOracleClient::new(e, &get_oracle(e));
+        let price_in = oracle_price(e, &oracle, &underlying_asset)?;
+        let price_out = oracle_price(e, &oracle, &payment_token)?;
+        underlying_amount
+            .checked_mul(price_in)
+            .map(|value| value / price_out)
+            .ok_or(BondError::PriceUnavailable)?
+    };
+
+    if price <= 0 {
+        return Err(BondError::PriceUnavailable);
+    }
+    Ok(price)
+}
+
+/// Moves the shares vested since `last_ledger` from `unvested` to `vested`
+fn settle(position: &mut BondPosition, ledger: u32) {
+    if ledger >= position.vesting_end {
+        position.vested += position.unvested;
+        position.unvested = 0;
+    } else if ledger > position.last_ledger {
+        let newly_vested = position.unvested * (ledger - position.last_ledger) as i128
+            / (position.vesting_end - position.last_ledger) as i128;
+        position.vested += newly_vested;
+        position.unvested -= newly_vested;
+    }
+    position.last_ledger = ledger;
+}
+
+/// Sells the discounted shares for `payment`, already held by the buyer in the payment token
+fn purchase(e: &Env, buyer: Address, payment: i128, min_shares: i128) -> Result<i128, BondError> {
+    extend_instance_ttl(e);
+
+    let terms = get_terms(e);
+    let discount_bps = current_discount_bps(e, &terms);
+    let discounted_price = discount_bps.complement().apply(market_price(e)?);
+    if discounted_price <= 0 {
+        return Err(BondError::PriceUnavailable);
+    }
+
+    let shares = payment * SHARE_UNIT / discounted_price;
+    if shares == 0 {
+        return Err(BondError::InvalidArgument);
+    }
+    if shares < min_shares {
+        return Err(BondError::SlippageExceeded);
+    }
+
+    // Both the lifetime capacity and the shares actually held must cover the sale
+    let total_sold = get_total_sold(e) + shares;
+    let outstanding = get_outstanding(e) + shares;
+    let this = e.current_contract_address();
+    if total_sold > terms.capacity
+        || DeFindexVaultClient::new(e, &get_vault_address(e)).balance(&this) < outstanding
+    {
+        return Err(BondError::CapacityExceeded);
+    }
+
+    token::Client::new(e, &get_payment_token(e)).transfer(&buyer, get_treasury(e), &payment);
+
+    let ledger = e.ledger().sequence();
+    let mut position = get_position(e, &buyer).unwrap_or(BondPosition {
+        unvested: 0,
+        vested: 0,
+        last_ledger: ledger,
+        vesting_end: ledger,
+    });
+    settle(&mut position, ledger);
+    position.unvested += shares;
+    position.vesting_end = ledger + terms.vesting_period;
+    set_position(e, &buyer, &position);
+
+    set_total_sold(e, total_sold);
+    set_outstanding(e, outstanding);
+    set_last_sale_ledger(e, ledger);
+
+    BondPurchased {
+        buyer,
+        payment,
+        shares,
+        discount_bps,
+    }
+    .publish(e);
+
+    Ok(shares)
+}
+
+#[contract]
+struct Bond;
+
+#[contractimpl]
+impl Bond {
+    /// Initialize the bond sale
+    ///
+    /// ## Parameters:
+    /// - `admin`: Account managing the terms
+    /// - `vault_address`: DeFindex vault whose shares are sold
+    /// - `oracle`: SEP-40 price feed pricing the underlying asset in the payment token
+    /// - `payment_token`: Token the treasury wants in exchange
+    /// - `treasury`: Receiver of the payments
+    /// - `terms`: Pricing, capacity and vesting controls
+    pub fn __constructor(
+        e: Env,
+        admin: Address,
+        vault_address: Address,
+        oracle: Address,
+        payment_token: Address,
+        treasury: Address,
+        terms: BondTerms,
+    ) {
+        if check_terms(&terms).is_err() {
+            panic_with_error!(&e, BondError::InvalidArgument);
+        }
+        // Single-asset vault: its first asset is the underlying one
+        let underlying_asset = DeFindexVaultClient::new(&e, &vault_address)
+            .get_assets()
+            .get(0)
+            .unwrap_or_else(|| panic_with_error!(&e, BondError::InvalidArgument))
+            .address;
+
+        set_admin(&e, admin);
+        set_vault_address(&e, vault_address);
+        set_oracle(&e, oracle);
+        set_underlying_asset_address(&e, underlying_asset);
+        set_payment_token(&e, payment_token);
+        set_treasury(&e, treasury);
+        set_terms(&e, &terms);
+        set_last_sale_ledger(&e, e.ledger().sequence());
+    }
+
+    /// Replace the pricing, capacity and vesting controls (admin only)
+    ///
+    /// `capacity` counts every share sold so far, so lowering it below that stops the sale. A new
+    /// `vesting_period` applies from each buyer's next purchase.
+    pub fn set_terms(e: Env, terms: BondTerms) -> Result<(), BondError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        check_terms(&terms)?;
+        set_terms(&e, &terms);
+        Ok(())
+    }
+
+    /// Set the aggregator `bond_with` converts other tokens through (admin only)
+    pub fn set_aggregator(e: Env, aggregator_address: Address) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_aggregator_address(&e, aggregator_address);
+    }
+
+    /// Return vault shares not owed to any buyer to the admin (admin only)
+    pub fn withdraw_unsold(e: Env, shares: i128) -> Result<(), BondError> {
+        let admin = get_admin(&e);
+        admin.require_auth();
+        check_nonnegative_amount(shares)?;
+        extend_instance_ttl(&e);
+
+        let vault_client = DeFindexVaultClient::new(&e, &get_vault_address(&e));
+        let this = e.current_contract_address();
+        if vault_client.balance(&this) - get_outstanding(&e) < shares {
+            return Err(BondError::CapacityExceeded);
+        }
+        vault_client.transfer(&this, &admin, &shares);
+        Ok(())
+    }
+
+    /// Buy vault shares at the current discount, paying with the payment token
+    ///
+    /// The payment goes straight to the treasury; the shares vest linearly from now on.
+    ///
+    /// ## Parameters:
+    /// - `buyer`: The user buying (must sign the transaction)
+    /// - `payment`: Amount of payment token to spend
+    /// - `min_shares`: Fewest shares the buyer accepts for `payment`
+    ///
+    /// ## Returns:
+    /// Vault shares added to the buyer's vesting position
+    pub fn bond(e: Env, buyer: Address, payment: i128, min_shares: i128) -> Result<i128, BondError> {
+        buyer.require_auth();
+        check_nonnegative_amount(payment)?;
+        check_nonnegative_amount(min_shares)?;
+        purchase(&e, buyer, payment, min_shares)
+    }
+
+    /// Buy vault shares paying with any token, converted into the payment token first
+    ///
+    /// `amount_in` of `token_in` is swapped through the aggregator into the buyer's wallet; any
+    /// payment token received beyond `payment` stays there. Paying directly in the payment token
+    /// skips the swap.
+    ///
+    /// ## Parameters:
+    /// - `buyer`: The user buying (must sign the transaction, including the aggregator swap)
+    /// - `token_in`: Token the buyer pays with
+    /// - `amount_in`: Amount of `token_in` to convert
+    /// - `payment`: Amount of payment token the conversion must yield and the purchase spends
+    /// - `min_shares`: Fewest shares the buyer accepts for `payment`
+    ///
+    /// ## Returns:
+    /// Vault shares added to the buyer's vesting position
+    pub fn bond_with(
+        e: Env,
+        buyer: Address,
+        token_in: Address,
+        amount_in: i128,
+        payment: i128,
+        min_shares: i128,
+    ) -> Result<i128, BondError> {
+        buyer.require_auth();
+        check_nonnegative_amount(amount_in)?;
+        check_nonnegative_amount(payment)?;
+        check_nonnegative_amount(min_shares)?;
+
+        let aggregator = get_aggregator_address(&e).ok_or(BondError::AggregatorNotSet)?;
+        let payment_token = get_payment_token(&e);
+        convert_for_payment(&e, &aggregator, &buyer, &token_in, &payment_token, amount_in, payment)
+            .ok_or(BondError::PaymentConversionFailed)?;
+
+        purchase(&e, buyer, payment, min_shares)
+    }
+
+    /// Transfer the buyer's vested shares to them
+    ///
+    /// ## Returns:
+    /// Vault shares transferred
+    pub fn claim(e: Env, buyer: Address) -> Result<i128, BondError> {
+        buyer.require_auth();
+        extend_instance_ttl(&e);
+
+        let Some(mut position) = get_position(&e, &buyer) else {
+            return Ok(0);
+        };
+        settle(&mut position, e.ledger().sequence());
+        let shares = position.vested;
+        position.vested = 0;
+        set_position(&e, &buyer, &position);
+
+        if shares > 0 {
+            set_outstanding(&e, get_outstanding(&e) - shares);
+            DeFindexVaultClient::new(&e, &get_vault_address(&e)).transfer(&e.current_contract_address(), &buyer, &shares);
+            BondClaimed { buyer, shares }.publish(&e);
+        }
+        Ok(shares)
+    }
+
+    /// Buyer's vesting position, settled at the current ledger
+    pub fn position(e: Env, buyer: Address) -> Option<BondPosition> {
+        let mut position = get_position(&e, &buyer)?;
+        settle(&mut position, e.ledger().sequence());
+        Some(position)
+    }
+
+    /// Current offer for `SHARE_UNIT` (one) vault share
+    ///
+    /// ## Returns:
+    /// `(market price, discount in bps, discounted price)`, prices in payment token units
+    pub fn price(e: Env) -> Result<(i128, Bps, i128), BondError> {
+        let market = market_price(&e)?;
+        let discount_bps = current_discount_bps(&e, &get_terms(&e));
+        let discounted = discount_bps.complement().apply(market);
+        Ok((market, discount_bps, discounted))
+    }
+
+    /// Shares that can still be sold under the capacity
+    pub fn remaining_capacity(e: Env) -> i128 {
+        (get_terms(&e).capacity - get_total_sold(&e)).max(0)
+    }
+
+    /// Pricing, capacity and vesting controls
+    pub fn terms(e: Env) -> BondTerms {
+        get_terms(&e)
+    }
+
+    /// Aggregator used by `bond_with`, if any
+    pub fn aggregator(e: Env) -> Option<Address> {
+        get_aggregator_address(&e)
+    }
+}
@@ -0,0 +1,26 @@
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+// SEP-40 price feed interface (e.g. Reflector) pricing the underlying asset in the payment token
+
+/// Asset quoted by the oracle
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// Price of an asset, with the oracle's `decimals`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn decimals(e: Env) -> u32;
+    fn lastprice(e: Env, asset: Asset) -> Option<PriceData>;
+}
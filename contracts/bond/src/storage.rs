@@ -0,0 +1,241 @@
+use common::math::Bps;
+use soroban_sdk::{contracttype, Address, Env};
+
+#[derive(Clone)]
+#[contracttype]
+
+enum DataKey {
+    Admin,
+    DeFindexVaultAddress,
+    Oracle,
+    UnderlyingAsset,
+    PaymentToken,
+    Treasury,
+    AggregatorAddress,
+    Terms,
+    LastSaleLedger,
+    TotalSold,
+    Outstanding,
+    Position(Address),
+}
+
+/// Pricing, capacity and vesting controls of the bond sale, set by the admin
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondTerms {
+    /// Discount to the market price right after a sale, in basis points
+    pub base_discount_bps: Bps,
+    /// Largest discount, reached when no sale happened for `discount_growth_period` ledgers
+    pub max_discount_bps: Bps,
+    /// Ledgers over which the discount grows linearly from base to max
+    pub discount_growth_period: u32,
+    /// Total vault shares that can ever be sold
+    pub capacity: i128,
+    /// Vesting period of a purchase, in ledgers
+    pub vesting_period: u32,
+}
+
+/// Vault shares bought by a user, vesting linearly
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondPosition {
+    /// Shares still vesting
+    pub unvested: i128,
+    /// Shares vested but not claimed yet
+    pub vested: i128,
+    /// Ledger up to which `unvested` has been settled
+    pub last_ledger: u32,
+    /// Ledger at which `unvested` is fully vested
+    pub vesting_end: u32,
+}
+
+#[allow(dead_code)]
+const DAY_IN_LEDGERS: u32 = 17280;
+#[allow(dead_code)]
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+#[allow(dead_code)]
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+const PERSISTENT_BUMP_AMOUNT: u32 = 60 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+pub fn extend_instance_ttl(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Admin Address
+pub fn set_admin(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Admin, &address);
+}
+
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap()
+}
+
+// DeFindex Vault Address (its shares are sold)
+pub fn set_vault_address(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::DeFindexVaultAddress, &address);
+}
+
+pub fn get_vault_address(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::DeFindexVaultAddress)
+        .unwrap()
+}
+
+// SEP-40 Oracle (prices the underlying asset in the payment token)
+pub fn set_oracle(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Oracle, &address);
+}
+
+pub fn get_oracle(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Oracle)
+        .unwrap()
+}
+
+// Underlying asset of the vault
+pub fn set_underlying_asset_address(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::UnderlyingAsset, &address);
+}
+
+pub fn get_underlying_asset_address(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::UnderlyingAsset)
+        .unwrap()
+}
+
+// Token the treasury wants in exchange for the shares
+pub fn set_payment_token(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::PaymentToken, &address);
+}
+
+pub fn get_payment_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::PaymentToken)
+        .unwrap()
+}
+
+// Treasury receiving the payments
+pub fn set_treasury(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Treasury, &address);
+}
+
+pub fn get_treasury(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Treasury)
+        .unwrap()
+}
+
+// Aggregator converting other tokens into the payment token
+pub fn set_aggregator_address(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::AggregatorAddress, &address);
+}
+
+pub fn get_aggregator_address(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::AggregatorAddress)
+}
+
+// Pricing, capacity and vesting controls
+pub fn set_terms(e: &Env, terms: &BondTerms) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Terms, terms);
+}
+
+pub fn get_terms(e: &Env) -> BondTerms {
+    e.storage()
+        .instance()
+        .get(&DataKey::Terms)
+        .unwrap()
+}
+
+// Ledger of the latest sale (the discount grows from there)
+pub fn set_last_sale_ledger(e: &Env, ledger: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::LastSaleLedger, &ledger);
+}
+
+pub fn get_last_sale_ledger(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::LastSaleLedger)
+        .unwrap()
+}
+
+// Shares sold so far, counted against the capacity
+pub fn set_total_sold(e: &Env, amount: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TotalSold, &amount);
+}
+
+pub fn get_total_sold(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::TotalSold)
+        .unwrap_or(0)
+}
+
+// Shares sold but not claimed yet, which the contract must keep in custody
+pub fn set_outstanding(e: &Env, amount: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Outstanding, &amount);
+}
+
+pub fn get_outstanding(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::Outstanding)
+        .unwrap_or(0)
+}
+
+// Vesting position of each buyer
+pub fn set_position(e: &Env, buyer: &Address, position: &BondPosition) {
+    let key = DataKey::Position(buyer.clone());
+    e.storage().persistent().set(&key, position);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_position(e: &Env, buyer: &Address) -> Option<BondPosition> {
+    let key = DataKey::Position(buyer.clone());
+    let position = e.storage().persistent().get(&key);
+    if position.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    position
+}
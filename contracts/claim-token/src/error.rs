@@ -0,0 +1,15 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ClaimTokenError {
+    /// Invalid parameters
+    InvalidArgument = 1,
+
+    NegativeNotAllowed = 2,
+    /// Transfer or burn above the holder's balance
+    InsufficientBalance = 3,
+    /// Spend above the spender's allowance
+    InsufficientAllowance = 4,
+}
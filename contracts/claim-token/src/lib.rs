@@ -0,0 +1,195 @@
+#![no_std]
+//! # Claim Token - Transferable Claims on Zap Deposits
+//!
+//! A plain SEP-41 token whose only minter is the admin, the `defindex-zap` contract. When a user
+//! zaps through `deposit_for_claim`, the zap keeps the minted vault shares and mints the same
+//! number of claims to the user. Claims move like any other token; whoever holds them can later
+//! call the zap's `redeem_claims` to burn them and receive the underlying asset of the shares.
+//!
+//! Transfers to a muxed address credit the underlying account; the muxed id is not recorded.
+
+use soroban_sdk::{
+    Address, Env, MuxedAddress, String, contract, contractimpl, panic_with_error, token::TokenInterface,
+};
+
+mod storage;
+mod error;
+mod events;
+
+use storage::{
+    extend_instance_ttl, get_admin, get_allowance, get_balance, get_metadata, get_total_supply,
+    set_admin, set_allowance, set_balance, set_metadata, set_total_supply, Allowance, Metadata,
+};
+use error::ClaimTokenError;
+use events::{Approve, Burn, Mint, Transfer};
+
+/// Panics unless the amount is non-negative
+fn check_nonnegative_amount(e: &Env, amount: i128) {
+    if amount < 0 {
+        panic_with_error!(e, ClaimTokenError::NegativeNotAllowed);
+    }
+}
+
+/// Moves `amount` from one balance to another
+fn move_balance(e: &Env, from: &Address, to: &Address, amount: i128) {
+    let from_balance = get_balance(e, from);
+    if from_balance < amount {
+        panic_with_error!(e, ClaimTokenError::InsufficientBalance);
+    }
+    set_balance(e, from, from_balance - amount);
+    set_balance(e, to, get_balance(e, to) + amount);
+}
+
+/// Removes `amount` from a balance and from the supply
+fn burn_balance(e: &Env, from: &Address, amount: i128) {
+    let balance = get_balance(e, from);
+    if balance < amount {
+        panic_with_error!(e, ClaimTokenError::InsufficientBalance);
+    }
+    set_balance(e, from, balance - amount);
+    set_total_supply(e, get_total_supply(e) - amount);
+}
+
+/// Consumes `amount` of the allowance `spender` holds over the tokens of `from`
+fn spend_allowance(e: &Env, from: &Address, spender: &Address, amount: i128) {
+    let allowance = get_allowance(e, from, spender);
+    if allowance.amount < amount {
+        panic_with_error!(e, ClaimTokenError::InsufficientAllowance);
+    }
+    if amount > 0 {
+        set_allowance(
+            e,
+            from,
+            spender,
+            &Allowance {
+                amount: allowance.amount - amount,
+                live_until_ledger: allowance.live_until_ledger,
+            },
+        );
+    }
+}
+
+#[contract]
+struct ClaimToken;
+
+#[contractimpl]
+impl ClaimToken {
+    /// Initialize the token
+    ///
+    /// ## Parameters:
+    /// - `admin`: The only account allowed to mint (the zap contract)
+    /// - `name`, `symbol`, `decimals`: SEP-41 metadata; `decimals` should match the vault shares
+    pub fn __constructor(e: Env, admin: Address, name: String, symbol: String, decimals: u32) {
+        set_admin(&e, admin);
+        set_metadata(&e, &Metadata { name, symbol, decimals });
+    }
+
+    /// Mint claims to `to` (admin only)
+    pub fn mint(e: Env, to: Address, amount: i128) {
+        get_admin(&e).require_auth();
+        check_nonnegative_amount(&e, amount);
+        extend_instance_ttl(&e);
+
+        set_balance(&e, &to, get_balance(&e, &to) + amount);
+        set_total_supply(&e, get_total_supply(&e) + amount);
+        Mint { to, amount }.publish(&e);
+    }
+
+    /// Claims in circulation
+    pub fn total_supply(e: Env) -> i128 {
+        get_total_supply(&e)
+    }
+
+    /// The minting account
+    pub fn admin(e: Env) -> Address {
+        get_admin(&e)
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for ClaimToken {
+    fn allowance(e: Env, from: Address, spender: Address) -> i128 {
+        get_allowance(&e, &from, &spender).amount
+    }
+
+    fn approve(e: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+        check_nonnegative_amount(&e, amount);
+        if amount > 0 && expiration_ledger < e.ledger().sequence() {
+            panic_with_error!(&e, ClaimTokenError::InvalidArgument);
+        }
+        extend_instance_ttl(&e);
+
+        set_allowance(
+            &e,
+            &from,
+            &spender,
+            &Allowance {
+                amount,
+                live_until_ledger: expiration_ledger,
+            },
+        );
+        Approve {
+            from,
+            spender,
+            amount,
+            live_until_ledger: expiration_ledger,
+        }
+        .publish(&e);
+    }
+
+    fn balance(e: Env, id: Address) -> i128 {
+        get_balance(&e, &id)
+    }
+
+    fn transfer(e: Env, from: Address, to: MuxedAddress, amount: i128) {
+        from.require_auth();
+        check_nonnegative_amount(&e, amount);
+        extend_instance_ttl(&e);
+
+        let to = to.address();
+        move_balance(&e, &from, &to, amount);
+        Transfer { from, to, amount }.publish(&e);
+    }
+
+    fn transfer_from(e: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        check_nonnegative_amount(&e, amount);
+        extend_instance_ttl(&e);
+
+        spend_allowance(&e, &from, &spender, amount);
+        move_balance(&e, &from, &to, amount);
+        Transfer { from, to, amount }.publish(&e);
+    }
+
+    fn burn(e: Env, from: Address, amount: i128) {
+        from.require_auth();
+        check_nonnegative_amount(&e, amount);
+        extend_instance_ttl(&e);
+
+        burn_balance(&e, &from, amount);
+        Burn { from, amount }.publish(&e);
+    }
+
+    fn burn_from(e: Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+        check_nonnegative_amount(&e, amount);
+        extend_instance_ttl(&e);
+
+        spend_allowance(&e, &from, &spender, amount);
+        burn_balance(&e, &from, amount);
+        Burn { from, amount }.publish(&e);
+    }
+
+    fn decimals(e: Env) -> u32 {
+        get_metadata(&e).decimals
+    }
+
+    fn name(e: Env) -> String {
+        get_metadata(&e).name
+    }
+
+    fn symbol(e: Env) -> String {
+        get_metadata(&e).symbol
+    }
+}
@@ -0,0 +1,132 @@
+use soroban_sdk::{contracttype, Address, Env, String};
+
+#[derive(Clone)]
+#[contracttype]
+
+enum DataKey {
+    Admin,
+    Metadata,
+    TotalSupply,
+    Balance(Address),
+    Allowance(Address, Address),
+}
+
+/// Token name, symbol and decimals, fixed at deployment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Metadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u32,
+}
+
+/// Spending allowance of `spender` over the tokens of `from`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Allowance {
+    pub amount: i128,
+    /// Last ledger sequence at which the allowance can be used
+    pub live_until_ledger: u32,
+}
+
+#[allow(dead_code)]
+const DAY_IN_LEDGERS: u32 = 17280;
+#[allow(dead_code)]
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+#[allow(dead_code)]
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+const PERSISTENT_BUMP_AMOUNT: u32 = 60 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+pub fn extend_instance_ttl(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Admin Address (the only minter)
+pub fn set_admin(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Admin, &address);
+}
+
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap()
+}
+
+// Token metadata
+pub fn set_metadata(e: &Env, metadata: &Metadata) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Metadata, metadata);
+}
+
+pub fn get_metadata(e: &Env) -> Metadata {
+    e.storage()
+        .instance()
+        .get(&DataKey::Metadata)
+        .unwrap()
+}
+
+// Tokens in circulation
+pub fn set_total_supply(e: &Env, amount: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TotalSupply, &amount);
+}
+
+pub fn get_total_supply(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::TotalSupply)
+        .unwrap_or(0)
+}
+
+// Balance of each holder
+pub fn set_balance(e: &Env, holder: &Address, amount: i128) {
+    let key = DataKey::Balance(holder.clone());
+    e.storage().persistent().set(&key, &amount);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_balance(e: &Env, holder: &Address) -> i128 {
+    let key = DataKey::Balance(holder.clone());
+    let amount = e.storage().persistent().get(&key);
+    if amount.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    amount.unwrap_or(0)
+}
+
+// Allowances, kept in temporary storage until they expire
+pub fn set_allowance(e: &Env, from: &Address, spender: &Address, allowance: &Allowance) {
+    let key = DataKey::Allowance(from.clone(), spender.clone());
+    e.storage().temporary().set(&key, allowance);
+    if allowance.amount > 0 {
+        let live_for = allowance.live_until_ledger - e.ledger().sequence();
+        e.storage().temporary().extend_ttl(&key, live_for, live_for);
+    }
+}
+
+/// Allowance still usable at the current ledger (zero once expired)
+pub fn get_allowance(e: &Env, from: &Address, spender: &Address) -> Allowance {
+    let key = DataKey::Allowance(from.clone(), spender.clone());
+    match e.storage().temporary().get::<_, Allowance>(&key) {
+        Some(allowance) if allowance.live_until_ledger >= e.ledger().sequence() => allowance,
+        _ => Allowance {
+            amount: 0,
+            live_until_ledger: 0,
+        },
+    }
+}
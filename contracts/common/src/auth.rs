@@ -0,0 +1,75 @@
+//! Fluent builder for invoker contract authorization entries
+//!
+//! Building `InvokerContractAuthEntry` / `SubContractInvocation` trees by hand is verbose and
+//! easy to get subtly wrong (argument order, missing nesting). `AuthBuilder` keeps the
+//! structure readable:
+//!
+//! ```ignore
+//! AuthBuilder::new(&e)
+//!     .transfer(&token, &e.current_contract_address(), &pair, amount)
+//!     .authorize();
+//! ```
+
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    Address, Env, IntoVal, Symbol, Val, Vec,
+};
+
+/// Collects the sub-contract invocations the current contract authorizes
+pub struct AuthBuilder {
+    e: Env,
+    entries: Vec<InvokerContractAuthEntry>,
+}
+
+impl AuthBuilder {
+    pub fn new(e: &Env) -> Self {
+        AuthBuilder {
+            e: e.clone(),
+            entries: Vec::new(e),
+        }
+    }
+
+    /// Authorizes `contract.fn_name(args)` with these exact arguments
+    pub fn call(mut self, contract: &Address, fn_name: &str, args: Vec<Val>) -> Self {
+        self.entries
+            .push_back(InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: contract.clone(),
+                    fn_name: Symbol::new(&self.e, fn_name),
+                    args,
+                },
+                sub_invocations: Vec::new(&self.e),
+            }));
+        self
+    }
+
+    /// Authorizes `token.transfer(from, to, amount)`
+    pub fn transfer(self, token: &Address, from: &Address, to: &Address, amount: i128) -> Self {
+        let args: Vec<Val> = (from.clone(), to.clone(), amount).into_val(&self.e);
+        self.call(token, "transfer", args)
+    }
+
+    /// Nests the entries of `sub` under the most recently added entry
+    ///
+    /// Use it when an authorized call itself makes calls that require this contract's
+    /// authorization (e.g. a router call that transfers this contract's tokens).
+    pub fn nested(mut self, sub: AuthBuilder) -> Self {
+        if let Some(InvokerContractAuthEntry::Contract(mut invocation)) = self.entries.pop_back() {
+            invocation.sub_invocations.append(&sub.entries);
+            self.entries
+                .push_back(InvokerContractAuthEntry::Contract(invocation));
+        }
+        self
+    }
+
+    /// Returns the collected entries
+    pub fn build(self) -> Vec<InvokerContractAuthEntry> {
+        self.entries
+    }
+
+    /// Authorizes the collected entries for the next cross-contract call
+    pub fn authorize(self) {
+        let e = self.e.clone();
+        e.authorize_as_current_contract(self.build());
+    }
+}
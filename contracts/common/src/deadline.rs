@@ -0,0 +1,38 @@
+//! Swap deadlines expressed in ledgers or as timestamps
+//!
+//! Entrypoints take an optional `deadline_delta`, a number of ledgers from now, and convert it
+//! into the absolute timestamp the Soroswap Router checks. Callers can also pass an absolute
+//! deadline, rejected up front once it has passed. Each contract maps these to its own errors:
+//!
+//! ```ignore
+//! if is_expired(&e, deadline) {
+//!     return Err(MyError::DeadlineExpired);
+//! }
+//! let deadline = resolve_deadline(&e, deadline_delta).ok_or(MyError::InvalidArgument)?;
+//! ```
+
+use soroban_sdk::Env;
+
+/// Approximate ledger close time, used to turn ledger counts into timestamps
+pub const SECONDS_PER_LEDGER: u64 = 5;
+
+/// Resolves the deadline passed to the Soroswap Router
+///
+/// `deadline_delta` is converted using the current ledger timestamp. When it is not provided
+/// the swap keeps the absolute `u64::MAX` deadline. `None` when the delta is zero.
+pub fn resolve_deadline(e: &Env, deadline_delta: Option<u32>) -> Option<u64> {
+    match deadline_delta {
+        Some(0) => None,
+        Some(delta) => Some(
+            e.ledger()
+                .timestamp()
+                .saturating_add(delta as u64 * SECONDS_PER_LEDGER),
+        ),
+        None => Some(u64::MAX),
+    }
+}
+
+/// Whether the absolute `deadline`, a ledger timestamp in seconds, has already passed
+pub fn is_expired(e: &Env, deadline: u64) -> bool {
+    deadline < e.ledger().timestamp()
+}
@@ -0,0 +1,23 @@
+#![no_std]
+//! # Common - Helpers Shared by the Workshop Contracts
+//!
+//! Code that every contract in the workspace would otherwise copy-paste lives here.
+//!
+//! ## Modules:
+//! - `auth`: Fluent builder for `authorize_as_current_contract` entries
+//! - `deadline`: Conversion of ledger-count swap deadlines into router timestamps
+//! - `math`: `Bps` basis-point type shared by every fee, slippage and weight parameter
+//! - `pay`: Pay-in-any-asset adapter routing the payer's token through the aggregator
+//! - `soroswap_pair`: Soroswap pair client with reserve reads and single-hop output math
+//! - `test_vectors`: Canonical auth entries and their XDR encodings for client-side validation
+//!   (behind the `testutils` feature)
+//! - `tutorial`: Workshop step constants and the reporter side of tutorial-mode recording
+
+pub mod auth;
+pub mod deadline;
+pub mod math;
+pub mod pay;
+pub mod soroswap_pair;
+#[cfg(any(test, feature = "testutils"))]
+pub mod test_vectors;
+pub mod tutorial;
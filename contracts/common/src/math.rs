@@ -0,0 +1,98 @@
+//! Basis-point arithmetic
+//!
+//! Fee, slippage, discount and weight parameters are fractions of 100% expressed in basis points.
+//! Passing them around as raw `u32` makes it easy to accept 12_000 bps somewhere; `Bps` keeps the
+//! bound next to the value:
+//!
+//! ```ignore
+//! const DEFAULT_PENALTY: Bps = Bps::from_const(500); // 50_000 fails to compile
+//!
+//! if !terms.discount.is_valid() {
+//!     return Err(MyError::InvalidArgument);
+//! }
+//! let discounted = terms.discount.complement().apply(price);
+//! ```
+//!
+//! Values decoded from contract arguments or storage are not checked by the decoding itself, so
+//! entrypoints still call `is_valid` on every `Bps` they are given.
+
+use soroban_sdk::contracttype;
+
+/// Basis points in 100%
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// A fraction of 100% in basis points, at most `BPS_DENOMINATOR`
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Bps(u32);
+
+impl Bps {
+    pub const ZERO: Bps = Bps(0);
+    pub const MAX: Bps = Bps(BPS_DENOMINATOR);
+
+    /// `value` bps, or `None` above 100%
+    pub fn new(value: u32) -> Option<Bps> {
+        if value <= BPS_DENOMINATOR {
+            Some(Bps(value))
+        } else {
+            None
+        }
+    }
+
+    /// `value` bps for constants; out-of-range values fail at compile time
+    pub const fn from_const(value: u32) -> Bps {
+        assert!(value <= BPS_DENOMINATOR, "basis points above 100%");
+        Bps(value)
+    }
+
+    /// Whether the value is at most 100%
+    pub fn is_valid(self) -> bool {
+        self.0 <= BPS_DENOMINATOR
+    }
+
+    /// Raw value in basis points
+    pub fn value(self) -> u32 {
+        self.0
+    }
+
+    /// `amount * self`, rounded down
+    pub fn apply(self, amount: i128) -> i128 {
+        amount * self.0 as i128 / BPS_DENOMINATOR as i128
+    }
+
+    /// `amount * self`, rounded down; `None` on overflow
+    pub fn checked_apply(self, amount: i128) -> Option<i128> {
+        amount
+            .checked_mul(self.0 as i128)
+            .map(|scaled| scaled / BPS_DENOMINATOR as i128)
+    }
+
+    /// `amount * self`, rounded up
+    pub fn apply_ceil(self, amount: i128) -> i128 {
+        (amount * self.0 as i128 + BPS_DENOMINATOR as i128 - 1) / BPS_DENOMINATOR as i128
+    }
+
+    /// 100% minus `self`
+    pub fn complement(self) -> Bps {
+        Bps(BPS_DENOMINATOR.saturating_sub(self.0))
+    }
+
+    /// `self + other`, or `None` above 100%
+    pub fn checked_add(self, other: Bps) -> Option<Bps> {
+        Bps::new(self.0.checked_add(other.0)?)
+    }
+
+    /// `self - other`, or `None` below 0%
+    pub fn checked_sub(self, other: Bps) -> Option<Bps> {
+        Some(Bps(self.0.checked_sub(other.0)?))
+    }
+
+    /// `amount` as a fraction of `total`, rounded down; `None` when `total` is not positive or
+    /// `amount` exceeds it
+    pub fn ratio(amount: i128, total: i128) -> Option<Bps> {
+        if total <= 0 || amount < 0 || amount > total {
+            return None;
+        }
+        Some(Bps((amount * BPS_DENOMINATOR as i128 / total) as u32))
+    }
+}
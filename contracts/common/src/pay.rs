@@ -0,0 +1,90 @@
+//! Pay-in-any-asset adapter
+//!
+//! Contracts that must be paid in a specific asset (bonds, subscriptions, loan repayments) can
+//! accept any token instead: the payer's token is first routed through the `soroswap-auth`
+//! aggregator into the required asset, then the contract settles exactly as before.
+//!
+//! ```ignore
+//! if convert_for_payment(&e, &aggregator, &payer, &token_in, &usdc, amount_in, price).is_none() {
+//!     return Err(MyError::PaymentConversionFailed);
+//! }
+//! usdc_client.transfer(&payer, &treasury, &price);
+//! ```
+//!
+//! The swap is made with the payer as the aggregator's `caller`, so the output lands in the
+//! payer's wallet and any excess over the required amount simply stays there. The payer's
+//! signature has to cover the aggregator call as a sub-invocation of the paying contract.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, Vec};
+
+/// What a `soroswap-auth` swap actually did
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapResult {
+    /// Amount of the first token of `path` the caller committed
+    pub amount_in: i128,
+    /// Amount of the last token of `path` delivered, net of `protocol_fee`
+    pub amount_out: i128,
+    pub path: Vec<Address>,
+    /// Pair of each hop
+    pub pairs: Vec<Address>,
+    /// Liquidity-provider fee of each hop, in the hop's input token (the output token on Phoenix
+    /// and Aquarius, whose pools take their fee from the output)
+    pub fees: Vec<i128>,
+    /// Protocol fee taken from the output, in the last token of `path`
+    pub protocol_fee: i128,
+    /// Router that executed the swap or, on exchanges swapped through a single pool, the pool
+    pub venue: Address,
+}
+
+// Interface of the `soroswap-auth` aggregator used for the conversion
+#[allow(dead_code)]
+#[contractclient(name = "AggregatorClient")]
+pub trait Aggregator {
+    #[allow(clippy::too_many_arguments)]
+    fn swap(
+        e: Env,
+        caller: Address,
+        token_in: Address,
+        token_out: Address,
+        amount: i128,
+        amount_out_min: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+        path: Option<Vec<Address>>,
+    ) -> SwapResult;
+}
+
+/// Converts `amount_in` of the payer's `token_in` into at least `required_amount` of `required_token`
+///
+/// Returns the amount of `required_token` the payer received, or `None` when it falls short
+/// of `required_amount`. When the payer already pays in `required_token` nothing is swapped.
+pub fn convert_for_payment(
+    e: &Env,
+    aggregator: &Address,
+    payer: &Address,
+    token_in: &Address,
+    required_token: &Address,
+    amount_in: i128,
+    required_amount: i128,
+) -> Option<i128> {
+    if token_in == required_token {
+        return Some(required_amount);
+    }
+
+    // The aggregator enforces `required_amount` on the venue itself, so a shortfall reverts the
+    // swap rather than leaving the payer holding a partial conversion
+    match AggregatorClient::new(e, aggregator).try_swap(
+        payer,
+        token_in,
+        required_token,
+        &amount_in,
+        &required_amount,
+        &None,
+        &None,
+        &None,
+    ) {
+        Ok(Ok(result)) => Some(result.amount_out),
+        _ => None,
+    }
+}
@@ -0,0 +1,60 @@
+//! Soroswap pair client and the reserve math built on it
+//!
+//! Reading reserves straight from a pair is cheaper than asking the router, and the output of a
+//! single hop can be computed locally with the router's own formula:
+//!
+//! ```ignore
+//! let reserves = reserves_toward(&e, &pair, &token_in)?;
+//! let expected = amount_out(amount, reserves.reserve_in, reserves.reserve_out)?;
+//! ```
+#![allow(clippy::too_many_arguments)]
+
+use soroban_sdk::{Address, Env};
+
+use crate::math::{Bps, BPS_DENOMINATOR};
+
+mod pair {
+    soroban_sdk::contractimport!(file = "../wasms/soroswap_pair.optimized.wasm");
+}
+
+pub type SoroswapPairClient<'a> = pair::Client<'a>;
+
+/// Liquidity-provider fee Soroswap pairs charge on each hop's input
+pub const SOROSWAP_FEE: Bps = Bps::from_const(30);
+
+/// Reserves of a pair seen from the token sold into it
+pub struct Reserves {
+    pub reserve_in: i128,
+    pub reserve_out: i128,
+    /// Whether the token sold is the pair's `token_0`, which decides the side of `swap` paid out
+    pub in_is_token_0: bool,
+}
+
+/// Reserves of `pair` toward swapping `token_in` into it
+///
+/// `None` when the pair cannot be read (archived, not a pair) or does not trade `token_in`.
+pub fn reserves_toward(e: &Env, pair: &Address, token_in: &Address) -> Option<Reserves> {
+    let pair_client = SoroswapPairClient::new(e, pair);
+    let (reserve_0, reserve_1) = pair_client.try_get_reserves().ok()?.ok()?;
+    if pair_client.try_token_0().ok()?.ok()? == *token_in {
+        Some(Reserves { reserve_in: reserve_0, reserve_out: reserve_1, in_is_token_0: true })
+    } else if pair_client.try_token_1().ok()?.ok()? == *token_in {
+        Some(Reserves { reserve_in: reserve_1, reserve_out: reserve_0, in_is_token_0: false })
+    } else {
+        None
+    }
+}
+
+/// Output of a Soroswap pair for `amount_in`, with its fee taken from the input, as the router
+/// computes it
+///
+/// `None` for an empty pool or on overflow.
+pub fn amount_out(amount_in: i128, reserve_in: i128, reserve_out: i128) -> Option<i128> {
+    if reserve_in <= 0 || reserve_out <= 0 {
+        return None;
+    }
+    let amount_in_with_fee = SOROSWAP_FEE.complement().value() as i128 * amount_in;
+    let numerator = amount_in_with_fee.checked_mul(reserve_out)?;
+    let denominator = reserve_in.checked_mul(BPS_DENOMINATOR as i128)?.checked_add(amount_in_with_fee)?;
+    Some(numerator / denominator)
+}
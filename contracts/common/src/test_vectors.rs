@@ -0,0 +1,115 @@
+//! Deterministic test vectors for auth-entry construction
+//!
+//! Each vector is the `AuthBuilder` output for fixed inputs. SDK and frontend teams can build
+//! the same structure client-side and compare its XDR (`ScVal`) encoding, hex-encoded, with the
+//! committed fixtures in `contracts/common/test_vectors/<name>.hex`; `cargo test -p common`
+//! checks that the vectors still match them. Compiled with the `testutils` feature only.
+//!
+//! ```ignore
+//! for vector in vectors(&e) {
+//!     let xdr = vector.xdr(&e);
+//!     // compare with test_vectors/<vector.name>.hex
+//! }
+//! ```
+
+use soroban_sdk::{
+    auth::InvokerContractAuthEntry, xdr::ToXdr, Address, Bytes, Env, IntoVal, Val, Vec,
+};
+
+use crate::auth::AuthBuilder;
+
+/// Contract authorizing the entries
+pub const AUTHORIZER: &str = "CB3OFRLI5TS7WHN4ELPG44P2NG47SIMEDLX4FIS5ZW27VBBJMMMVM4QK";
+/// Router the swap vector calls
+pub const ROUTER: &str = "CCMAPXWVZD4USEKDWRYS7DA4Y3D7E2SDMGBFJUCEXTC7VN6CUBGWPFUS";
+/// Pair receiving the swap input
+pub const PAIR: &str = "CDM7U3IQTUE65ZUFOBLV7NI46GYHNBSXAYAXJ3W3EK4Z7S2RE2EYIBDW";
+/// Token sold
+pub const TOKEN_IN: &str = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+/// Token bought
+pub const TOKEN_OUT: &str = "CDVPCLH7ISXWEAH4CQA7WWDZ3YKROPRGQGQXD4SINZT55L5LH2YMADVK";
+/// Account receiving the plain transfers
+pub const RECIPIENT: &str = "CDWEFYYHMGEZEFC5TBUDXM3IJJ7K7W5BDGE765UIYQEV4JFWDOLSTOEK";
+
+/// Amount used by every vector (1 token with 7 decimals)
+pub const AMOUNT: i128 = 10_000_000;
+/// Deadline passed to the router in the swap vector
+pub const DEADLINE: u64 = 1_700_000_000;
+
+/// Canonical auth entries for known inputs
+pub struct AuthVector {
+    pub name: &'static str,
+    pub entries: Vec<InvokerContractAuthEntry>,
+}
+
+impl AuthVector {
+    /// XDR encoding of the entries, as compared against the fixtures
+    pub fn xdr(&self, e: &Env) -> Bytes {
+        self.entries.clone().to_xdr(e)
+    }
+}
+
+/// Returns every vector, in fixture order
+pub fn vectors(e: &Env) -> [AuthVector; 3] {
+    let authorizer = Address::from_str(e, AUTHORIZER);
+    let router = Address::from_str(e, ROUTER);
+    let pair = Address::from_str(e, PAIR);
+    let token_in = Address::from_str(e, TOKEN_IN);
+    let token_out = Address::from_str(e, TOKEN_OUT);
+    let recipient = Address::from_str(e, RECIPIENT);
+
+    // Single `token.transfer` with no nesting
+    let single_transfer = AuthBuilder::new(e)
+        .transfer(&token_in, &authorizer, &recipient, AMOUNT)
+        .build();
+
+    // Two sibling transfers, in insertion order
+    let two_transfers = AuthBuilder::new(e)
+        .transfer(&token_in, &authorizer, &recipient, AMOUNT)
+        .transfer(&token_out, &authorizer, &recipient, AMOUNT)
+        .build();
+
+    // Router swap with the pair transfer nested under it
+    let path: Vec<Address> = Vec::from_array(e, [token_in.clone(), token_out]);
+    let swap_args: Vec<Val> =
+        (AMOUNT, 0_i128, path, authorizer.clone(), DEADLINE).into_val(e);
+    let router_swap = AuthBuilder::new(e)
+        .call(&router, "swap_exact_tokens_for_tokens", swap_args)
+        .nested(AuthBuilder::new(e).transfer(&token_in, &authorizer, &pair, AMOUNT))
+        .build();
+
+    [
+        AuthVector { name: "single_transfer", entries: single_transfer },
+        AuthVector { name: "two_transfers", entries: two_transfers },
+        AuthVector { name: "router_swap", entries: router_swap },
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use std::{format, string::String};
+
+    use soroban_sdk::Env;
+
+    use super::vectors;
+
+    fn fixture(name: &str) -> &'static str {
+        match name {
+            "single_transfer" => include_str!("../test_vectors/single_transfer.hex"),
+            "two_transfers" => include_str!("../test_vectors/two_transfers.hex"),
+            "router_swap" => include_str!("../test_vectors/router_swap.hex"),
+            _ => panic!("no fixture for {name}"),
+        }
+    }
+
+    #[test]
+    fn vectors_match_fixtures() {
+        let e = Env::default();
+        for vector in vectors(&e) {
+            let hex: String = vector.xdr(&e).iter().map(|byte| format!("{byte:02x}")).collect();
+            assert_eq!(hex, fixture(vector.name).trim(), "{} diverged from its fixture", vector.name);
+        }
+    }
+}
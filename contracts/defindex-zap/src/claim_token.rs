@@ -0,0 +1,9 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+// Interface of the `claim-token` contract this zap mints claims on
+#[allow(dead_code)]
+#[contractclient(name = "ClaimTokenClient")]
+pub trait ClaimToken {
+    fn mint(e: Env, to: Address, amount: i128);
+    fn burn(e: Env, from: Address, amount: i128);
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::too_many_arguments)]
+
 soroban_sdk::contractimport!(file = "../wasms/defindex_vault.wasm");
 
 #[allow(dead_code)]
@@ -17,5 +17,23 @@ pub enum DeFindexError {
     /// Invalid parameters
     InvalidArgument = 6,
 
-    NegativeNotAllowed= 7
+    NegativeNotAllowed= 7,
+    /// Caller has not accepted the current terms of use
+    TermsNotAccepted = 8,
+    /// Entrypoint disabled by the admin
+    FeatureDisabled = 9,
+    /// Output below the caller's minimum, or required input above the caller's maximum
+    SlippageExceeded = 10,
+    /// No sensitive governance change is pending
+    RageQuitUnavailable = 11,
+    /// The vault is scored above the caller's maximum risk, or not scored at all
+    RiskTooHigh = 12,
+    /// The owner has been active within their inactivity period
+    OwnerStillActive = 13,
+    /// No claim token is configured
+    ClaimsUnavailable = 14,
+    /// The admin paused the contract
+    ContractPaused = 15,
+    /// The caller's swap deadline has already passed
+    DeadlineExpired = 16,
 }
@@ -0,0 +1,122 @@
+use soroban_sdk::{contractevent, Address};
+
+/// Emitted when the admin publishes a new terms of use version
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TermsVersionUpdated {
+    pub version: u32,
+}
+
+/// Emitted when a user acknowledges a terms of use version
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TermsAccepted {
+    #[topic]
+    pub user: Address,
+    pub version: u32,
+}
+
+/// Emitted when the admin pauses or unpauses the contract
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseUpdated {
+    pub paused: bool,
+}
+
+/// Emitted when the admin changes the enabled features bitmask
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeaturesUpdated {
+    pub previous: u32,
+    pub features: u32,
+}
+
+/// Emitted when a user lets a delegate exit their position within limits
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawerAuthorized {
+    #[topic]
+    pub user: Address,
+    #[topic]
+    pub delegate: Address,
+    pub max_amount: i128,
+    pub expiry: u32,
+}
+
+/// Emitted when a user revokes a delegate
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawerRevoked {
+    #[topic]
+    pub user: Address,
+    #[topic]
+    pub delegate: Address,
+}
+
+/// Emitted when a user opens a savings bucket
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BucketCreated {
+    #[topic]
+    pub owner: Address,
+    pub id: u32,
+    pub unlock_ledger: u32,
+}
+
+/// Emitted when a savings bucket is withdrawn
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BucketWithdrawn {
+    #[topic]
+    pub owner: Address,
+    pub id: u32,
+    /// Vault shares paid out, rewards included
+    pub shares: i128,
+    /// Vault shares forfeited to the other buckets (0 once unlocked)
+    pub penalty: i128,
+}
+
+/// Emitted when a user designates (or replaces) their beneficiary
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BeneficiarySet {
+    #[topic]
+    pub user: Address,
+    pub beneficiary: Address,
+    pub inactivity_ledgers: u32,
+}
+
+/// Emitted when a beneficiary takes over an inactive user's positions
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InheritanceClaimed {
+    #[topic]
+    pub user: Address,
+    #[topic]
+    pub beneficiary: Address,
+    /// Vault shares moved from the user's wallet
+    pub shares: i128,
+    /// Savings buckets reassigned to the beneficiary
+    pub buckets: u32,
+}
+
+/// Emitted when a zap deposit is turned into transferable claims
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimsMinted {
+    #[topic]
+    pub owner: Address,
+    /// Vault shares held by this contract, and claims minted to the owner
+    pub shares: i128,
+}
+
+/// Emitted when claims are burned for the underlying asset of their shares
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimsRedeemed {
+    #[topic]
+    pub holder: Address,
+    pub claims: i128,
+    /// Underlying asset paid to the holder
+    pub amount: i128,
+}
@@ -0,0 +1,32 @@
+//! Per-entrypoint feature flags
+//!
+//! The enabled features are stored as a bitmask in instance storage. The admin can switch
+//! individual entrypoints off without pausing the whole contract.
+
+/// `deposit`
+pub const FEATURE_DEPOSIT: u32 = 1 << 0;
+/// `withdraw_exact_shares`
+pub const FEATURE_WITHDRAW_EXACT_SHARES: u32 = 1 << 1;
+/// `withdraw_exact_amount`
+pub const FEATURE_WITHDRAW_EXACT_AMOUNT: u32 = 1 << 2;
+
+/// `deposit_partial`
+pub const FEATURE_DEPOSIT_PARTIAL: u32 = 1 << 3;
+
+/// `withdraw_as_delegate`
+pub const FEATURE_WITHDRAW_AS_DELEGATE: u32 = 1 << 4;
+
+/// `create_bucket` and `deposit_to_bucket`
+pub const FEATURE_BUCKETS: u32 = 1 << 5;
+
+/// `deposit_for_claim`
+pub const FEATURE_CLAIMS: u32 = 1 << 6;
+
+/// Every known feature; the default when no mask has been stored yet
+pub const ALL_FEATURES: u32 = FEATURE_DEPOSIT
+    | FEATURE_WITHDRAW_EXACT_SHARES
+    | FEATURE_WITHDRAW_EXACT_AMOUNT
+    | FEATURE_DEPOSIT_PARTIAL
+    | FEATURE_WITHDRAW_AS_DELEGATE
+    | FEATURE_BUCKETS
+    | FEATURE_CLAIMS;
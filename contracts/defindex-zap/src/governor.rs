@@ -0,0 +1,8 @@
+use soroban_sdk::{contractclient, Env};
+
+// Interface of the `governor` contract that can open the rage-quit exit
+#[allow(dead_code)]
+#[contractclient(name = "GovernorClient")]
+pub trait Governor {
+    fn sensitive_change_pending(e: Env) -> bool;
+}
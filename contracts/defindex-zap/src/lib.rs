@@ -21,23 +21,79 @@
 //! The user's signature (`caller.require_auth()`) authorizes this contract to coordinate both
 //! the swap (via Soroswap Router) and the deposit (via DeFindex Vault) on their behalf. Since
 //! the contract never takes custody of tokens, no additional authorization context is required.
+//!
+//! The one exception is `withdraw_as_delegate`: the user is not signing, so this contract takes
+//! the vault shares through an allowance and redeems them itself before forwarding the proceeds.
+//!
+//! ## Savings Buckets:
+//! `create_bucket` opens a time-locked bucket and `deposit_to_bucket` zaps into it: the minted
+//! vault shares are held by this contract until the bucket unlocks. Withdrawing earlier forfeits
+//! a penalty (`bucket_penalty`, in basis points of the deposited shares) that is shared among the
+//! buckets still locked, pro-rata to their shares - a commitment-savings primitive.
+//!
+//! ## Beneficiaries:
+//! A user can designate a beneficiary with an inactivity period. Every operation the user signs
+//! here counts as activity; once the period passes without any, the beneficiary can claim the
+//! user's vault shares (through an allowance the user granted this contract) and savings buckets.
+//!
+//! ## Transferable Claims:
+//! `deposit_for_claim` zaps like `deposit` but keeps the minted vault shares here and mints the
+//! same number of `claim-token` claims to the user. The claims trade like any SEP-41 token, and
+//! whoever holds them can `redeem_claims` for the underlying asset of the shares.
 
+use common::{
+    deadline,
+    math::Bps,
+    tutorial::{record_step, STEP_WITHDRAW, STEP_ZAP},
+};
 use soroban_sdk::{
-    Address, Env, Vec, contract, contractimpl, vec
+    Address, Env, Vec, contract, contractimpl, token, vec
 };
 
+mod claim_token;
 mod defindex_vault;
+mod governor;
+mod risk_registry;
 mod soroswap_router;
 mod storage;
 mod error;
+mod events;
+mod features;
+mod types;
 
+use claim_token::ClaimTokenClient;
 use defindex_vault::DeFindexVaultClient;
+use governor::GovernorClient;
+use risk_registry::RiskRegistryClient;
 use soroswap_router::SoroswapRouterClient;
 use storage::{
-    extend_instance_ttl, get_vault_address, set_vault_address, get_soroswap_router_address, set_soroswap_router_address
+    extend_instance_ttl, get_vault_address, set_vault_address, get_soroswap_router_address, set_soroswap_router_address,
+    get_admin, set_admin, get_terms_version, set_terms_version, get_accepted_terms, set_accepted_terms,
+    get_receipts_enabled, set_receipts_enabled, get_receipt_count, set_receipt_count, get_receipt, set_receipt,
+    get_user_receipt_count, set_user_receipt_count, get_user_receipt, set_user_receipt, Receipt,
+    get_features, set_features, get_paused, set_paused, get_share_checkpoint_count, set_share_checkpoint_count,
+    get_share_checkpoint, set_share_checkpoint, ShareCheckpoint, get_governor, set_governor,
+    get_withdrawer, set_withdrawer, remove_withdrawer, WithdrawerAllowance,
+    get_risk_registry, set_risk_registry, get_max_risk, set_max_risk, remove_max_risk,
+    get_bucket, set_bucket, remove_bucket, get_bucket_count, set_bucket_count, get_bucket_penalty,
+    set_bucket_penalty, get_bucket_total_shares, set_bucket_total_shares,
+    get_bucket_reward_per_share, set_bucket_reward_per_share, Bucket,
+    get_beneficiary, set_beneficiary, remove_beneficiary, get_last_activity, set_last_activity,
+    remove_last_activity, BeneficiaryDesignation, get_claim_token, set_claim_token, get_tutorial,
+    set_tutorial,
 };
 use error::DeFindexError;
+use events::{
+    BeneficiarySet, BucketCreated, BucketWithdrawn, ClaimsMinted, ClaimsRedeemed, FeaturesUpdated,
+    InheritanceClaimed, PauseUpdated, TermsAccepted, TermsVersionUpdated, WithdrawerAuthorized,
+    WithdrawerRevoked,
+};
+use features::{
+    ALL_FEATURES, FEATURE_BUCKETS, FEATURE_CLAIMS, FEATURE_DEPOSIT, FEATURE_DEPOSIT_PARTIAL, FEATURE_WITHDRAW_AS_DELEGATE,
+    FEATURE_WITHDRAW_EXACT_AMOUNT, FEATURE_WITHDRAW_EXACT_SHARES,
+};
 
+use types::{DepositPreview, HistoryPage, StrategyBreakdown, VaultBreakdown, WithdrawPreview};
 use crate::storage::{get_underlying_asset_address, set_underlying_asset_address};
 
 /// Validates that the amount is non-negative
@@ -51,6 +107,293 @@ pub fn check_nonnegative_amount(amount: i128) -> Result<(), DeFindexError> {
     }
 }
 
+/// Largest page returned by the paginated views
+const MAX_PAGE_SIZE: u32 = 50;
+
+/// Fixed-point scale of the bucket reward-per-share accumulator
+const REWARD_PRECISION: i128 = 1_000_000_000_000;
+
+/// Resolves the deadline passed to the Soroswap Router (see `common::deadline`)
+///
+/// `deadline` is the caller's absolute bound and `deadline_delta` a relative one; when both are
+/// given the earlier applies. Fails with `DeadlineExpired` once `deadline` has passed.
+pub fn resolve_deadline(e: &Env, deadline: Option<u64>, deadline_delta: Option<u32>) -> Result<u64, DeFindexError> {
+    if deadline.is_some_and(|deadline| deadline::is_expired(e, deadline)) {
+        return Err(DeFindexError::DeadlineExpired);
+    }
+    let resolved = deadline::resolve_deadline(e, deadline_delta).ok_or(DeFindexError::InvalidArgument)?;
+    Ok(deadline.map_or(resolved, |deadline| deadline.min(resolved)))
+}
+
+/// Validates that the caller accepted the current terms of use
+///
+/// The gate is only active once the admin publishes a terms version (non-zero); bumping the
+/// version invalidates every earlier acknowledgment.
+pub fn check_terms_accepted(e: &Env, caller: &Address) -> Result<(), DeFindexError> {
+    let version = get_terms_version(e);
+    if version != 0 && get_accepted_terms(e, caller) != version {
+        Err(DeFindexError::TermsNotAccepted)
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates that the admin has not paused the contract
+pub fn check_not_paused(e: &Env) -> Result<(), DeFindexError> {
+    if get_paused(e) {
+        Err(DeFindexError::ContractPaused)
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates that the admin has not disabled the given entrypoint
+pub fn check_feature_enabled(e: &Env, feature: u32) -> Result<(), DeFindexError> {
+    if get_features(e) & feature == 0 {
+        Err(DeFindexError::FeatureDisabled)
+    } else {
+        Ok(())
+    }
+}
+
+/// Vault risk score according to the configured registry, if both exist
+fn vault_risk_score(e: &Env) -> Option<u32> {
+    let registry = get_risk_registry(e)?;
+    RiskRegistryClient::new(e, &registry).score(&get_vault_address(e))
+}
+
+/// Validates that the vault is within the caller's risk tolerance, when they set one
+///
+/// An unscored vault (or no registry at all) counts as exceeding any tolerance.
+pub fn check_risk_tolerance(e: &Env, caller: &Address) -> Result<(), DeFindexError> {
+    let Some(max_risk) = get_max_risk(e, caller) else {
+        return Ok(());
+    };
+    match vault_risk_score(e) {
+        Some(score) if score <= max_risk => Ok(()),
+        _ => Err(DeFindexError::RiskTooHigh),
+    }
+}
+
+/// Builds the direct swap path `token_in -> token_out`
+fn direct_path(e: &Env, token_in: &Address, token_out: &Address) -> Vec<Address> {
+    let mut path: Vec<Address> = Vec::new(e);
+    path.push_back(token_in.clone());
+    path.push_back(token_out.clone());
+    path
+}
+
+/// Swaps `token_in` to the underlying asset and deposits `vault_bps` of the output
+///
+/// Returns `(deposited, kept)`: the amount deposited into the vault and the amount of
+/// underlying asset left in the user's wallet.
+fn swap_and_deposit(
+    e: &Env,
+    caller: &Address,
+    token_in: &Address,
+    amount: i128,
+    vault_bps: Bps,
+    deadline: u64,
+) -> (i128, i128) {
+    // Get the vault's underlying asset (the target token for our swap)
+    let underlying_asset = get_underlying_asset_address(e);
+
+    // Step 1: Swap token_in → underlying_asset via Soroswap Router
+    let soroswap_router_address = get_soroswap_router_address(e);
+    let soroswap_router_client = SoroswapRouterClient::new(e, &soroswap_router_address);
+
+    // Build swap path (direct pair)
+    let mut path: Vec<Address> = Vec::new(e);
+    path.push_back(token_in.clone());
+    path.push_back(underlying_asset.clone());
+
+    // Execute swap - tokens go from user → pair → back to user (as underlying_asset)
+    // User's signature authorizes the router to transfer token_in from their account
+    let swap_result = soroswap_router_client.swap_exact_tokens_for_tokens(
+        &amount,     // Exact amount of token_in to swap
+        &0,          // Minimum amount out (0 for simplicity; use slippage calculation in production)
+        &path,       // Swap route: token_in → underlying_asset
+        caller,      // Recipient of swapped tokens (user receives underlying_asset)
+        &deadline,   // Deadline (max unless the caller provided `deadline_delta`)
+    );
+
+    // Get amount of underlying_asset received from swap
+    let total_swapped_amount = swap_result.last().unwrap();
+
+    // Only `vault_bps` of what the swap actually produced goes into the vault; the rest
+    // simply stays in the user's wallet
+    let deposited_amount = vault_bps.apply(total_swapped_amount);
+
+    // Step 2: Deposit the swapped underlying_asset into DeFindex vault
+    let defindex_vault_address = get_vault_address(e);
+    let defindex_vault_client = DeFindexVaultClient::new(e, &defindex_vault_address);
+
+    // Deposit into vault - user's signature authorizes vault to transfer from their account
+    defindex_vault_client.deposit(
+        &vec![e, deposited_amount],  // Amounts array (for multi-asset vaults; currently single-asset)
+        &vec![e, 0],                 // Minimum amounts out (slippage protection for multi-asset vaults)
+        caller,                      // Depositor (receives vault shares)
+        &false                       // invest: false = keep as idle in vault; true = invest into strategy
+    );
+
+    record_receipt(e, caller, token_in, &underlying_asset, amount, deposited_amount);
+    checkpoint_shares(e, caller);
+
+    (deposited_amount, total_swapped_amount - deposited_amount)
+}
+
+/// Converts an amount of the vault's underlying asset into the vault shares that redeem it
+///
+/// Rounds up so that withdrawing the returned shares yields at least `underlying_amount`.
+fn shares_for_underlying(e: &Env, underlying_amount: i128) -> Result<i128, DeFindexError> {
+    let (total_supply, total_managed) = vault_totals(e)?;
+    if total_managed <= 0 {
+        return Err(DeFindexError::InvalidArgument);
+    }
+
+    let numerator = underlying_amount
+        .checked_mul(total_supply)
+        .ok_or(DeFindexError::InvalidArgument)?;
+    Ok((numerator + total_managed - 1) / total_managed)
+}
+
+/// Vault shares `total_supply` and underlying asset `total_managed` of the vault
+fn vault_totals(e: &Env) -> Result<(i128, i128), DeFindexError> {
+    let defindex_vault_client = DeFindexVaultClient::new(e, &get_vault_address(e));
+    let total_managed = defindex_vault_client
+        .fetch_total_managed_funds()
+        .get(0)
+        .ok_or(DeFindexError::InvalidArgument)?
+        .total_amount;
+    Ok((defindex_vault_client.total_supply(), total_managed))
+}
+
+/// Underlying asset `shares` redeem for, and whether it was estimated from the vault totals
+///
+/// Prefers the vault's own `get_asset_amounts_per_shares`. Older deployments without it (or
+/// whose call fails) fall back to `shares * total_managed / total_supply`.
+fn underlying_for_shares(e: &Env, shares: i128) -> Result<(i128, bool), DeFindexError> {
+    let defindex_vault_client = DeFindexVaultClient::new(e, &get_vault_address(e));
+    if let Ok(Ok(amounts)) = defindex_vault_client.try_get_asset_amounts_per_shares(&shares) {
+        if let Some(underlying) = amounts.get(0) {
+            return Ok((underlying, false));
+        }
+    }
+
+    let (total_supply, total_managed) = vault_totals(e)?;
+    if total_supply <= 0 {
+        return Err(DeFindexError::InvalidArgument);
+    }
+    let numerator = shares
+        .checked_mul(total_managed)
+        .ok_or(DeFindexError::InvalidArgument)?;
+    Ok((numerator / total_supply, true))
+}
+
+/// Quotes `amount` of `token_in` in `token_out` on the router (1:1 when they match)
+fn quote_swap(e: &Env, token_in: &Address, token_out: &Address, amount: i128) -> Result<i128, DeFindexError> {
+    if token_in == token_out || amount == 0 {
+        return Ok(amount);
+    }
+    let soroswap_router_client = SoroswapRouterClient::new(e, &get_soroswap_router_address(e));
+    match soroswap_router_client.try_router_get_amounts_out(&amount, &direct_path(e, token_in, token_out)) {
+        Ok(Ok(amounts)) => amounts.last().ok_or(DeFindexError::SwapFailed),
+        _ => Err(DeFindexError::SwapFailed),
+    }
+}
+
+/// Records that `user` just operated, when they have a beneficiary watching for inactivity
+fn record_activity(e: &Env, user: &Address) {
+    if get_beneficiary(e, user).is_some() {
+        set_last_activity(e, user, e.ledger().sequence());
+    }
+}
+
+/// Accounts the penalty shares a bucket earned since it was last settled into `rewards`
+fn settle_bucket(e: &Env, bucket: &mut Bucket) {
+    let reward_per_share = get_bucket_reward_per_share(e);
+    bucket.rewards += bucket.shares * (reward_per_share - bucket.reward_per_share_paid) / REWARD_PRECISION;
+    bucket.reward_per_share_paid = reward_per_share;
+}
+
+/// Records the vault shares `user` holds right now as a checkpoint
+///
+/// A second checkpoint in the same ledger replaces the first, so each ledger has at most one.
+fn checkpoint_shares(e: &Env, user: &Address) {
+    let shares = DeFindexVaultClient::new(e, &get_vault_address(e)).balance(user);
+    let checkpoint = ShareCheckpoint {
+        ledger: e.ledger().sequence(),
+        shares,
+    };
+
+    let count = get_share_checkpoint_count(e, user);
+    if count > 0 && get_share_checkpoint(e, user, count - 1).ledger == checkpoint.ledger {
+        set_share_checkpoint(e, user, count - 1, &checkpoint);
+    } else {
+        set_share_checkpoint(e, user, count, &checkpoint);
+        set_share_checkpoint_count(e, user, count + 1);
+    }
+}
+
+/// Vault shares `user` held at the end of `ledger`, according to their checkpoints
+///
+/// Binary search for the last checkpoint at or before `ledger`; 0 before the first one.
+fn shares_at(e: &Env, user: &Address, ledger: u32) -> i128 {
+    let mut low: u32 = 0;
+    let mut high = get_share_checkpoint_count(e, user);
+    while low < high {
+        let mid = (low + high) / 2;
+        if get_share_checkpoint(e, user, mid).ledger <= ledger {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    if low == 0 {
+        0
+    } else {
+        get_share_checkpoint(e, user, low - 1).shares
+    }
+}
+
+/// Persists a receipt for an executed operation when receipts are enabled
+///
+/// The receipt is also appended to the caller's receipt index. Returns the receipt id, or
+/// `None` when receipt persistence is turned off.
+fn record_receipt(
+    e: &Env,
+    caller: &Address,
+    token_in: &Address,
+    token_out: &Address,
+    amount_in: i128,
+    amount_out: i128,
+) -> Option<u64> {
+    if !get_receipts_enabled(e) {
+        return None;
+    }
+
+    let id = get_receipt_count(e);
+    set_receipt(
+        e,
+        &Receipt {
+            id,
+            caller: caller.clone(),
+            token_in: token_in.clone(),
+            token_out: token_out.clone(),
+            amount_in,
+            amount_out,
+            ledger: e.ledger().sequence(),
+        },
+    );
+    set_receipt_count(e, id + 1);
+
+    let index = get_user_receipt_count(e, caller);
+    set_user_receipt(e, caller, index, id);
+    set_user_receipt_count(e, caller, index + 1);
+
+    Some(id)
+}
+
 #[contract]
 struct DeFindexSimple;
 
@@ -59,15 +402,171 @@ impl DeFindexSimple {
     /// Initialize the zapper contract with required addresses
     ///
     /// ## Parameters:
+    /// - `admin`: Address allowed to manage the contract configuration
     /// - `vault_address`: The DeFindex vault where deposits will be made
     /// - `router_address`: The Soroswap router used for token swaps
     /// - `underlying_asset`: The vault's underlying asset (target token for swaps)
-    pub fn __constructor(e: Env, vault_address: Address, router_address: Address, underlying_asset: Address) {
+    pub fn __constructor(e: Env, admin: Address, vault_address: Address, router_address: Address, underlying_asset: Address) {
+        set_admin(&e, admin);
         set_vault_address(&e, vault_address);
         set_soroswap_router_address(&e, router_address);
         set_underlying_asset_address(&e, underlying_asset);
     }
 
+    /// Publish a new terms of use version (admin only)
+    ///
+    /// Once a non-zero version is set, users must call `accept_terms` with that exact version
+    /// before their next operation. Setting `0` disables the gate.
+    pub fn set_terms_version(e: Env, version: u32) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_terms_version(&e, version);
+        TermsVersionUpdated { version }.publish(&e);
+    }
+
+    /// Acknowledge the current terms of use
+    ///
+    /// The accepted version is recorded in persistent storage as an on-chain acknowledgment record.
+    pub fn accept_terms(e: Env, caller: Address, version: u32) -> Result<(), DeFindexError> {
+        caller.require_auth();
+        record_activity(&e, &caller);
+        extend_instance_ttl(&e);
+        if version == 0 || version != get_terms_version(&e) {
+            return Err(DeFindexError::InvalidArgument);
+        }
+        set_accepted_terms(&e, &caller, version);
+        TermsAccepted { user: caller, version }.publish(&e);
+        Ok(())
+    }
+
+    /// Current terms of use version (`0` when no acknowledgment is required)
+    pub fn terms_version(e: Env) -> u32 {
+        get_terms_version(&e)
+    }
+
+    /// Terms of use version last accepted by `user` (`0` if never accepted)
+    pub fn accepted_terms(e: Env, user: Address) -> u32 {
+        get_accepted_terms(&e, &user)
+    }
+
+    /// Replace the enabled features bitmask (admin only)
+    ///
+    /// Clearing a bit disables the matching entrypoint without pausing the rest of the contract.
+    /// Unknown bits are rejected.
+    pub fn set_features(e: Env, features: u32) -> Result<(), DeFindexError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        if features & !ALL_FEATURES != 0 {
+            return Err(DeFindexError::InvalidArgument);
+        }
+        let previous = get_features(&e);
+        set_features(&e, features);
+        FeaturesUpdated { previous, features }.publish(&e);
+        Ok(())
+    }
+
+    /// Bitmask of the currently enabled features
+    pub fn features(e: Env) -> u32 {
+        get_features(&e)
+    }
+
+    /// Block every deposit entrypoint until `unpause` (admin only)
+    ///
+    /// Withdrawals stay open, so users can still exit while the contract is paused.
+    pub fn pause(e: Env) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_paused(&e, true);
+        PauseUpdated { paused: true }.publish(&e);
+    }
+
+    /// Re-enable the deposit entrypoints after `pause` (admin only)
+    pub fn unpause(e: Env) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_paused(&e, false);
+        PauseUpdated { paused: false }.publish(&e);
+    }
+
+    /// Whether the admin paused the contract
+    pub fn paused(e: Env) -> bool {
+        get_paused(&e)
+    }
+
+    /// Turn receipt persistence on or off (admin only)
+    pub fn set_receipts_enabled(e: Env, enabled: bool) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_receipts_enabled(&e, enabled);
+    }
+
+    /// Retrieve a stored receipt by id
+    pub fn get_receipt(e: Env, id: u64) -> Option<Receipt> {
+        get_receipt(&e, id)
+    }
+
+    /// List the receipt ids recorded for `user`, oldest first
+    ///
+    /// Returns at most `limit` ids starting at position `start` of the user's receipt index.
+    pub fn user_receipts(e: Env, user: Address, start: u32, limit: u32) -> Vec<u64> {
+        let end = get_user_receipt_count(&e, &user).min(start.saturating_add(limit));
+        let mut ids: Vec<u64> = Vec::new(&e);
+        for index in start..end {
+            if let Some(id) = get_user_receipt(&e, &user, index) {
+                ids.push_back(id);
+            }
+        }
+        ids
+    }
+
+    /// Number of receipts recorded for `user`, the length of their receipt index
+    pub fn user_receipt_count(e: Env, user: Address) -> u32 {
+        get_user_receipt_count(&e, &user)
+    }
+
+    /// Page through a user's receipts, newest first
+    ///
+    /// Pass `cursor = None` for the first page, then the returned `next_cursor` for each
+    /// following one until it comes back as `None`. At most `limit` receipts (capped at 50) are
+    /// returned per page. Meant for activity feeds rendered straight from simulation calls.
+    pub fn history(e: Env, user: Address, cursor: Option<u32>, limit: u32) -> HistoryPage {
+        let count = get_user_receipt_count(&e, &user);
+        // Positions at or above `end` are skipped: either newer than the cursor or not recorded
+        let end = match cursor {
+            Some(position) => position.saturating_add(1).min(count),
+            None => count,
+        };
+        let start = end.saturating_sub(limit.min(MAX_PAGE_SIZE));
+
+        let mut receipts: Vec<Receipt> = Vec::new(&e);
+        for index in (start..end).rev() {
+            if let Some(receipt) = get_user_receipt(&e, &user, index).and_then(|id| get_receipt(&e, id)) {
+                receipts.push_back(receipt);
+            }
+        }
+
+        HistoryPage {
+            receipts,
+            next_cursor: if start > 0 { Some(start - 1) } else { None },
+        }
+    }
+
+    /// Record the vault shares a user holds right now
+    ///
+    /// Zap deposits and withdrawals checkpoint automatically; shares moved outside the zap are
+    /// only seen once someone calls this. Permissionless, since it records the true balance.
+    pub fn checkpoint_shares(e: Env, user: Address) {
+        extend_instance_ttl(&e);
+        checkpoint_shares(&e, &user);
+    }
+
+    /// Vault shares a user held at the end of `ledger`, per their latest checkpoint at or before it
+    ///
+    /// Used by governance to snapshot voting power at a proposal's start ledger.
+    pub fn shares_at(e: Env, user: Address, ledger: u32) -> i128 {
+        shares_at(&e, &user, ledger)
+    }
+
     /// Zap: Swap any token to vault's underlying asset and deposit in one transaction
     ///
     /// ## What This Does:
@@ -92,53 +591,885 @@ impl DeFindexSimple {
     /// - `caller`: The user depositing (must sign the transaction)
     /// - `token_in`: The token user is depositing (will be swapped to underlying asset)
     /// - `amount`: Amount of `token_in` to swap and deposit
+    /// - `deadline`: Optional latest ledger timestamp at which the swap may execute
+    /// - `deadline_delta`: Optional swap deadline, in ledgers from now
     ///
     /// ## Returns:
     /// Amount of underlying asset deposited into the vault
-    pub fn deposit(e: Env, caller: Address, token_in: Address, amount: i128) -> Result<i128, DeFindexError> {
+    pub fn deposit(
+        e: Env,
+        caller: Address,
+        token_in: Address,
+        amount: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+    ) -> Result<i128, DeFindexError> {
         // Verify the caller has signed this transaction
         caller.require_auth();
+        record_activity(&e, &caller);
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_DEPOSIT)?;
         check_nonnegative_amount(amount)?;
+        check_terms_accepted(&e, &caller)?;
+        check_risk_tolerance(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
         extend_instance_ttl(&e);
 
-        // Get the vault's underlying asset (the target token for our swap)
+        let (deposited_amount, _) = swap_and_deposit(&e, &caller, &token_in, amount, Bps::MAX, deadline);
+        record_step(&e, &get_tutorial(&e), &caller, STEP_ZAP);
+
+        // Return the amount deposited
+        Ok(deposited_amount)
+    }
+
+    /// Estimate what `deposit(token_in, amount)` swaps to and the vault shares it is worth
+    ///
+    /// The swap is quoted on the router; shares are valued from the vault totals, rounding down.
+    /// An empty vault is valued 1:1. Also reports the vault's risk score, if scored.
+    pub fn preview_deposit(e: Env, token_in: Address, amount: i128) -> Result<DepositPreview, DeFindexError> {
+        check_nonnegative_amount(amount)?;
+        let underlying = quote_swap(&e, &token_in, &get_underlying_asset_address(&e), amount)?;
+
+        let (total_supply, total_managed) = vault_totals(&e)?;
+        let shares = if total_supply <= 0 || total_managed <= 0 {
+            underlying
+        } else {
+            underlying
+                .checked_mul(total_supply)
+                .ok_or(DeFindexError::InvalidArgument)?
+                / total_managed
+        };
+
+        Ok(DepositPreview {
+            underlying,
+            shares,
+            risk_score: vault_risk_score(&e),
+        })
+    }
+
+    /// Estimate what `withdraw_exact_shares(shares, token_out)` delivers
+    ///
+    /// Older vaults without `get_asset_amounts_per_shares` still get an estimate, computed from
+    /// the vault totals and flagged with `from_fallback`.
+    pub fn preview_withdraw(e: Env, shares: i128, token_out: Address) -> Result<WithdrawPreview, DeFindexError> {
+        check_nonnegative_amount(shares)?;
+        let (underlying, from_fallback) = underlying_for_shares(&e, shares)?;
+        let amount_out = quote_swap(&e, &get_underlying_asset_address(&e), &token_out, underlying)?;
+
+        Ok(WithdrawPreview {
+            underlying,
+            amount_out,
+            from_fallback,
+        })
+    }
+
+    /// Allocation of the vault's underlying asset across its strategies, plus the idle balance
+    ///
+    /// Normalizes the vault's `fetch_total_managed_funds` report for the underlying asset, so
+    /// frontends can show where zapped funds end up. Shares are fractions of the vault total,
+    /// rounded down; they are zero while the vault manages nothing.
+    pub fn vault_breakdown(e: Env) -> Result<VaultBreakdown, DeFindexError> {
+        let underlying_asset = get_underlying_asset_address(&e);
+        let allocation = DeFindexVaultClient::new(&e, &get_vault_address(&e))
+            .fetch_total_managed_funds()
+            .iter()
+            .find(|allocation| allocation.asset == underlying_asset)
+            .ok_or(DeFindexError::InvalidArgument)?;
+
+        let total = allocation.total_amount;
+        let share_of_total = |amount: i128| Bps::ratio(amount, total).unwrap_or(Bps::ZERO);
+        let mut strategies: Vec<StrategyBreakdown> = Vec::new(&e);
+        for strategy in allocation.strategy_allocations.iter() {
+            strategies.push_back(StrategyBreakdown {
+                strategy: strategy.strategy_address,
+                amount: strategy.amount,
+                share: share_of_total(strategy.amount),
+                paused: strategy.paused,
+            });
+        }
+
+        Ok(VaultBreakdown {
+            total,
+            idle: allocation.idle_amount,
+            idle_share: share_of_total(allocation.idle_amount),
+            strategies,
+        })
+    }
+
+    /// Zap only part of the swapped output into the vault, keeping the rest in the wallet
+    ///
+    /// Works like `deposit`, but only `vault_bps` (basis points, at most 10_000) of the underlying
+    /// asset ends up in the vault. The split is applied to what the swap actually produced, so
+    /// slippage affects both parts proportionally. Matches how users ladder into positions.
+    ///
+    /// ## Returns:
+    /// `(deposited, kept)`: underlying asset deposited into the vault and left in the wallet
+    pub fn deposit_partial(
+        e: Env,
+        caller: Address,
+        token_in: Address,
+        amount: i128,
+        vault_bps: Bps,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+    ) -> Result<(i128, i128), DeFindexError> {
+        caller.require_auth();
+        record_activity(&e, &caller);
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_DEPOSIT_PARTIAL)?;
+        check_nonnegative_amount(amount)?;
+        if vault_bps == Bps::ZERO || !vault_bps.is_valid() {
+            return Err(DeFindexError::InvalidArgument);
+        }
+        check_terms_accepted(&e, &caller)?;
+        check_risk_tolerance(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+
+        Ok(swap_and_deposit(&e, &caller, &token_in, amount, vault_bps, deadline))
+    }
+
+    /// Reverse zap: redeem an exact number of vault shares and receive `token_out`
+    ///
+    /// ## What This Does:
+    /// 1. Withdraws `shares` from the DeFindex vault (user receives the underlying asset)
+    /// 2. Swaps the underlying asset → `token_out` via Soroswap Router (skipped when they match)
+    /// 3. Fails with `SlippageExceeded` if the user receives less than `min_out`
+    ///
+    /// Covers the "exit everything" intent: pass the full share balance.
+    ///
+    /// ## Authorization Flow (Simple Proxy):
+    /// The user's signature authorizes the vault to burn their shares and the router to
+    /// transfer the underlying asset from their account, exactly like `deposit`.
+    ///
+    /// ## Token Flow:
+    /// ```
+    /// User (shares) → Vault → User (underlying_asset) → Router → Pair → User (token_out)
+    /// ```
+    ///
+    /// ## Returns:
+    /// Amount of `token_out` received
+    pub fn withdraw_exact_shares(
+        e: Env,
+        caller: Address,
+        shares: i128,
+        token_out: Address,
+        min_out: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+    ) -> Result<i128, DeFindexError> {
+        caller.require_auth();
+        record_activity(&e, &caller);
+        check_feature_enabled(&e, FEATURE_WITHDRAW_EXACT_SHARES)?;
+        check_nonnegative_amount(shares)?;
+        check_nonnegative_amount(min_out)?;
+        check_terms_accepted(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+
+        // Step 1: Burn the user's shares - the vault sends the underlying asset to the user
+        let defindex_vault_address = get_vault_address(&e);
+        let defindex_vault_client = DeFindexVaultClient::new(&e, &defindex_vault_address);
+        let withdrawn_amount = defindex_vault_client
+            .withdraw(&shares, &vec![&e, 0], &caller)
+            .get(0)
+            .unwrap();
+
+        // Step 2: Swap underlying_asset → token_out, unless the user wants the underlying asset
         let underlying_asset = get_underlying_asset_address(&e);
+        let amount_out = if token_out == underlying_asset {
+            withdrawn_amount
+        } else {
+            let soroswap_router_client = SoroswapRouterClient::new(&e, &get_soroswap_router_address(&e));
+            soroswap_router_client
+                .swap_exact_tokens_for_tokens(
+                    &withdrawn_amount,
+                    &0,
+                    &direct_path(&e, &underlying_asset, &token_out),
+                    &caller,
+                    &deadline,
+                )
+                .last()
+                .unwrap()
+        };
 
-        // Step 1: Swap token_in → underlying_asset via Soroswap Router
-        let soroswap_router_address = get_soroswap_router_address(&e);
-        let soroswap_router_client = SoroswapRouterClient::new(&e, &soroswap_router_address);
-
-        // Build swap path (direct pair)
-        let mut path: Vec<Address> = Vec::new(&e);
-        path.push_back(token_in.clone());
-        path.push_back(underlying_asset.clone());
-
-        // Execute swap - tokens go from user → pair → back to user (as underlying_asset)
-        // User's signature authorizes the router to transfer token_in from their account
-        let swap_result = soroswap_router_client.swap_exact_tokens_for_tokens(
-            &amount,     // Exact amount of token_in to swap
-            &0,          // Minimum amount out (0 for simplicity; use slippage calculation in production)
-            &path,       // Swap route: token_in → underlying_asset
-            &caller,     // Recipient of swapped tokens (user receives underlying_asset)
-            &u64::MAX,   // Deadline (max for simplicity; use actual timestamp in production)
+        // Checked after the swap: the whole call reverts if the user would get too little
+        if amount_out < min_out {
+            return Err(DeFindexError::SlippageExceeded);
+        }
+
+        record_receipt(&e, &caller, &defindex_vault_address, &token_out, shares, amount_out);
+        checkpoint_shares(&e, &caller);
+        record_step(&e, &get_tutorial(&e), &caller, STEP_WITHDRAW);
+
+        Ok(amount_out)
+    }
+
+    /// Reverse zap: receive exactly `target_amount_out` of `token_out`, burning as few shares as needed
+    ///
+    /// ## What This Does:
+    /// 1. Quotes how much underlying asset the router needs to output `target_amount_out`
+    /// 2. Converts that amount to vault shares using the vault's price per share (rounded up)
+    /// 3. Fails with `SlippageExceeded` if more than `max_shares` would be burned
+    /// 4. Withdraws the shares and swaps for exactly `target_amount_out`
+    ///
+    /// Covers the "I need exactly 500 USDC" intent. Any underlying asset left over after the
+    /// exact-output swap stays in the user's wallet.
+    ///
+    /// ## Returns:
+    /// Number of vault shares burned
+    pub fn withdraw_exact_amount(
+        e: Env,
+        caller: Address,
+        token_out: Address,
+        target_amount_out: i128,
+        max_shares: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+    ) -> Result<i128, DeFindexError> {
+        caller.require_auth();
+        record_activity(&e, &caller);
+        check_feature_enabled(&e, FEATURE_WITHDRAW_EXACT_AMOUNT)?;
+        check_nonnegative_amount(target_amount_out)?;
+        check_nonnegative_amount(max_shares)?;
+        check_terms_accepted(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+
+        let underlying_asset = get_underlying_asset_address(&e);
+        let soroswap_router_client = SoroswapRouterClient::new(&e, &get_soroswap_router_address(&e));
+        let path = direct_path(&e, &underlying_asset, &token_out);
+
+        // Step 1: How much underlying asset is needed to end up with target_amount_out
+        let underlying_needed = if token_out == underlying_asset {
+            target_amount_out
+        } else {
+            soroswap_router_client
+                .router_get_amounts_in(&target_amount_out, &path)
+                .get(0)
+                .unwrap()
+        };
+
+        // Step 2: Shares that redeem at least that much underlying asset
+        let shares = shares_for_underlying(&e, underlying_needed)?;
+        if shares > max_shares {
+            return Err(DeFindexError::SlippageExceeded);
+        }
+
+        // Step 3: Burn the shares - the vault sends the underlying asset to the user
+        let defindex_vault_address = get_vault_address(&e);
+        let defindex_vault_client = DeFindexVaultClient::new(&e, &defindex_vault_address);
+        let withdrawn_amount = defindex_vault_client
+            .withdraw(&shares, &vec![&e, 0], &caller)
+            .get(0)
+            .unwrap();
+
+        // Step 4: Swap for exactly target_amount_out, spending at most what was withdrawn
+        if token_out != underlying_asset {
+            soroswap_router_client.swap_tokens_for_exact_tokens(
+                &target_amount_out,
+                &withdrawn_amount,
+                &path,
+                &caller,
+                &deadline,
+            );
+        }
+
+        record_receipt(&e, &caller, &defindex_vault_address, &token_out, shares, target_amount_out);
+        checkpoint_shares(&e, &caller);
+        record_step(&e, &get_tutorial(&e), &caller, STEP_WITHDRAW);
+
+        Ok(shares)
+    }
+
+    /// Set the registry scoring the vault's risk (admin only)
+    pub fn set_risk_registry(e: Env, registry: Address) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_risk_registry(&e, &registry);
+    }
+
+    /// DeFindex vault the zap deposits into, whose shares it checkpoints
+    pub fn vault(e: Env) -> Address {
+        get_vault_address(&e)
+    }
+
+    /// Registry scoring the vault's risk, if any
+    pub fn risk_registry(e: Env) -> Option<Address> {
+        get_risk_registry(&e)
+    }
+
+    /// Current risk score of the vault, `None` when it is not scored or no registry is set
+    pub fn vault_risk_score(e: Env) -> Option<u32> {
+        vault_risk_score(&e)
+    }
+
+    /// Set the highest vault risk score the caller accepts for deposits
+    ///
+    /// While set, deposits fail with `RiskTooHigh` when the vault is scored above `score` or not
+    /// scored at all. `None` removes the limit. Withdrawals are never restricted.
+    pub fn set_max_risk(e: Env, caller: Address, score: Option<u32>) {
+        caller.require_auth();
+        record_activity(&e, &caller);
+        extend_instance_ttl(&e);
+        match score {
+            Some(score) => set_max_risk(&e, &caller, score),
+            None => remove_max_risk(&e, &caller),
+        }
+    }
+
+    /// Highest vault risk score a user accepts, if they set one
+    pub fn max_risk(e: Env, user: Address) -> Option<u32> {
+        get_max_risk(&e, &user)
+    }
+
+    /// Turn tutorial mode on by setting the `tutorial` contract, or off with `None` (admin only)
+    ///
+    /// While on, `deposit` and the exact withdrawals report the workshop steps their users
+    /// complete.
+    pub fn set_tutorial(e: Env, tutorial: Option<Address>) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_tutorial(&e, &tutorial);
+    }
+
+    /// Tutorial contract recording workshop steps, if tutorial mode is on
+    pub fn tutorial(e: Env) -> Option<Address> {
+        get_tutorial(&e)
+    }
+
+    /// Set the governor whose pending sensitive changes open `rage_quit` (admin only)
+    pub fn set_governor(e: Env, governor: Address) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_governor(&e, &governor);
+    }
+
+    /// Governor whose pending sensitive changes open `rage_quit`, if any
+    pub fn governor(e: Env) -> Option<Address> {
+        get_governor(&e)
+    }
+
+    /// Exit a vault position while a sensitive governance change waits in the timelock
+    ///
+    /// Redeems `shares` straight to the underlying asset - no swap, no fee - and is deliberately
+    /// exempt from the feature flags and the terms gate: the governance process cannot close
+    /// the exit it is supposed to guarantee. Only available while the configured governor
+    /// reports a pending sensitive change.
+    ///
+    /// ## Returns:
+    /// Amount of underlying asset received
+    pub fn rage_quit(e: Env, caller: Address, shares: i128) -> Result<i128, DeFindexError> {
+        caller.require_auth();
+        record_activity(&e, &caller);
+        check_nonnegative_amount(shares)?;
+        extend_instance_ttl(&e);
+
+        let governor = get_governor(&e).ok_or(DeFindexError::RageQuitUnavailable)?;
+        if !GovernorClient::new(&e, &governor).sensitive_change_pending() {
+            return Err(DeFindexError::RageQuitUnavailable);
+        }
+
+        let defindex_vault_address = get_vault_address(&e);
+        let withdrawn_amount = DeFindexVaultClient::new(&e, &defindex_vault_address)
+            .withdraw(&shares, &vec![&e, 0], &caller)
+            .get(0)
+            .unwrap();
+
+        record_receipt(&e, &caller, &defindex_vault_address, &get_underlying_asset_address(&e), shares, withdrawn_amount);
+        checkpoint_shares(&e, &caller);
+
+        Ok(withdrawn_amount)
+    }
+
+    /// Let `delegate` exit the caller's vault position within limits
+    ///
+    /// The delegate can redeem up to `max_amount` vault shares in total, until the `expiry`
+    /// ledger; the proceeds always go to the caller, never to the delegate. The shares are pulled
+    /// with `transfer_from`, so the caller also approves this contract as a spender of their
+    /// vault shares. Replaces any previous authorization of the same delegate.
+    pub fn authorize_withdrawer(
+        e: Env,
+        caller: Address,
+        delegate: Address,
+        max_amount: i128,
+        expiry: u32,
+    ) -> Result<(), DeFindexError> {
+        caller.require_auth();
+        record_activity(&e, &caller);
+        check_nonnegative_amount(max_amount)?;
+        if max_amount == 0 || delegate == caller || expiry < e.ledger().sequence() {
+            return Err(DeFindexError::InvalidArgument);
+        }
+        extend_instance_ttl(&e);
+
+        set_withdrawer(
+            &e,
+            &caller,
+            &delegate,
+            &WithdrawerAllowance {
+                remaining_shares: max_amount,
+                expiry,
+            },
         );
 
-        // Get amount of underlying_asset received from swap
-        let total_swapped_amount = swap_result.last().unwrap();
+        WithdrawerAuthorized {
+            user: caller,
+            delegate,
+            max_amount,
+            expiry,
+        }
+        .publish(&e);
+        Ok(())
+    }
 
-        // Step 2: Deposit the swapped underlying_asset into DeFindex vault
+    /// Revoke a delegate's withdrawal authorization
+    pub fn revoke_withdrawer(e: Env, caller: Address, delegate: Address) {
+        caller.require_auth();
+        record_activity(&e, &caller);
+        extend_instance_ttl(&e);
+        remove_withdrawer(&e, &caller, &delegate);
+        WithdrawerRevoked { user: caller, delegate }.publish(&e);
+    }
+
+    /// Remaining limits of a delegate over a user's position, if authorized (it may be expired)
+    pub fn withdrawer_allowance(e: Env, user: Address, delegate: Address) -> Option<WithdrawerAllowance> {
+        get_withdrawer(&e, &user, &delegate)
+    }
+
+    /// Redeem a user's vault shares as their authorized delegate
+    ///
+    /// The delegate signs instead of the user, so the vault cannot burn the user's shares
+    /// directly (it requires `from` to authorize). Instead this contract takes the shares with
+    /// `transfer_from`, withdraws them as their owner - as the direct invoker of the vault, its
+    /// own authorization is implicit - and forwards the underlying asset to the user.
+    ///
+    /// ## Returns:
+    /// Amount of underlying asset delivered to the user
+    pub fn withdraw_as_delegate(
+        e: Env,
+        delegate: Address,
+        user: Address,
+        shares: i128,
+        min_out: i128,
+    ) -> Result<i128, DeFindexError> {
+        delegate.require_auth();
+        check_feature_enabled(&e, FEATURE_WITHDRAW_AS_DELEGATE)?;
+        check_nonnegative_amount(shares)?;
+        check_nonnegative_amount(min_out)?;
+        check_terms_accepted(&e, &user)?;
+        extend_instance_ttl(&e);
+
+        let mut allowance = get_withdrawer(&e, &user, &delegate).ok_or(DeFindexError::Unauthorized)?;
+        if allowance.expiry < e.ledger().sequence() || shares > allowance.remaining_shares {
+            return Err(DeFindexError::Unauthorized);
+        }
+        allowance.remaining_shares -= shares;
+        set_withdrawer(&e, &user, &delegate, &allowance);
+
+        let this = e.current_contract_address();
         let defindex_vault_address = get_vault_address(&e);
         let defindex_vault_client = DeFindexVaultClient::new(&e, &defindex_vault_address);
+        defindex_vault_client.transfer_from(&this, &user, &this, &shares);
+        let withdrawn_amount = defindex_vault_client
+            .withdraw(&shares, &vec![&e, 0], &this)
+            .get(0)
+            .unwrap();
+        if withdrawn_amount < min_out {
+            return Err(DeFindexError::SlippageExceeded);
+        }
+
+        let underlying_asset = get_underlying_asset_address(&e);
+        token::Client::new(&e, &underlying_asset).transfer(&this, &user, &withdrawn_amount);
+
+        record_receipt(&e, &user, &defindex_vault_address, &underlying_asset, shares, withdrawn_amount);
+        checkpoint_shares(&e, &user);
 
-        // Deposit into vault - user's signature authorizes vault to transfer from their account
-        defindex_vault_client.deposit(
-            &vec![&e, total_swapped_amount],  // Amounts array (for multi-asset vaults; currently single-asset)
-            &vec![&e, 0],                      // Minimum amounts out (slippage protection for multi-asset vaults)
-            &caller,                            // Depositor (receives vault shares)
-            &false                              // invest: false = keep as idle in vault; true = invest into strategy
+        Ok(withdrawn_amount)
+    }
+
+    /// Set the early-withdrawal penalty of savings buckets, in basis points (admin only)
+    pub fn set_bucket_penalty(e: Env, bps: Bps) -> Result<(), DeFindexError> {
+        get_admin(&e).require_auth();
+        if bps >= Bps::MAX {
+            return Err(DeFindexError::InvalidArgument);
+        }
+        extend_instance_ttl(&e);
+        set_bucket_penalty(&e, bps);
+        Ok(())
+    }
+
+    /// Early-withdrawal penalty of savings buckets, in basis points
+    pub fn bucket_penalty(e: Env) -> Bps {
+        get_bucket_penalty(&e)
+    }
+
+    /// Open an empty savings bucket locked until `unlock_ledger`
+    ///
+    /// ## Returns:
+    /// Id of the new bucket
+    pub fn create_bucket(e: Env, caller: Address, unlock_ledger: u32) -> Result<u32, DeFindexError> {
+        caller.require_auth();
+        record_activity(&e, &caller);
+        check_feature_enabled(&e, FEATURE_BUCKETS)?;
+        check_terms_accepted(&e, &caller)?;
+        if unlock_ledger <= e.ledger().sequence() {
+            return Err(DeFindexError::InvalidArgument);
+        }
+        extend_instance_ttl(&e);
+
+        let id = get_bucket_count(&e);
+        set_bucket(
+            &e,
+            &Bucket {
+                id,
+                owner: caller.clone(),
+                unlock_ledger,
+                shares: 0,
+                reward_per_share_paid: get_bucket_reward_per_share(&e),
+                rewards: 0,
+            },
         );
+        set_bucket_count(&e, id + 1);
 
-        // Return the amount deposited
-        Ok(total_swapped_amount)
+        BucketCreated {
+            owner: caller,
+            id,
+            unlock_ledger,
+        }
+        .publish(&e);
+        Ok(id)
+    }
+
+    /// Zap `token_in` into the vault and lock the minted shares in one of the caller's buckets
+    ///
+    /// Works like `deposit`; the caller's signature also authorizes moving the minted shares
+    /// into this contract's custody.
+    ///
+    /// ## Returns:
+    /// Vault shares added to the bucket
+    pub fn deposit_to_bucket(
+        e: Env,
+        caller: Address,
+        bucket_id: u32,
+        token_in: Address,
+        amount: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+    ) -> Result<i128, DeFindexError> {
+        caller.require_auth();
+        record_activity(&e, &caller);
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_BUCKETS)?;
+        check_nonnegative_amount(amount)?;
+        check_terms_accepted(&e, &caller)?;
+        check_risk_tolerance(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+
+        let mut bucket = get_bucket(&e, bucket_id).ok_or(DeFindexError::InvalidArgument)?;
+        if bucket.owner != caller {
+            return Err(DeFindexError::Unauthorized);
+        }
+
+        let defindex_vault_client = DeFindexVaultClient::new(&e, &get_vault_address(&e));
+        let shares_before = defindex_vault_client.balance(&caller);
+        swap_and_deposit(&e, &caller, &token_in, amount, Bps::MAX, deadline);
+        let shares = defindex_vault_client.balance(&caller) - shares_before;
+        defindex_vault_client.transfer(&caller, &e.current_contract_address(), &shares);
+
+        settle_bucket(&e, &mut bucket);
+        bucket.shares += shares;
+        set_bucket(&e, &bucket);
+        set_bucket_total_shares(&e, get_bucket_total_shares(&e) + shares);
+        checkpoint_shares(&e, &caller);
+
+        Ok(shares)
+    }
+
+    /// Close one of the caller's buckets and receive its vault shares
+    ///
+    /// From `unlock_ledger` on, the bucket pays out its shares plus the penalty rewards it earned.
+    /// Before that, `bucket_penalty` of the deposited shares is forfeited to the buckets still
+    /// holding shares (or to the admin when there are none); earned rewards are always paid out
+    /// in full. Stays available when the bucket features are switched off.
+    ///
+    /// ## Returns:
+    /// Vault shares transferred to the caller
+    pub fn withdraw_bucket(e: Env, caller: Address, bucket_id: u32) -> Result<i128, DeFindexError> {
+        caller.require_auth();
+        record_activity(&e, &caller);
+        extend_instance_ttl(&e);
+
+        let mut bucket = get_bucket(&e, bucket_id).ok_or(DeFindexError::InvalidArgument)?;
+        if bucket.owner != caller {
+            return Err(DeFindexError::Unauthorized);
+        }
+        settle_bucket(&e, &mut bucket);
+        remove_bucket(&e, bucket_id);
+
+        let remaining_shares = get_bucket_total_shares(&e) - bucket.shares;
+        set_bucket_total_shares(&e, remaining_shares);
+
+        let defindex_vault_client = DeFindexVaultClient::new(&e, &get_vault_address(&e));
+        let this = e.current_contract_address();
+        let penalty = if e.ledger().sequence() < bucket.unlock_ledger {
+            get_bucket_penalty(&e).apply(bucket.shares)
+        } else {
+            0
+        };
+        if penalty > 0 {
+            if remaining_shares > 0 {
+                set_bucket_reward_per_share(
+                    &e,
+                    get_bucket_reward_per_share(&e) + penalty * REWARD_PRECISION / remaining_shares,
+                );
+            } else {
+                defindex_vault_client.transfer(&this, &get_admin(&e), &penalty);
+            }
+        }
+
+        let shares = bucket.shares - penalty + bucket.rewards;
+        if shares > 0 {
+            defindex_vault_client.transfer(&this, &caller, &shares);
+        }
+        checkpoint_shares(&e, &caller);
+
+        BucketWithdrawn {
+            owner: caller,
+            id: bucket_id,
+            shares,
+            penalty,
+        }
+        .publish(&e);
+        Ok(shares)
+    }
+
+    /// Savings bucket by id, with the penalty rewards earned so far included in `rewards`
+    pub fn bucket(e: Env, bucket_id: u32) -> Option<Bucket> {
+        let mut bucket = get_bucket(&e, bucket_id)?;
+        settle_bucket(&e, &mut bucket);
+        Some(bucket)
+    }
+
+    /// Designate the account that can take over the caller's positions after inactivity
+    ///
+    /// For the vault shares in the caller's wallet the caller must also grant this contract a
+    /// vault share allowance (`approve`) lasting as long as the designation should.
+    ///
+    /// ## Parameters:
+    /// - `caller`: The user designating (must sign the transaction)
+    /// - `beneficiary`: Account that can claim once the caller is inactive
+    /// - `inactivity_ledgers`: Ledgers without any operation after which claiming opens
+    pub fn set_beneficiary(
+        e: Env,
+        caller: Address,
+        beneficiary: Address,
+        inactivity_ledgers: u32,
+    ) -> Result<(), DeFindexError> {
+        caller.require_auth();
+        if inactivity_ledgers == 0 || beneficiary == caller {
+            return Err(DeFindexError::InvalidArgument);
+        }
+        extend_instance_ttl(&e);
+
+        set_beneficiary(
+            &e,
+            &caller,
+            &BeneficiaryDesignation {
+                beneficiary: beneficiary.clone(),
+                inactivity_ledgers,
+            },
+        );
+        record_activity(&e, &caller);
+
+        BeneficiarySet {
+            user: caller,
+            beneficiary,
+            inactivity_ledgers,
+        }
+        .publish(&e);
+        Ok(())
+    }
+
+    /// Remove the caller's beneficiary designation
+    pub fn remove_beneficiary(e: Env, caller: Address) {
+        caller.require_auth();
+        extend_instance_ttl(&e);
+        remove_beneficiary(&e, &caller);
+        remove_last_activity(&e, &caller);
+    }
+
+    /// Prove the caller is still active without doing anything else
+    pub fn heartbeat(e: Env, caller: Address) {
+        caller.require_auth();
+        record_activity(&e, &caller);
+    }
+
+    /// Beneficiary designation of a user, if any
+    pub fn beneficiary(e: Env, user: Address) -> Option<BeneficiaryDesignation> {
+        get_beneficiary(&e, &user)
+    }
+
+    /// Ledger of the last operation of a user with a beneficiary, if any
+    pub fn last_activity(e: Env, user: Address) -> Option<u32> {
+        get_last_activity(&e, &user)
+    }
+
+    /// Take over the positions of an inactive user who designated the caller
+    ///
+    /// Moves as many of the user's vault shares as both their balance and their allowance to
+    /// this contract cover, and reassigns the listed savings buckets (which keep their lock).
+    /// The designation is consumed.
+    ///
+    /// ## Parameters:
+    /// - `beneficiary`: The designated account (must sign the transaction)
+    /// - `user`: The inactive user
+    /// - `bucket_ids`: Savings buckets of the user to reassign
+    ///
+    /// ## Returns:
+    /// Vault shares moved from the user's wallet
+    pub fn claim_inheritance(
+        e: Env,
+        beneficiary: Address,
+        user: Address,
+        bucket_ids: Vec<u32>,
+    ) -> Result<i128, DeFindexError> {
+        beneficiary.require_auth();
+        extend_instance_ttl(&e);
+
+        let designation = get_beneficiary(&e, &user).ok_or(DeFindexError::Unauthorized)?;
+        if designation.beneficiary != beneficiary {
+            return Err(DeFindexError::Unauthorized);
+        }
+        let last_activity = get_last_activity(&e, &user).unwrap_or(0);
+        if e.ledger().sequence() < last_activity.saturating_add(designation.inactivity_ledgers) {
+            return Err(DeFindexError::OwnerStillActive);
+        }
+        remove_beneficiary(&e, &user);
+        remove_last_activity(&e, &user);
+
+        let this = e.current_contract_address();
+        let defindex_vault_client = DeFindexVaultClient::new(&e, &get_vault_address(&e));
+        let shares = defindex_vault_client
+            .balance(&user)
+            .min(defindex_vault_client.allowance(&user, &this));
+        if shares > 0 {
+            defindex_vault_client.transfer_from(&this, &user, &beneficiary, &shares);
+        }
+
+        for bucket_id in bucket_ids.iter() {
+            let mut bucket = get_bucket(&e, bucket_id).ok_or(DeFindexError::InvalidArgument)?;
+            if bucket.owner != user {
+                return Err(DeFindexError::Unauthorized);
+            }
+            bucket.owner = beneficiary.clone();
+            set_bucket(&e, &bucket);
+        }
+
+        checkpoint_shares(&e, &user);
+        checkpoint_shares(&e, &beneficiary);
+
+        InheritanceClaimed {
+            user,
+            beneficiary,
+            shares,
+            buckets: bucket_ids.len(),
+        }
+        .publish(&e);
+        Ok(shares)
+    }
+
+    /// Set the claim token minted by `deposit_for_claim` (admin only)
+    ///
+    /// The token's admin must be this contract, so that only it can mint claims.
+    pub fn set_claim_token(e: Env, claim_token: Address) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_claim_token(&e, &claim_token);
+    }
+
+    /// Claim token minted by `deposit_for_claim`, if configured
+    pub fn claim_token(e: Env) -> Option<Address> {
+        get_claim_token(&e)
+    }
+
+    /// Zap `token_in` into the vault and receive transferable claims instead of vault shares
+    ///
+    /// Works like `deposit`; the caller's signature also authorizes moving the minted shares
+    /// into this contract's custody. One claim is minted per vault share held.
+    ///
+    /// ## Returns:
+    /// Vault shares held, equal to the claims minted to the caller
+    pub fn deposit_for_claim(
+        e: Env,
+        caller: Address,
+        token_in: Address,
+        amount: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+    ) -> Result<i128, DeFindexError> {
+        caller.require_auth();
+        record_activity(&e, &caller);
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_CLAIMS)?;
+        check_nonnegative_amount(amount)?;
+        check_terms_accepted(&e, &caller)?;
+        check_risk_tolerance(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+
+        let claim_token = get_claim_token(&e).ok_or(DeFindexError::ClaimsUnavailable)?;
+        let defindex_vault_client = DeFindexVaultClient::new(&e, &get_vault_address(&e));
+        let shares_before = defindex_vault_client.balance(&caller);
+        swap_and_deposit(&e, &caller, &token_in, amount, Bps::MAX, deadline);
+        let shares = defindex_vault_client.balance(&caller) - shares_before;
+        defindex_vault_client.transfer(&caller, &e.current_contract_address(), &shares);
+
+        ClaimTokenClient::new(&e, &claim_token).mint(&caller, &shares);
+        checkpoint_shares(&e, &caller);
+
+        ClaimsMinted { owner: caller, shares }.publish(&e);
+        Ok(shares)
+    }
+
+    /// Burn claims and receive the underlying asset of the vault shares backing them
+    ///
+    /// Open to any holder, not only the original depositor, and available even when
+    /// `deposit_for_claim` is switched off. The holder's signature authorizes the burn.
+    ///
+    /// ## Returns:
+    /// Amount of underlying asset received
+    pub fn redeem_claims(e: Env, holder: Address, claims: i128, min_out: i128) -> Result<i128, DeFindexError> {
+        holder.require_auth();
+        record_activity(&e, &holder);
+        check_nonnegative_amount(claims)?;
+        check_nonnegative_amount(min_out)?;
+        extend_instance_ttl(&e);
+
+        let claim_token = get_claim_token(&e).ok_or(DeFindexError::ClaimsUnavailable)?;
+        ClaimTokenClient::new(&e, &claim_token).burn(&holder, &claims);
+
+        let this = e.current_contract_address();
+        let defindex_vault_address = get_vault_address(&e);
+        let withdrawn_amount = DeFindexVaultClient::new(&e, &defindex_vault_address)
+            .withdraw(&claims, &vec![&e, 0], &this)
+            .get(0)
+            .unwrap();
+        if withdrawn_amount < min_out {
+            return Err(DeFindexError::SlippageExceeded);
+        }
+
+        let underlying_asset = get_underlying_asset_address(&e);
+        token::Client::new(&e, &underlying_asset).transfer(&this, &holder, &withdrawn_amount);
+        record_receipt(&e, &holder, &defindex_vault_address, &underlying_asset, claims, withdrawn_amount);
+
+        ClaimsRedeemed {
+            holder,
+            claims,
+            amount: withdrawn_amount,
+        }
+        .publish(&e);
+        Ok(withdrawn_amount)
     }
 }
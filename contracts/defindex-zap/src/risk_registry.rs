@@ -0,0 +1,8 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+// Interface of the `risk-registry` contract scoring the vault
+#[allow(dead_code)]
+#[contractclient(name = "RiskRegistryClient")]
+pub trait RiskRegistry {
+    fn score(e: Env, vault: Address) -> Option<u32>;
+}
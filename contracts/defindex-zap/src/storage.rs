@@ -1,14 +1,101 @@
+use common::math::Bps;
 use soroban_sdk::{contracttype, Address, Env};
 
+use crate::features::ALL_FEATURES;
+
 #[derive(Clone)]
 #[contracttype]
 
 enum DataKey {
     DeFindexVaultAddress,
     SoroswapRouterAddress,
-    UnderlyingAsset
+    UnderlyingAsset,
+    Admin,
+    TermsVersion,
+    AcceptedTerms(Address),
+    ReceiptsEnabled,
+    ReceiptCount,
+    Receipt(u64),
+    UserReceiptCount(Address),
+    UserReceipt(Address, u32),
+    Features,
+    Paused,
+    ShareCheckpointCount(Address),
+    ShareCheckpoint(Address, u32),
+    Governor,
+    Withdrawer(Address, Address),
+    RiskRegistry,
+    Tutorial,
+    MaxRisk(Address),
+    BucketPenalty,
+    BucketCount,
+    Bucket(u32),
+    BucketTotalShares,
+    BucketRewardPerShare,
+    Beneficiary(Address),
+    LastActivity(Address),
+    ClaimToken,
+}
+
+/// Compact, retrievable record of an executed operation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Receipt {
+    pub id: u64,
+    pub caller: Address,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+    pub ledger: u32,
+}
+
+/// Vault shares a user held as of a ledger
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShareCheckpoint {
+    pub ledger: u32,
+    pub shares: i128,
+}
+
+/// Limits within which a delegate may exit a user's position
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawerAllowance {
+    /// Vault shares the delegate can still redeem
+    pub remaining_shares: i128,
+    /// Last ledger sequence at which the delegate can act
+    pub expiry: u32,
+}
+
+/// Time-locked savings bucket holding vault shares in this contract's custody
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bucket {
+    pub id: u32,
+    pub owner: Address,
+    /// First ledger sequence at which the bucket can be withdrawn without penalty
+    pub unlock_ledger: u32,
+    /// Vault shares deposited
+    pub shares: i128,
+    /// Reward-per-share already accounted into `rewards`
+    pub reward_per_share_paid: i128,
+    /// Vault shares earned from early-withdrawal penalties
+    pub rewards: i128,
 }
 
+/// Account that can take over a user's positions once the user goes inactive
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BeneficiaryDesignation {
+    pub beneficiary: Address,
+    /// Ledgers without any operation of the user after which the beneficiary can claim
+    pub inactivity_ledgers: u32,
+}
+
+/// Default early-withdrawal penalty, in basis points
+const DEFAULT_BUCKET_PENALTY_BPS: Bps = Bps::from_const(500);
+
 #[allow(dead_code)]
 const DAY_IN_LEDGERS: u32 = 17280;
 #[allow(dead_code)]
@@ -16,12 +103,21 @@ const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
 #[allow(dead_code)]
 const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
 
+const PERSISTENT_BUMP_AMOUNT: u32 = 60 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
 pub fn extend_instance_ttl(e: &Env) {
     e.storage()
         .instance()
         .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 }
 
+fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
 // DeFindex Vault Address
 pub fn set_vault_address(e: &Env, address: Address) {
     e.storage()
@@ -63,3 +159,390 @@ pub fn get_underlying_asset_address(e: &Env) -> Address {
         .get(&DataKey::UnderlyingAsset)
         .unwrap()
 }
+
+// Admin Address
+pub fn set_admin(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Admin, &address);
+}
+
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap()
+}
+
+// Terms of use version (0 means no acknowledgment is required)
+pub fn set_terms_version(e: &Env, version: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TermsVersion, &version);
+}
+
+pub fn get_terms_version(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::TermsVersion)
+        .unwrap_or(0)
+}
+
+// Terms version accepted by each user
+pub fn set_accepted_terms(e: &Env, user: &Address, version: u32) {
+    let key = DataKey::AcceptedTerms(user.clone());
+    e.storage().persistent().set(&key, &version);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_accepted_terms(e: &Env, user: &Address) -> u32 {
+    let key = DataKey::AcceptedTerms(user.clone());
+    let version = e.storage().persistent().get(&key);
+    if version.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    version.unwrap_or(0)
+}
+
+// Receipt persistence toggle
+pub fn set_receipts_enabled(e: &Env, enabled: bool) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ReceiptsEnabled, &enabled);
+}
+
+pub fn get_receipts_enabled(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&DataKey::ReceiptsEnabled)
+        .unwrap_or(false)
+}
+
+// Receipts (ids are sequential, starting at 0)
+pub fn set_receipt_count(e: &Env, count: u64) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ReceiptCount, &count);
+}
+
+pub fn get_receipt_count(e: &Env) -> u64 {
+    e.storage()
+        .instance()
+        .get(&DataKey::ReceiptCount)
+        .unwrap_or(0)
+}
+
+pub fn set_receipt(e: &Env, receipt: &Receipt) {
+    let key = DataKey::Receipt(receipt.id);
+    e.storage().persistent().set(&key, receipt);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_receipt(e: &Env, id: u64) -> Option<Receipt> {
+    let key = DataKey::Receipt(id);
+    let receipt = e.storage().persistent().get(&key);
+    if receipt.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    receipt
+}
+
+// Per-user receipt index (position -> receipt id)
+pub fn set_user_receipt_count(e: &Env, user: &Address, count: u32) {
+    let key = DataKey::UserReceiptCount(user.clone());
+    e.storage().persistent().set(&key, &count);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_user_receipt_count(e: &Env, user: &Address) -> u32 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::UserReceiptCount(user.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_user_receipt(e: &Env, user: &Address, index: u32, id: u64) {
+    let key = DataKey::UserReceipt(user.clone(), index);
+    e.storage().persistent().set(&key, &id);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_user_receipt(e: &Env, user: &Address, index: u32) -> Option<u64> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::UserReceipt(user.clone(), index))
+}
+
+// Emergency pause flag
+pub fn set_paused(e: &Env, paused: bool) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Paused, &paused);
+}
+
+pub fn get_paused(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&DataKey::Paused)
+        .unwrap_or(false)
+}
+
+// Enabled features bitmask (see `features.rs`)
+pub fn set_features(e: &Env, features: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Features, &features);
+}
+
+pub fn get_features(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::Features)
+        .unwrap_or(ALL_FEATURES)
+}
+
+// Per-user vault share checkpoints, in ledger order
+pub fn set_share_checkpoint_count(e: &Env, user: &Address, count: u32) {
+    let key = DataKey::ShareCheckpointCount(user.clone());
+    e.storage().persistent().set(&key, &count);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_share_checkpoint_count(e: &Env, user: &Address) -> u32 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::ShareCheckpointCount(user.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_share_checkpoint(e: &Env, user: &Address, index: u32, checkpoint: &ShareCheckpoint) {
+    let key = DataKey::ShareCheckpoint(user.clone(), index);
+    e.storage().persistent().set(&key, checkpoint);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_share_checkpoint(e: &Env, user: &Address, index: u32) -> ShareCheckpoint {
+    let key = DataKey::ShareCheckpoint(user.clone(), index);
+    let checkpoint = e.storage().persistent().get(&key).unwrap();
+    extend_persistent_ttl(e, &key);
+    checkpoint
+}
+
+// Governor whose pending sensitive changes open the rage-quit exit
+pub fn set_governor(e: &Env, address: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Governor, address);
+}
+
+pub fn get_governor(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::Governor)
+}
+
+// Withdrawal delegations (user, delegate)
+pub fn set_withdrawer(e: &Env, user: &Address, delegate: &Address, allowance: &WithdrawerAllowance) {
+    let key = DataKey::Withdrawer(user.clone(), delegate.clone());
+    e.storage().persistent().set(&key, allowance);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_withdrawer(e: &Env, user: &Address, delegate: &Address) -> Option<WithdrawerAllowance> {
+    let key = DataKey::Withdrawer(user.clone(), delegate.clone());
+    let allowance = e.storage().persistent().get(&key);
+    if allowance.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    allowance
+}
+
+pub fn remove_withdrawer(e: &Env, user: &Address, delegate: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::Withdrawer(user.clone(), delegate.clone()));
+}
+
+// Risk registry scoring the vault
+pub fn set_risk_registry(e: &Env, address: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::RiskRegistry, address);
+}
+
+pub fn get_risk_registry(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::RiskRegistry)
+}
+
+// Tutorial contract recording workshop steps (tutorial mode is off while unset)
+pub fn set_tutorial(e: &Env, tutorial: &Option<Address>) {
+    match tutorial {
+        Some(tutorial) => e.storage().instance().set(&DataKey::Tutorial, tutorial),
+        None => e.storage().instance().remove(&DataKey::Tutorial),
+    }
+}
+
+pub fn get_tutorial(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::Tutorial)
+}
+
+// Highest vault risk score each user accepts
+pub fn set_max_risk(e: &Env, user: &Address, score: u32) {
+    let key = DataKey::MaxRisk(user.clone());
+    e.storage().persistent().set(&key, &score);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_max_risk(e: &Env, user: &Address) -> Option<u32> {
+    let key = DataKey::MaxRisk(user.clone());
+    let score = e.storage().persistent().get(&key);
+    if score.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    score
+}
+
+pub fn remove_max_risk(e: &Env, user: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::MaxRisk(user.clone()));
+}
+
+// Early-withdrawal penalty of savings buckets, in basis points
+pub fn set_bucket_penalty(e: &Env, bps: Bps) {
+    e.storage()
+        .instance()
+        .set(&DataKey::BucketPenalty, &bps);
+}
+
+pub fn get_bucket_penalty(e: &Env) -> Bps {
+    e.storage()
+        .instance()
+        .get(&DataKey::BucketPenalty)
+        .unwrap_or(DEFAULT_BUCKET_PENALTY_BPS)
+}
+
+// Number of buckets ever created (next bucket id)
+pub fn set_bucket_count(e: &Env, count: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::BucketCount, &count);
+}
+
+pub fn get_bucket_count(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::BucketCount)
+        .unwrap_or(0)
+}
+
+// Savings buckets by id
+pub fn set_bucket(e: &Env, bucket: &Bucket) {
+    let key = DataKey::Bucket(bucket.id);
+    e.storage().persistent().set(&key, bucket);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_bucket(e: &Env, id: u32) -> Option<Bucket> {
+    let key = DataKey::Bucket(id);
+    let bucket = e.storage().persistent().get(&key);
+    if bucket.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    bucket
+}
+
+pub fn remove_bucket(e: &Env, id: u32) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::Bucket(id));
+}
+
+// Vault shares deposited across all buckets
+pub fn set_bucket_total_shares(e: &Env, shares: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::BucketTotalShares, &shares);
+}
+
+pub fn get_bucket_total_shares(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::BucketTotalShares)
+        .unwrap_or(0)
+}
+
+// Penalty shares distributed per deposited bucket share, scaled by `REWARD_PRECISION`
+pub fn set_bucket_reward_per_share(e: &Env, value: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::BucketRewardPerShare, &value);
+}
+
+pub fn get_bucket_reward_per_share(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::BucketRewardPerShare)
+        .unwrap_or(0)
+}
+
+// Beneficiary designated by each user
+pub fn set_beneficiary(e: &Env, user: &Address, designation: &BeneficiaryDesignation) {
+    let key = DataKey::Beneficiary(user.clone());
+    e.storage().persistent().set(&key, designation);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_beneficiary(e: &Env, user: &Address) -> Option<BeneficiaryDesignation> {
+    let key = DataKey::Beneficiary(user.clone());
+    let designation = e.storage().persistent().get(&key);
+    if designation.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    designation
+}
+
+pub fn remove_beneficiary(e: &Env, user: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::Beneficiary(user.clone()));
+}
+
+// Ledger of the last operation signed by a user with a beneficiary
+pub fn set_last_activity(e: &Env, user: &Address, ledger: u32) {
+    let key = DataKey::LastActivity(user.clone());
+    e.storage().persistent().set(&key, &ledger);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_last_activity(e: &Env, user: &Address) -> Option<u32> {
+    let key = DataKey::LastActivity(user.clone());
+    let ledger = e.storage().persistent().get(&key);
+    if ledger.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    ledger
+}
+
+pub fn remove_last_activity(e: &Env, user: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::LastActivity(user.clone()));
+}
+
+// Claim token minted against the vault shares held for `deposit_for_claim`
+pub fn set_claim_token(e: &Env, address: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ClaimToken, address);
+}
+
+pub fn get_claim_token(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::ClaimToken)
+}
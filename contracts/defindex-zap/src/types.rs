@@ -0,0 +1,61 @@
+use common::math::Bps;
+use soroban_sdk::{contracttype, Address, Vec};
+
+use crate::storage::Receipt;
+
+/// One page of a user's receipts, newest first
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HistoryPage {
+    pub receipts: Vec<Receipt>,
+    /// Cursor of the next (older) page, `None` once the oldest receipt has been returned
+    pub next_cursor: Option<u32>,
+}
+
+/// Estimated outcome of a `deposit`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositPreview {
+    /// Underlying asset the swap is quoted to produce
+    pub underlying: i128,
+    /// Vault shares that underlying amount is worth right now
+    pub shares: i128,
+    /// Risk score of the vault, if it is scored
+    pub risk_score: Option<u32>,
+}
+
+/// Estimated outcome of a `withdraw_exact_shares`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawPreview {
+    /// Underlying asset the shares redeem for
+    pub underlying: i128,
+    /// `token_out` the underlying asset is quoted to swap to
+    pub amount_out: i128,
+    /// Whether the redemption was estimated from the vault totals because the vault does not
+    /// expose `get_asset_amounts_per_shares`
+    pub from_fallback: bool,
+}
+
+/// Where the vault's underlying asset currently sits
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VaultBreakdown {
+    /// Underlying asset managed by the vault, idle and invested
+    pub total: i128,
+    /// Underlying asset held by the vault itself, not invested in any strategy
+    pub idle: i128,
+    pub idle_share: Bps,
+    pub strategies: Vec<StrategyBreakdown>,
+}
+
+/// One strategy's part of a `VaultBreakdown`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StrategyBreakdown {
+    pub strategy: Address,
+    pub amount: i128,
+    /// `amount` as a fraction of the vault total
+    pub share: Bps,
+    pub paused: bool,
+}
@@ -0,0 +1,21 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum EmissionsError {
+    /// Invalid parameters
+    InvalidArgument = 1,
+
+    NegativeNotAllowed = 2,
+    /// Gauge is not registered
+    GaugeNotFound = 3,
+    /// Gauge is already registered
+    GaugeAlreadyRegistered = 4,
+    /// No gauge voting contract is configured
+    VotingNotSet = 5,
+    /// The last closed voting epoch has already been applied
+    AlreadySynced = 6,
+    /// The accumulator is more epochs behind than one checkpoint walks; call `checkpoint()` first
+    CheckpointBehind = 7,
+}
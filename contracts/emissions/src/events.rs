@@ -0,0 +1,19 @@
+use soroban_sdk::{contractevent, Address};
+
+/// Emitted when a gauge is registered or its emission weight changes
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GaugeWeightUpdated {
+    #[topic]
+    pub gauge: Address,
+    pub weight: u32,
+}
+
+/// Emitted when a gauge claims its released rewards
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardsClaimed {
+    #[topic]
+    pub gauge: Address,
+    pub amount: i128,
+}
@@ -0,0 +1,10 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+// Interface of the `gauge-voting` contract whose tallies direct the emissions
+#[allow(dead_code)]
+#[contractclient(name = "GaugeVotingClient")]
+pub trait GaugeVoting {
+    fn epoch(e: Env) -> u32;
+    fn tally(e: Env, epoch: u32, gauge: Address) -> i128;
+    fn total_votes(e: Env, epoch: u32) -> i128;
+}
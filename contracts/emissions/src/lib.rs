@@ -0,0 +1,315 @@
+#![no_std]
+//! # Emissions - Decaying Reward Schedule
+//!
+//! This contract releases a reward token to registered gauges (a staking contract, an LP
+//! incentive program, ...) on a schedule that decays every epoch. It completes the incentive
+//! stack: the other contracts only need to claim from it.
+//!
+//! ## Schedule:
+//! Emissions start at deployment with `initial_rate` tokens per ledger. At the end of every
+//! epoch of `epoch_length` ledgers the rate is reduced by `decay_bps`, so the total supply
+//! released converges instead of growing forever.
+//!
+//! ## Accounting:
+//! The released amount is split between the gauges in proportion to their weight with a
+//! reward-per-weight accumulator, so the cost of an update does not depend on the number of
+//! gauges. A checkpoint moves the accumulator forward to the current ledger; it happens on every
+//! weight change and claim, and anyone may call `checkpoint()` to keep the epoch walk short.
+//! A checkpoint walks at most `MAX_EPOCHS_PER_CHECKPOINT` epochs and saves where it stopped, so
+//! after a long idle period `checkpoint()` is called until it catches up; weight changes are
+//! rejected until then.
+//!
+//! The contract releases tokens it already holds: the admin funds it with the reward token.
+//!
+//! ## Governance-Directed Weights:
+//! The admin sets the gauge weights directly until a `gauge-voting` contract is configured.
+//! Afterwards `sync_weights()` (permissionless, once per voting epoch) replaces every weight
+//! with the gauge's share of the votes in the last closed epoch.
+
+use common::math::{Bps, BPS_DENOMINATOR};
+use soroban_sdk::{
+    Address, Env, Vec, contract, contractimpl, panic_with_error, token
+};
+
+mod gauge_voting;
+mod storage;
+mod error;
+mod events;
+
+use gauge_voting::GaugeVotingClient;
+use storage::{
+    extend_instance_ttl, get_admin, get_checkpoint, get_gauge, get_gauges, get_reward_token,
+    get_schedule, get_synced_epoch, get_total_weight, get_voting, set_admin, set_checkpoint,
+    set_gauge, set_gauges, set_reward_token, set_schedule, set_synced_epoch, set_total_weight,
+    set_voting, Checkpoint, GaugeState, Schedule,
+};
+use error::EmissionsError;
+use events::{GaugeWeightUpdated, RewardsClaimed};
+
+/// Fixed-point scale of `Checkpoint::reward_per_weight`
+const PRECISION: i128 = 1_000_000_000_000;
+
+/// Most epoch boundaries crossed by one checkpoint, bounding its cost after a long idle period
+const MAX_EPOCHS_PER_CHECKPOINT: u32 = 128;
+
+/// Moves the accumulator from `checkpoint.ledger` to `ledger`, one epoch segment at a time
+///
+/// Stops at an epoch boundary before `ledger` once `MAX_EPOCHS_PER_CHECKPOINT` epochs were
+/// crossed; the next call resumes from there.
+fn advance(schedule: &Schedule, checkpoint: &Checkpoint, total_weight: u64, ledger: u32) -> Checkpoint {
+    let mut next = checkpoint.clone();
+    let mut epochs = 0;
+    while next.ledger < ledger && next.epoch_rate > 0 {
+        if epochs == MAX_EPOCHS_PER_CHECKPOINT {
+            return next;
+        }
+        let epoch_end = (schedule.start_ledger as u64 + (next.epoch as u64 + 1) * schedule.epoch_length as u64)
+            .min(u32::MAX as u64) as u32;
+        let segment_end = epoch_end.min(ledger);
+
+        let released = next.epoch_rate * (segment_end - next.ledger) as i128;
+        if total_weight > 0 {
+            next.reward_per_weight += released * PRECISION / total_weight as i128;
+        }
+        next.ledger = segment_end;
+
+        if segment_end == epoch_end {
+            next.epoch += 1;
+            next.epoch_rate = schedule.decay_bps.complement().apply(next.epoch_rate);
+            epochs += 1;
+        }
+    }
+    // Once the rate has decayed to zero nothing is released any more
+    next.ledger = next.ledger.max(ledger);
+    next
+}
+
+/// Credits a gauge with everything released to it up to `checkpoint`
+fn settle(state: &mut GaugeState, checkpoint: &Checkpoint) {
+    state.accrued += state.weight as i128 * (checkpoint.reward_per_weight - state.reward_per_weight_paid) / PRECISION;
+    state.reward_per_weight_paid = checkpoint.reward_per_weight;
+}
+
+/// Checkpoints the global accumulator at the current ledger
+fn checkpoint(e: &Env) -> Checkpoint {
+    let checkpoint = advance(&get_schedule(e), &get_checkpoint(e), get_total_weight(e), e.ledger().sequence());
+    set_checkpoint(e, &checkpoint);
+    checkpoint
+}
+
+/// Settles a gauge and changes its weight, keeping the total weight in sync
+///
+/// The accumulator must reach the current ledger: otherwise the new weight would also apply to
+/// the rewards released before the change.
+fn update_gauge_weight(e: &Env, gauge: &Address, mut state: GaugeState, weight: u32) -> Result<(), EmissionsError> {
+    let checkpoint = checkpoint(e);
+    if checkpoint.ledger < e.ledger().sequence() {
+        return Err(EmissionsError::CheckpointBehind);
+    }
+    settle(&mut state, &checkpoint);
+
+    set_total_weight(e, get_total_weight(e) - state.weight as u64 + weight as u64);
+    state.weight = weight;
+    set_gauge(e, gauge, &state);
+
+    GaugeWeightUpdated {
+        gauge: gauge.clone(),
+        weight,
+    }
+    .publish(e);
+    Ok(())
+}
+
+#[contract]
+struct Emissions;
+
+#[contractimpl]
+impl Emissions {
+    /// Initialize the contract with the admin, the reward token and the emission schedule
+    ///
+    /// ## Parameters:
+    /// - `admin`: Account managing the gauges
+    /// - `reward_token`: Token released to the gauges
+    /// - `epoch_length`: Length of an epoch, in ledgers
+    /// - `initial_rate`: Tokens released per ledger during the first epoch
+    /// - `decay_bps`: Rate reduction at the end of every epoch, in basis points
+    pub fn __constructor(
+        e: Env,
+        admin: Address,
+        reward_token: Address,
+        epoch_length: u32,
+        initial_rate: i128,
+        decay_bps: Bps,
+    ) {
+        if epoch_length == 0 || initial_rate < 0 || !decay_bps.is_valid() {
+            panic_with_error!(&e, EmissionsError::InvalidArgument);
+        }
+
+        set_admin(&e, admin);
+        set_reward_token(&e, reward_token);
+        set_schedule(
+            &e,
+            &Schedule {
+                start_ledger: e.ledger().sequence(),
+                epoch_length,
+                initial_rate,
+                decay_bps,
+            },
+        );
+        set_checkpoint(
+            &e,
+            &Checkpoint {
+                ledger: e.ledger().sequence(),
+                epoch: 0,
+                epoch_rate: initial_rate,
+                reward_per_weight: 0,
+            },
+        );
+    }
+
+    /// Register a gauge with its share of the emissions (admin only)
+    pub fn add_gauge(e: Env, gauge: Address, weight: u32) -> Result<(), EmissionsError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        if get_gauge(&e, &gauge).is_some() {
+            return Err(EmissionsError::GaugeAlreadyRegistered);
+        }
+
+        let mut gauges = get_gauges(&e);
+        gauges.push_back(gauge.clone());
+        set_gauges(&e, &gauges);
+
+        // Start from the current accumulator so the gauge earns nothing released before it joined
+        let state = GaugeState {
+            weight: 0,
+            reward_per_weight_paid: checkpoint(&e).reward_per_weight,
+            accrued: 0,
+        };
+        update_gauge_weight(&e, &gauge, state, weight)
+    }
+
+    /// Change a gauge's share of the emissions (admin only)
+    ///
+    /// Rewards released so far are settled with the previous weight. A weight of 0 stops the
+    /// gauge's emissions without losing what it already earned.
+    pub fn set_gauge_weight(e: Env, gauge: Address, weight: u32) -> Result<(), EmissionsError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        let state = get_gauge(&e, &gauge).ok_or(EmissionsError::GaugeNotFound)?;
+        update_gauge_weight(&e, &gauge, state, weight)
+    }
+
+    /// Let a `gauge-voting` contract direct the gauge weights (admin only)
+    pub fn set_voting(e: Env, voting: Address) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        // Only epochs closed from now on are applied
+        set_synced_epoch(&e, GaugeVotingClient::new(&e, &voting).epoch());
+        set_voting(&e, &voting);
+    }
+
+    /// Apply the tallies of the last closed voting epoch to the gauge weights
+    ///
+    /// Every registered gauge receives its share of the epoch's votes, in basis points; gauges
+    /// without votes drop to 0. Permissionless, and effective once per voting epoch. An epoch
+    /// without any votes leaves the weights unchanged.
+    ///
+    /// ## Returns:
+    /// Voting epoch whose tallies were applied
+    pub fn sync_weights(e: Env) -> Result<u32, EmissionsError> {
+        extend_instance_ttl(&e);
+        let voting = GaugeVotingClient::new(&e, &get_voting(&e).ok_or(EmissionsError::VotingNotSet)?);
+
+        let current_epoch = voting.epoch();
+        let synced_epoch = get_synced_epoch(&e);
+        if current_epoch <= synced_epoch {
+            return Err(EmissionsError::AlreadySynced);
+        }
+        let epoch = current_epoch - 1;
+        set_synced_epoch(&e, current_epoch);
+
+        let total_votes = voting.total_votes(&epoch);
+        if total_votes > 0 {
+            for gauge in get_gauges(&e).iter() {
+                let weight = voting.tally(&epoch, &gauge) * BPS_DENOMINATOR as i128 / total_votes;
+                let state = get_gauge(&e, &gauge).ok_or(EmissionsError::GaugeNotFound)?;
+                update_gauge_weight(&e, &gauge, state, weight as u32)?;
+            }
+        }
+        Ok(epoch)
+    }
+
+    /// Gauge voting contract directing the weights, if any
+    pub fn voting(e: Env) -> Option<Address> {
+        get_voting(&e)
+    }
+
+    /// Move the emission accumulator forward to the current ledger
+    ///
+    /// Permissionless; claims and weight changes checkpoint on their own. Walks at most
+    /// `MAX_EPOCHS_PER_CHECKPOINT` epochs: when the returned `ledger` is still behind the current
+    /// one, call it again.
+    pub fn checkpoint(e: Env) -> Checkpoint {
+        extend_instance_ttl(&e);
+        checkpoint(&e)
+    }
+
+    /// Rewards a gauge can claim right now
+    ///
+    /// Counts the epochs one checkpoint walks, like `claim`.
+    pub fn pending(e: Env, gauge: Address) -> Result<i128, EmissionsError> {
+        let mut state = get_gauge(&e, &gauge).ok_or(EmissionsError::GaugeNotFound)?;
+        let checkpoint = advance(&get_schedule(&e), &get_checkpoint(&e), get_total_weight(&e), e.ledger().sequence());
+        settle(&mut state, &checkpoint);
+        Ok(state.accrued)
+    }
+
+    /// Transfer a gauge's released rewards to it
+    ///
+    /// Rewards of epochs the checkpoint has not walked yet stay claimable later.
+    ///
+    /// ## Returns:
+    /// Amount of the reward token transferred
+    pub fn claim(e: Env, gauge: Address) -> Result<i128, EmissionsError> {
+        gauge.require_auth();
+        extend_instance_ttl(&e);
+
+        let mut state = get_gauge(&e, &gauge).ok_or(EmissionsError::GaugeNotFound)?;
+        settle(&mut state, &checkpoint(&e));
+        let amount = state.accrued;
+        state.accrued = 0;
+        set_gauge(&e, &gauge, &state);
+
+        if amount > 0 {
+            token::Client::new(&e, &get_reward_token(&e)).transfer(&e.current_contract_address(), &gauge, &amount);
+        }
+
+        RewardsClaimed { gauge, amount }.publish(&e);
+        Ok(amount)
+    }
+
+    /// Registered gauges, in registration order
+    pub fn gauges(e: Env) -> Vec<Address> {
+        get_gauges(&e)
+    }
+
+    /// Weight and settlement state of a gauge
+    pub fn gauge(e: Env, gauge: Address) -> Option<GaugeState> {
+        get_gauge(&e, &gauge)
+    }
+
+    /// Emission schedule
+    pub fn schedule(e: Env) -> Schedule {
+        get_schedule(&e)
+    }
+
+    /// Tokens released per ledger at the ledger one checkpoint reaches
+    pub fn current_rate(e: Env) -> i128 {
+        advance(&get_schedule(&e), &get_checkpoint(&e), get_total_weight(&e), e.ledger().sequence()).epoch_rate
+    }
+
+    /// Address of the reward token
+    pub fn reward_token(e: Env) -> Address {
+        get_reward_token(&e)
+    }
+}
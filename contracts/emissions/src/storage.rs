@@ -0,0 +1,205 @@
+use common::math::Bps;
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+#[derive(Clone)]
+#[contracttype]
+
+enum DataKey {
+    Admin,
+    RewardToken,
+    Schedule,
+    Checkpoint,
+    TotalWeight,
+    Gauges,
+    Gauge(Address),
+    Voting,
+    SyncedEpoch,
+}
+
+/// Decaying emission schedule, fixed at deployment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Schedule {
+    /// Ledger sequence at which emissions start
+    pub start_ledger: u32,
+    /// Length of an epoch, in ledgers; the rate is constant within an epoch
+    pub epoch_length: u32,
+    /// Reward tokens released per ledger during the first epoch
+    pub initial_rate: i128,
+    /// Reduction of the rate from one epoch to the next, in basis points
+    pub decay_bps: Bps,
+}
+
+/// Global emission accumulator as of the last checkpoint
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    /// Ledger sequence of the checkpoint
+    pub ledger: u32,
+    /// Epoch containing `ledger`
+    pub epoch: u32,
+    /// Emission rate of `epoch`, per ledger
+    pub epoch_rate: i128,
+    /// Rewards released per unit of gauge weight since the start, scaled by `PRECISION`
+    pub reward_per_weight: i128,
+}
+
+/// Emission share and unclaimed rewards of a registered gauge
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GaugeState {
+    pub weight: u32,
+    /// `Checkpoint::reward_per_weight` when the gauge was last settled
+    pub reward_per_weight_paid: i128,
+    /// Rewards settled but not yet claimed
+    pub accrued: i128,
+}
+
+#[allow(dead_code)]
+const DAY_IN_LEDGERS: u32 = 17280;
+#[allow(dead_code)]
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+#[allow(dead_code)]
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+const PERSISTENT_BUMP_AMOUNT: u32 = 60 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+pub fn extend_instance_ttl(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Admin Address
+pub fn set_admin(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Admin, &address);
+}
+
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap()
+}
+
+// Reward token released to the gauges
+pub fn set_reward_token(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::RewardToken, &address);
+}
+
+pub fn get_reward_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::RewardToken)
+        .unwrap()
+}
+
+// Emission schedule
+pub fn set_schedule(e: &Env, schedule: &Schedule) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Schedule, schedule);
+}
+
+pub fn get_schedule(e: &Env) -> Schedule {
+    e.storage()
+        .instance()
+        .get(&DataKey::Schedule)
+        .unwrap()
+}
+
+// Global checkpoint
+pub fn set_checkpoint(e: &Env, checkpoint: &Checkpoint) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Checkpoint, checkpoint);
+}
+
+pub fn get_checkpoint(e: &Env) -> Checkpoint {
+    e.storage()
+        .instance()
+        .get(&DataKey::Checkpoint)
+        .unwrap()
+}
+
+// Sum of the weights of every registered gauge
+pub fn set_total_weight(e: &Env, weight: u64) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TotalWeight, &weight);
+}
+
+pub fn get_total_weight(e: &Env) -> u64 {
+    e.storage()
+        .instance()
+        .get(&DataKey::TotalWeight)
+        .unwrap_or(0)
+}
+
+// Registered gauges, in registration order
+pub fn set_gauges(e: &Env, gauges: &Vec<Address>) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Gauges, gauges);
+}
+
+pub fn get_gauges(e: &Env) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::Gauges)
+        .unwrap_or(Vec::new(e))
+}
+
+// State of each registered gauge
+pub fn set_gauge(e: &Env, gauge: &Address, state: &GaugeState) {
+    let key = DataKey::Gauge(gauge.clone());
+    e.storage().persistent().set(&key, state);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_gauge(e: &Env, gauge: &Address) -> Option<GaugeState> {
+    let key = DataKey::Gauge(gauge.clone());
+    let state = e.storage().persistent().get(&key);
+    if state.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    state
+}
+
+// Gauge voting contract directing the weights (unset: the admin sets them)
+pub fn set_voting(e: &Env, address: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Voting, address);
+}
+
+pub fn get_voting(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::Voting)
+}
+
+// Next voting epoch whose tallies have not been applied yet
+pub fn set_synced_epoch(e: &Env, epoch: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::SyncedEpoch, &epoch);
+}
+
+pub fn get_synced_epoch(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::SyncedEpoch)
+        .unwrap_or(0)
+}
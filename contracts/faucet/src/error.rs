@@ -0,0 +1,15 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FaucetError {
+    /// Invalid parameters
+    InvalidArgument = 1,
+    /// The address already received a drip within the cooldown
+    CooldownActive = 2,
+    /// Starter liquidity requested but none is configured
+    NoSeedConfigured = 3,
+    /// The router refused the starter liquidity
+    SeedFailed = 4,
+}
@@ -0,0 +1,11 @@
+use soroban_sdk::{contractevent, Address};
+
+/// Emitted when an address receives its test tokens
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dripped {
+    #[topic]
+    pub user: Address,
+    /// Whether a starter liquidity position was deposited as well
+    pub seeded: bool,
+}
@@ -0,0 +1,175 @@
+#![no_std]
+//! # Faucet - Rate-Limited Workshop Test Tokens
+//!
+//! Participants call `drip` to receive a fixed amount of each workshop test token, at most once
+//! every `cooldown` ledgers per address. On request the faucet also mints a starter liquidity
+//! position and deposits it into the Soroswap pair on the participant's behalf, so sessions can
+//! start trading without a manual token-distribution step.
+//!
+//! ## Setup:
+//! The faucet mints through each token's admin interface (`mint(to, amount)`), so it must be the
+//! admin of every test token: `set_admin` on a Stellar Asset Contract, or the admin passed to a
+//! custom token's constructor.
+//!
+//! ## Starter Liquidity:
+//! The seed amounts are minted to the participant, then the Soroswap Router's `add_liquidity`
+//! moves them into the pair and mints the LP tokens to the participant. The router requires the
+//! participant's authorization, which their signature of `drip` covers as a sub-invocation.
+
+use soroban_sdk::{Address, Env, Vec, contract, contractimpl, panic_with_error, token};
+
+mod soroswap_router;
+mod storage;
+mod error;
+mod events;
+
+use soroswap_router::SoroswapRouterClient;
+use storage::{
+    extend_instance_ttl, get_admin, get_cooldown, get_drips, get_last_drip, get_router, get_seed,
+    set_admin, set_cooldown, set_drips, set_last_drip, set_router, set_seed, Drip, LpSeed,
+};
+use error::FaucetError;
+use events::Dripped;
+
+/// Validates the drip list: at least one token, every amount positive
+fn check_drips(drips: &Vec<Drip>) -> Result<(), FaucetError> {
+    if drips.is_empty() || drips.iter().any(|drip| drip.amount <= 0) {
+        Err(FaucetError::InvalidArgument)
+    } else {
+        Ok(())
+    }
+}
+
+/// Mints `amount` of the test token `token` to `to`
+fn mint(e: &Env, token: &Address, to: &Address, amount: i128) {
+    token::StellarAssetClient::new(e, token).mint(to, &amount);
+}
+
+#[contract]
+struct Faucet;
+
+#[contractimpl]
+impl Faucet {
+    /// Initialize the faucet
+    ///
+    /// ## Parameters:
+    /// - `admin`: Account configuring the drips
+    /// - `router`: Soroswap Router receiving the starter liquidity
+    /// - `drips`: Tokens and amounts minted on every drip
+    /// - `cooldown`: Ledgers an address waits between drips
+    pub fn __constructor(e: Env, admin: Address, router: Address, drips: Vec<Drip>, cooldown: u32) {
+        if check_drips(&drips).is_err() || cooldown == 0 {
+            panic_with_error!(&e, FaucetError::InvalidArgument);
+        }
+        set_admin(&e, admin);
+        set_router(&e, router);
+        set_drips(&e, &drips);
+        set_cooldown(&e, cooldown);
+    }
+
+    /// Replace the tokens and amounts minted on every drip (admin only)
+    pub fn set_drips(e: Env, drips: Vec<Drip>) -> Result<(), FaucetError> {
+        get_admin(&e).require_auth();
+        check_drips(&drips)?;
+        extend_instance_ttl(&e);
+        set_drips(&e, &drips);
+        Ok(())
+    }
+
+    /// Set the ledgers an address waits between drips (admin only)
+    pub fn set_cooldown(e: Env, cooldown: u32) -> Result<(), FaucetError> {
+        get_admin(&e).require_auth();
+        if cooldown == 0 {
+            return Err(FaucetError::InvalidArgument);
+        }
+        extend_instance_ttl(&e);
+        set_cooldown(&e, cooldown);
+        Ok(())
+    }
+
+    /// Configure (or with `None` remove) the starter liquidity position (admin only)
+    pub fn set_seed(e: Env, seed: Option<LpSeed>) -> Result<(), FaucetError> {
+        get_admin(&e).require_auth();
+        if let Some(seed) = &seed {
+            if seed.token_a == seed.token_b || seed.amount_a <= 0 || seed.amount_b <= 0 {
+                return Err(FaucetError::InvalidArgument);
+            }
+        }
+        extend_instance_ttl(&e);
+        set_seed(&e, &seed);
+        Ok(())
+    }
+
+    /// Mint the configured test tokens to the caller
+    ///
+    /// Fails with `CooldownActive` when the caller received a drip less than `cooldown`
+    /// ledgers ago.
+    ///
+    /// ## Parameters:
+    /// - `caller`: Participant receiving the tokens (must sign the transaction)
+    /// - `seed_lp`: Also mint the starter liquidity and deposit it into its Soroswap pair
+    pub fn drip(e: Env, caller: Address, seed_lp: bool) -> Result<(), FaucetError> {
+        caller.require_auth();
+        extend_instance_ttl(&e);
+
+        let ledger = e.ledger().sequence();
+        if let Some(last) = get_last_drip(&e, &caller) {
+            if ledger < last.saturating_add(get_cooldown(&e)) {
+                return Err(FaucetError::CooldownActive);
+            }
+        }
+
+        for drip in get_drips(&e).iter() {
+            mint(&e, &drip.token, &caller, drip.amount);
+        }
+
+        if seed_lp {
+            let seed = get_seed(&e).ok_or(FaucetError::NoSeedConfigured)?;
+            mint(&e, &seed.token_a, &caller, seed.amount_a);
+            mint(&e, &seed.token_b, &caller, seed.amount_b);
+
+            // The router takes the seed amounts from the caller and mints the LP tokens to them
+            let router_client = SoroswapRouterClient::new(&e, &get_router(&e));
+            match router_client.try_add_liquidity(
+                &seed.token_a,
+                &seed.token_b,
+                &seed.amount_a,
+                &seed.amount_b,
+                &0,
+                &0,
+                &caller,
+                &u64::MAX,
+            ) {
+                Ok(Ok(_)) => {}
+                _ => return Err(FaucetError::SeedFailed),
+            }
+        }
+
+        set_last_drip(&e, &caller, ledger);
+        Dripped { user: caller, seeded: seed_lp }.publish(&e);
+        Ok(())
+    }
+
+    /// First ledger at which `user` can receive a drip again (0 when they never did)
+    pub fn next_drip(e: Env, user: Address) -> u32 {
+        match get_last_drip(&e, &user) {
+            Some(last) => last.saturating_add(get_cooldown(&e)),
+            None => 0,
+        }
+    }
+
+    /// Tokens and amounts minted on every drip
+    pub fn drips(e: Env) -> Vec<Drip> {
+        get_drips(&e)
+    }
+
+    /// Ledgers an address waits between drips
+    pub fn cooldown(e: Env) -> u32 {
+        get_cooldown(&e)
+    }
+
+    /// Starter liquidity position, if configured
+    pub fn seed(e: Env) -> Option<LpSeed> {
+        get_seed(&e)
+    }
+}
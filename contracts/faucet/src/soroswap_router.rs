@@ -0,0 +1,6 @@
+#![allow(clippy::too_many_arguments)]
+
+soroban_sdk::contractimport!(file = "../wasms/soroswap_router.optimized.wasm");
+
+#[allow(dead_code)]
+pub type SoroswapRouterClient<'a> = Client<'a>;
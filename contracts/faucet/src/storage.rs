@@ -0,0 +1,139 @@
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+#[derive(Clone)]
+#[contracttype]
+
+enum DataKey {
+    Admin,
+    Router,
+    Drips,
+    Cooldown,
+    Seed,
+    LastDrip(Address),
+}
+
+/// Test token minted on every drip
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Drip {
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Starter liquidity position minted and deposited on request
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LpSeed {
+    pub token_a: Address,
+    pub token_b: Address,
+    pub amount_a: i128,
+    pub amount_b: i128,
+}
+
+#[allow(dead_code)]
+const DAY_IN_LEDGERS: u32 = 17280;
+#[allow(dead_code)]
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+#[allow(dead_code)]
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+const PERSISTENT_BUMP_AMOUNT: u32 = 60 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+pub fn extend_instance_ttl(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Admin Address (configures the drips)
+pub fn set_admin(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Admin, &address);
+}
+
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap()
+}
+
+// Soroswap Router Address (receives the starter liquidity)
+pub fn set_router(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Router, &address);
+}
+
+pub fn get_router(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Router)
+        .unwrap()
+}
+
+// Tokens and amounts minted on every drip
+pub fn set_drips(e: &Env, drips: &Vec<Drip>) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Drips, drips);
+}
+
+pub fn get_drips(e: &Env) -> Vec<Drip> {
+    e.storage()
+        .instance()
+        .get(&DataKey::Drips)
+        .unwrap_or(Vec::new(e))
+}
+
+// Ledgers an address waits between drips
+pub fn set_cooldown(e: &Env, ledgers: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Cooldown, &ledgers);
+}
+
+pub fn get_cooldown(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::Cooldown)
+        .unwrap()
+}
+
+// Starter liquidity position (none until the admin configures one)
+pub fn set_seed(e: &Env, seed: &Option<LpSeed>) {
+    match seed {
+        Some(seed) => e.storage().instance().set(&DataKey::Seed, seed),
+        None => e.storage().instance().remove(&DataKey::Seed),
+    }
+}
+
+pub fn get_seed(e: &Env) -> Option<LpSeed> {
+    e.storage()
+        .instance()
+        .get(&DataKey::Seed)
+}
+
+// Ledger sequence of each address's latest drip
+pub fn set_last_drip(e: &Env, user: &Address, ledger: u32) {
+    let key = DataKey::LastDrip(user.clone());
+    e.storage().persistent().set(&key, &ledger);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_last_drip(e: &Env, user: &Address) -> Option<u32> {
+    let key = DataKey::LastDrip(user.clone());
+    let ledger = e.storage().persistent().get(&key);
+    if ledger.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    ledger
+}
@@ -0,0 +1,19 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GaugeVotingError {
+    /// Invalid parameters
+    InvalidArgument = 1,
+
+    NegativeNotAllowed = 2,
+    /// Gauge is not registered
+    GaugeNotFound = 3,
+    /// Gauge is already registered
+    GaugeAlreadyRegistered = 4,
+    /// Unlock exceeds the voter's locked balance
+    InsufficientBalance = 5,
+    /// Tokens backing votes of the current epoch cannot be unlocked
+    VoteActive = 6,
+}
@@ -0,0 +1,12 @@
+use soroban_sdk::{contractevent, Address};
+
+/// Emitted when a voter casts (or recasts) their votes for an epoch
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Voted {
+    #[topic]
+    pub voter: Address,
+    pub epoch: u32,
+    /// Voting power spread across the gauges
+    pub power: i128,
+}
@@ -0,0 +1,207 @@
+#![no_std]
+//! # Gauge Voting - Governance-Directed Emissions
+//!
+//! Holders of the voting token lock it in this contract and, every epoch, spread their voting
+//! power across the registered gauges (vaults, staking contracts, LP incentive programs). The
+//! per-epoch tallies are public; the `emissions` contract reads the tallies of the last closed
+//! epoch to split its rewards.
+//!
+//! ## Key Characteristics:
+//! - Voting power equals the amount of voting token locked by the voter
+//! - Votes only count for the epoch they are cast in; voters vote again every epoch
+//! - Recasting within the same epoch replaces the previous votes
+//! - Tokens behind a vote of the current epoch stay locked until the epoch ends, so the same
+//!   tokens cannot be moved to another account and vote twice
+
+use common::math::{Bps, BPS_DENOMINATOR};
+use soroban_sdk::{
+    Address, Env, Vec, contract, contractimpl, panic_with_error, token
+};
+
+mod storage;
+mod error;
+mod events;
+
+use storage::{
+    extend_instance_ttl, get_admin, get_allocation, get_epoch_length, get_gauges, get_locked,
+    get_start_ledger, get_tally, get_total_votes, get_voting_token, set_admin, set_allocation,
+    set_epoch_length, set_gauges, set_locked, set_start_ledger, set_tally, set_total_votes,
+    set_voting_token, Allocation,
+};
+use error::GaugeVotingError;
+use events::Voted;
+
+/// Validates that the amount is non-negative
+pub fn check_nonnegative_amount(amount: i128) -> Result<(), GaugeVotingError> {
+    if amount < 0 {
+        Err(GaugeVotingError::NegativeNotAllowed)
+    } else {
+        Ok(())
+    }
+}
+
+/// Epoch containing the current ledger
+fn current_epoch(e: &Env) -> u32 {
+    (e.ledger().sequence() - get_start_ledger(e)) / get_epoch_length(e)
+}
+
+/// Whether the voter has votes counted in the current epoch
+fn has_active_vote(e: &Env, voter: &Address) -> bool {
+    match get_allocation(e, voter) {
+        Some(allocation) => allocation.epoch == current_epoch(e),
+        None => false,
+    }
+}
+
+#[contract]
+struct GaugeVoting;
+
+#[contractimpl]
+impl GaugeVoting {
+    /// Initialize the contract with the admin, the voting token and the epoch length in ledgers
+    pub fn __constructor(e: Env, admin: Address, voting_token: Address, epoch_length: u32) {
+        if epoch_length == 0 {
+            panic_with_error!(&e, GaugeVotingError::InvalidArgument);
+        }
+        set_admin(&e, admin);
+        set_voting_token(&e, voting_token);
+        set_start_ledger(&e, e.ledger().sequence());
+        set_epoch_length(&e, epoch_length);
+    }
+
+    /// Register a gauge that can receive votes (admin only)
+    pub fn add_gauge(e: Env, gauge: Address) -> Result<(), GaugeVotingError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+
+        let mut gauges = get_gauges(&e);
+        if gauges.contains(&gauge) {
+            return Err(GaugeVotingError::GaugeAlreadyRegistered);
+        }
+        gauges.push_back(gauge);
+        set_gauges(&e, &gauges);
+        Ok(())
+    }
+
+    /// Lock voting tokens, increasing the voter's power from their next vote on
+    pub fn lock(e: Env, voter: Address, amount: i128) -> Result<(), GaugeVotingError> {
+        voter.require_auth();
+        check_nonnegative_amount(amount)?;
+        extend_instance_ttl(&e);
+
+        token::Client::new(&e, &get_voting_token(&e)).transfer(&voter, e.current_contract_address(), &amount);
+        set_locked(&e, &voter, get_locked(&e, &voter) + amount);
+        Ok(())
+    }
+
+    /// Unlock voting tokens back to the voter
+    ///
+    /// Rejected with `VoteActive` while the voter has votes counted in the current epoch.
+    pub fn unlock(e: Env, voter: Address, amount: i128) -> Result<(), GaugeVotingError> {
+        voter.require_auth();
+        check_nonnegative_amount(amount)?;
+        extend_instance_ttl(&e);
+
+        if has_active_vote(&e, &voter) {
+            return Err(GaugeVotingError::VoteActive);
+        }
+        let locked = get_locked(&e, &voter);
+        if amount > locked {
+            return Err(GaugeVotingError::InsufficientBalance);
+        }
+        set_locked(&e, &voter, locked - amount);
+
+        token::Client::new(&e, &get_voting_token(&e)).transfer(&e.current_contract_address(), &voter, &amount);
+        Ok(())
+    }
+
+    /// Spread the voter's whole voting power across gauges for the current epoch
+    ///
+    /// Each gauge is paired with its share in basis points; the shares must add up to exactly
+    /// `BPS_DENOMINATOR`. Rounding dust goes to the last gauge. Votes cast earlier in the same
+    /// epoch are replaced.
+    ///
+    /// ## Returns:
+    /// Voting power cast
+    pub fn vote(e: Env, voter: Address, choices: Vec<(Address, Bps)>) -> Result<i128, GaugeVotingError> {
+        voter.require_auth();
+        extend_instance_ttl(&e);
+
+        let gauges = get_gauges(&e);
+        let mut total_bps = Bps::ZERO;
+        for (index, (gauge, bps)) in choices.iter().enumerate() {
+            if !gauges.contains(&gauge) {
+                return Err(GaugeVotingError::GaugeNotFound);
+            }
+            if choices.iter().skip(index + 1).any(|(other, _)| other == gauge) {
+                return Err(GaugeVotingError::InvalidArgument);
+            }
+            total_bps = total_bps.checked_add(bps).ok_or(GaugeVotingError::InvalidArgument)?;
+        }
+        let power = get_locked(&e, &voter);
+        if choices.is_empty() || total_bps.value() != BPS_DENOMINATOR || power == 0 {
+            return Err(GaugeVotingError::InvalidArgument);
+        }
+
+        let epoch = current_epoch(&e);
+        let mut total_votes = get_total_votes(&e, epoch);
+
+        // Withdraw the votes already cast this epoch
+        if let Some(previous) = get_allocation(&e, &voter) {
+            if previous.epoch == epoch {
+                for (gauge, votes) in previous.votes.iter() {
+                    set_tally(&e, epoch, &gauge, get_tally(&e, epoch, &gauge) - votes);
+                    total_votes -= votes;
+                }
+            }
+        }
+
+        let mut votes_cast: Vec<(Address, i128)> = Vec::new(&e);
+        let mut remaining = power;
+        for (index, (gauge, bps)) in choices.iter().enumerate() {
+            let votes = if index as u32 == choices.len() - 1 {
+                remaining
+            } else {
+                bps.apply(power)
+            };
+            remaining -= votes;
+            set_tally(&e, epoch, &gauge, get_tally(&e, epoch, &gauge) + votes);
+            votes_cast.push_back((gauge, votes));
+        }
+        set_total_votes(&e, epoch, total_votes + power);
+        set_allocation(&e, &voter, &Allocation { epoch, votes: votes_cast });
+
+        Voted { voter, epoch, power }.publish(&e);
+        Ok(power)
+    }
+
+    /// Epoch containing the current ledger
+    pub fn epoch(e: Env) -> u32 {
+        current_epoch(&e)
+    }
+
+    /// Voting power a gauge received in an epoch
+    pub fn tally(e: Env, epoch: u32, gauge: Address) -> i128 {
+        get_tally(&e, epoch, &gauge)
+    }
+
+    /// Voting power cast in an epoch, across all gauges
+    pub fn total_votes(e: Env, epoch: u32) -> i128 {
+        get_total_votes(&e, epoch)
+    }
+
+    /// Gauges that can receive votes, in registration order
+    pub fn gauges(e: Env) -> Vec<Address> {
+        get_gauges(&e)
+    }
+
+    /// Voting tokens locked by a voter
+    pub fn locked(e: Env, voter: Address) -> i128 {
+        get_locked(&e, &voter)
+    }
+
+    /// Latest votes of a voter (possibly from a past epoch)
+    pub fn allocation(e: Env, voter: Address) -> Option<Allocation> {
+        get_allocation(&e, &voter)
+    }
+}
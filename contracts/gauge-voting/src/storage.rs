@@ -0,0 +1,175 @@
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+#[derive(Clone)]
+#[contracttype]
+
+enum DataKey {
+    Admin,
+    VotingToken,
+    StartLedger,
+    EpochLength,
+    Gauges,
+    Locked(Address),
+    Allocation(Address),
+    Tally(u32, Address),
+    TotalVotes(u32),
+}
+
+/// Votes a voter cast in an epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Allocation {
+    pub epoch: u32,
+    /// Voting power given to each gauge
+    pub votes: Vec<(Address, i128)>,
+}
+
+#[allow(dead_code)]
+const DAY_IN_LEDGERS: u32 = 17280;
+#[allow(dead_code)]
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+#[allow(dead_code)]
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+const PERSISTENT_BUMP_AMOUNT: u32 = 60 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+pub fn extend_instance_ttl(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Admin Address
+pub fn set_admin(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Admin, &address);
+}
+
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap()
+}
+
+// Token locked to obtain voting power
+pub fn set_voting_token(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::VotingToken, &address);
+}
+
+pub fn get_voting_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::VotingToken)
+        .unwrap()
+}
+
+// Epoch clock (epoch 0 starts at deployment)
+pub fn set_start_ledger(e: &Env, ledger: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::StartLedger, &ledger);
+}
+
+pub fn get_start_ledger(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::StartLedger)
+        .unwrap()
+}
+
+pub fn set_epoch_length(e: &Env, length: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::EpochLength, &length);
+}
+
+pub fn get_epoch_length(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::EpochLength)
+        .unwrap()
+}
+
+// Gauges that can receive votes, in registration order
+pub fn set_gauges(e: &Env, gauges: &Vec<Address>) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Gauges, gauges);
+}
+
+pub fn get_gauges(e: &Env) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::Gauges)
+        .unwrap_or(Vec::new(e))
+}
+
+// Voting tokens locked by each voter
+pub fn set_locked(e: &Env, voter: &Address, amount: i128) {
+    let key = DataKey::Locked(voter.clone());
+    e.storage().persistent().set(&key, &amount);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_locked(e: &Env, voter: &Address) -> i128 {
+    let key = DataKey::Locked(voter.clone());
+    let amount = e.storage().persistent().get(&key);
+    if amount.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    amount.unwrap_or(0)
+}
+
+// Latest votes of each voter
+pub fn set_allocation(e: &Env, voter: &Address, allocation: &Allocation) {
+    let key = DataKey::Allocation(voter.clone());
+    e.storage().persistent().set(&key, allocation);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_allocation(e: &Env, voter: &Address) -> Option<Allocation> {
+    let key = DataKey::Allocation(voter.clone());
+    let allocation = e.storage().persistent().get(&key);
+    if allocation.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    allocation
+}
+
+// Voting power received by each gauge in each epoch
+pub fn set_tally(e: &Env, epoch: u32, gauge: &Address, votes: i128) {
+    let key = DataKey::Tally(epoch, gauge.clone());
+    e.storage().persistent().set(&key, &votes);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_tally(e: &Env, epoch: u32, gauge: &Address) -> i128 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Tally(epoch, gauge.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_total_votes(e: &Env, epoch: u32, votes: i128) {
+    let key = DataKey::TotalVotes(epoch);
+    e.storage().persistent().set(&key, &votes);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_total_votes(e: &Env, epoch: u32) -> i128 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::TotalVotes(epoch))
+        .unwrap_or(0)
+}
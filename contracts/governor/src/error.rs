@@ -0,0 +1,27 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GovernorError {
+    /// Invalid parameters
+    InvalidArgument = 1,
+
+    NegativeNotAllowed = 2,
+    /// Proposal does not exist
+    ProposalNotFound = 3,
+    /// Proposer's deposit is below the proposal threshold
+    BelowProposalThreshold = 4,
+    /// Voting on the proposal has ended
+    VotingClosed = 5,
+    /// Voter already voted on the proposal
+    AlreadyVoted = 6,
+    /// Proposal is not in the state the operation requires
+    InvalidState = 7,
+    /// Timelock delay of the queued proposal has not elapsed
+    TimelockNotExpired = 8,
+    /// Withdrawal exceeds the voter's deposit
+    InsufficientBalance = 9,
+    /// Deposit backs a vote on a proposal still being voted
+    VoteActive = 10,
+}
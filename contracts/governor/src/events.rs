@@ -0,0 +1,48 @@
+use soroban_sdk::{contractevent, Address};
+
+/// Emitted when a proposal is created
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalCreated {
+    #[topic]
+    pub id: u32,
+    pub proposer: Address,
+    pub vote_end: u32,
+}
+
+/// Emitted for every vote cast
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteCast {
+    #[topic]
+    pub id: u32,
+    #[topic]
+    pub voter: Address,
+    pub support: bool,
+    pub weight: i128,
+}
+
+/// Emitted when a passed proposal enters the timelock
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalQueued {
+    #[topic]
+    pub id: u32,
+    pub eta: u32,
+}
+
+/// Emitted once a proposal's calls have been executed
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalExecuted {
+    #[topic]
+    pub id: u32,
+}
+
+/// Emitted when a queued proposal that outlived its grace period is closed
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalExpired {
+    #[topic]
+    pub id: u32,
+}
@@ -0,0 +1,437 @@
+#![no_std]
+//! # Governor - Proposal-Based Parameter Management
+//!
+//! This contract lets the holders of a voting token manage the workshop contracts instead of a
+//! single admin key. Deploy the aggregator and the zap with the governor as their `admin`; every
+//! admin-gated entrypoint (terms, features, receipts, ...) is then only reachable through a
+//! proposal that passed a vote and waited out the timelock.
+//!
+//! ## Lifecycle:
+//! 1. `propose`: a depositor above the proposal threshold submits a list of contract calls
+//! 2. `vote`: depositors vote for or against during `voting_period` ledgers
+//! 3. `queue`: a proposal that reached quorum with a majority in favour enters the timelock
+//! 4. `execute`: after `timelock_delay` ledgers anyone can execute the calls, within
+//!    `GRACE_PERIOD` ledgers; after that the proposal expires and anyone can `expire` it
+//!
+//! ## Why No Extra Authorization Is Needed:
+//! The workshop contracts call `admin.require_auth()`. When the governor executes a call, it is
+//! the direct invoker of that contract, so its authorization is implicit - exactly like a user
+//! calling the contract themselves.
+//!
+//! ## Voting Power:
+//! Voting power is the amount of voting token deposited in the governor. Deposits backing a
+//! vote stay locked until that proposal's voting ends, so the same tokens cannot vote twice
+//! from different accounts.
+//!
+//! ## Sensitive Changes:
+//! A proposal calling any of `SENSITIVE_CALLS` (fee or router changes, admin batches that may
+//! carry them) is flagged as `sensitive` when it is created. While a sensitive proposal waits in
+//! the timelock, `sensitive_change_pending()` is true and the zap opens its
+//! `rage_quit` exit, so depositors who disagree can leave before the change takes effect. A
+//! sensitive proposal stops counting once it is executed or expired with `expire`, so one that
+//! is never executed does not hold the exit open forever.
+//!
+//! Alternatively, the governor can be deployed with a `share_source` (the zap): voting power is
+//! then the DeFindex vault shares the voter held at the proposal's snapshot ledger, the ledger
+//! before its creation, read from the zap's per-user share checkpoints. Those checkpoints only
+//! move on zap operations, so the power is also capped at the shares the voter holds now, and
+//! voting escrows them in the governor until the vote ends (`withdraw_shares` returns them).
+//! Shares cannot vote from one account and then move to another to vote again.
+
+use soroban_sdk::{
+    Address, Env, String, Symbol, Val, Vec, contract, contractimpl, panic_with_error, token
+};
+
+mod share_source;
+mod storage;
+mod error;
+mod events;
+mod types;
+
+use share_source::ShareSourceClient;
+use storage::{
+    extend_instance_ttl, get_config, get_deposit, get_escrowed_shares, get_locked_until, get_proposal,
+    get_proposal_count, get_sensitive_queued, get_voted, set_config, set_sensitive_queued, set_deposit,
+    set_escrowed_shares, set_locked_until, set_proposal, set_proposal_count, set_voted, Call, Config, Proposal,
+};
+use error::GovernorError;
+use events::{ProposalCreated, ProposalExecuted, ProposalExpired, ProposalQueued, VoteCast};
+use types::ProposalState;
+
+/// Validates that the amount is non-negative
+pub fn check_nonnegative_amount(amount: i128) -> Result<(), GovernorError> {
+    if amount < 0 {
+        Err(GovernorError::NegativeNotAllowed)
+    } else {
+        Ok(())
+    }
+}
+
+/// Largest number of calls in a proposal
+const MAX_CALLS: u32 = 10;
+
+/// Ledgers after its eta during which a queued proposal can still be executed (14 days)
+const GRACE_PERIOD: u32 = 14 * 17_280;
+
+/// Entrypoints whose calls make a proposal sensitive: fee and router changes of the workshop
+/// contracts, and `admin_batch`, whose operations can include both
+const SENSITIVE_CALLS: [&str; 7] = [
+    "set_fee",
+    "set_bucket_penalty",
+    "set_router",
+    "set_aqua_router",
+    "set_phoenix_factory",
+    "set_fallback_dexes",
+    "admin_batch",
+];
+
+/// Whether any of `calls` is to one of `SENSITIVE_CALLS`
+fn is_sensitive(e: &Env, calls: &Vec<Call>) -> bool {
+    calls
+        .iter()
+        .any(|call| SENSITIVE_CALLS.iter().any(|name| call.fn_name == Symbol::new(e, name)))
+}
+
+/// Voting power of `voter` as of `snapshot_ledger`
+///
+/// With a share source, the vault shares at the snapshot, capped at what the voter holds now
+/// (in their wallet or escrowed here) since the checkpoints can lag behind transfers. The
+/// current deposit otherwise.
+fn voting_power(e: &Env, config: &Config, voter: &Address, snapshot_ledger: u32) -> i128 {
+    match &config.share_source {
+        Some(share_source) => {
+            let source = ShareSourceClient::new(e, share_source);
+            let held = token::Client::new(e, &source.vault()).balance(voter) + get_escrowed_shares(e, voter);
+            source.shares_at(voter, &snapshot_ledger).min(held)
+        }
+        None => get_deposit(e, voter),
+    }
+}
+
+/// Stage of a proposal at the current ledger
+fn proposal_state(e: &Env, config: &Config, proposal: &Proposal) -> ProposalState {
+    if proposal.executed {
+        ProposalState::Executed
+    } else if proposal.eta != 0 {
+        if proposal.expired || e.ledger().sequence() > proposal.eta.saturating_add(GRACE_PERIOD) {
+            ProposalState::Expired
+        } else {
+            ProposalState::Queued
+        }
+    } else if e.ledger().sequence() <= proposal.vote_end {
+        ProposalState::Active
+    } else if proposal.for_votes + proposal.against_votes >= config.quorum
+        && proposal.for_votes > proposal.against_votes
+    {
+        ProposalState::Succeeded
+    } else {
+        ProposalState::Defeated
+    }
+}
+
+#[contract]
+struct Governor;
+
+#[contractimpl]
+impl Governor {
+    /// Initialize the governor with its voting token and governance parameters
+    ///
+    /// ## Parameters:
+    /// - `voting_token`: Token deposited to obtain voting power
+    /// - `voting_period`: Length of the voting window, in ledgers
+    /// - `timelock_delay`: Ledgers between queueing and execution
+    /// - `quorum`: Smallest participation for a proposal to pass
+    /// - `proposal_threshold`: Smallest voting power required to propose
+    /// - `share_source`: Optional vault share checkpoint contract (the zap) providing voting power
+    pub fn __constructor(
+        e: Env,
+        voting_token: Address,
+        voting_period: u32,
+        timelock_delay: u32,
+        quorum: i128,
+        proposal_threshold: i128,
+        share_source: Option<Address>,
+    ) {
+        if voting_period == 0 || quorum <= 0 || proposal_threshold < 0 {
+            panic_with_error!(&e, GovernorError::InvalidArgument);
+        }
+        set_config(
+            &e,
+            &Config {
+                voting_token,
+                voting_period,
+                timelock_delay,
+                quorum,
+                proposal_threshold,
+                share_source,
+            },
+        );
+    }
+
+    /// Deposit voting tokens, increasing the depositor's voting power
+    pub fn deposit(e: Env, voter: Address, amount: i128) -> Result<(), GovernorError> {
+        voter.require_auth();
+        check_nonnegative_amount(amount)?;
+        extend_instance_ttl(&e);
+
+        token::Client::new(&e, &get_config(&e).voting_token).transfer(&voter, e.current_contract_address(), &amount);
+        set_deposit(&e, &voter, get_deposit(&e, &voter) + amount);
+        Ok(())
+    }
+
+    /// Withdraw voting tokens
+    ///
+    /// Rejected with `VoteActive` while the deposit backs a vote on a proposal still being voted.
+    pub fn withdraw(e: Env, voter: Address, amount: i128) -> Result<(), GovernorError> {
+        voter.require_auth();
+        check_nonnegative_amount(amount)?;
+        extend_instance_ttl(&e);
+
+        if e.ledger().sequence() <= get_locked_until(&e, &voter) {
+            return Err(GovernorError::VoteActive);
+        }
+        let deposit = get_deposit(&e, &voter);
+        if amount > deposit {
+            return Err(GovernorError::InsufficientBalance);
+        }
+        set_deposit(&e, &voter, deposit - amount);
+
+        token::Client::new(&e, &get_config(&e).voting_token).transfer(&e.current_contract_address(), &voter, &amount);
+        Ok(())
+    }
+
+    /// Return the vault shares escrowed behind the voter's votes
+    ///
+    /// Rejected with `VoteActive` while they back a vote on a proposal still being voted.
+    ///
+    /// ## Returns:
+    /// Vault shares returned
+    pub fn withdraw_shares(e: Env, voter: Address) -> Result<i128, GovernorError> {
+        voter.require_auth();
+        extend_instance_ttl(&e);
+
+        if e.ledger().sequence() <= get_locked_until(&e, &voter) {
+            return Err(GovernorError::VoteActive);
+        }
+        let Some(share_source) = get_config(&e).share_source else {
+            return Ok(0);
+        };
+        let shares = get_escrowed_shares(&e, &voter);
+        if shares > 0 {
+            set_escrowed_shares(&e, &voter, 0);
+            let vault = ShareSourceClient::new(&e, &share_source).vault();
+            token::Client::new(&e, &vault).transfer(&e.current_contract_address(), &voter, &shares);
+        }
+        Ok(shares)
+    }
+
+    /// Create a proposal executing `calls` in order if it passes
+    ///
+    /// The proposal is sensitive, opening the zap's exit while it is queued, when one of the
+    /// calls is to `SENSITIVE_CALLS` (see the module docs).
+    ///
+    /// ## Returns:
+    /// Id of the new proposal
+    pub fn propose(
+        e: Env,
+        proposer: Address,
+        calls: Vec<Call>,
+        description: String,
+    ) -> Result<u32, GovernorError> {
+        proposer.require_auth();
+        extend_instance_ttl(&e);
+
+        let config = get_config(&e);
+        // Snapshot the ledger before this one, so balances can't change after the snapshot
+        // within the proposal's own ledger
+        let snapshot_ledger = e.ledger().sequence().saturating_sub(1);
+        if voting_power(&e, &config, &proposer, snapshot_ledger) < config.proposal_threshold {
+            return Err(GovernorError::BelowProposalThreshold);
+        }
+        if calls.is_empty() || calls.len() > MAX_CALLS {
+            return Err(GovernorError::InvalidArgument);
+        }
+
+        let sensitive = is_sensitive(&e, &calls);
+        let id = get_proposal_count(&e);
+        let vote_end = e.ledger().sequence() + config.voting_period;
+        set_proposal(
+            &e,
+            &Proposal {
+                id,
+                proposer: proposer.clone(),
+                description,
+                calls,
+                snapshot_ledger,
+                vote_end,
+                for_votes: 0,
+                against_votes: 0,
+                eta: 0,
+                executed: false,
+                expired: false,
+                sensitive,
+            },
+        );
+        set_proposal_count(&e, id + 1);
+
+        ProposalCreated { id, proposer, vote_end }.publish(&e);
+        Ok(id)
+    }
+
+    /// Vote on an active proposal with the voter's whole voting power
+    ///
+    /// ## Returns:
+    /// Voting power cast
+    pub fn vote(e: Env, voter: Address, id: u32, support: bool) -> Result<i128, GovernorError> {
+        voter.require_auth();
+        extend_instance_ttl(&e);
+
+        let config = get_config(&e);
+        let mut proposal = get_proposal(&e, id).ok_or(GovernorError::ProposalNotFound)?;
+        if proposal_state(&e, &config, &proposal) != ProposalState::Active {
+            return Err(GovernorError::VotingClosed);
+        }
+        if get_voted(&e, id, &voter) {
+            return Err(GovernorError::AlreadyVoted);
+        }
+        let weight = voting_power(&e, &config, &voter, proposal.snapshot_ledger);
+        if weight == 0 {
+            return Err(GovernorError::InvalidArgument);
+        }
+
+        if support {
+            proposal.for_votes += weight;
+        } else {
+            proposal.against_votes += weight;
+        }
+        set_proposal(&e, &proposal);
+        set_voted(&e, id, &voter);
+        // Shares backing the vote move into escrow, so they cannot vote again from another account
+        if let Some(share_source) = &config.share_source {
+            let escrowed = get_escrowed_shares(&e, &voter);
+            if weight > escrowed {
+                let vault = ShareSourceClient::new(&e, share_source).vault();
+                token::Client::new(&e, &vault).transfer(&voter, e.current_contract_address(), &(weight - escrowed));
+                set_escrowed_shares(&e, &voter, weight);
+            }
+        }
+        set_locked_until(&e, &voter, get_locked_until(&e, &voter).max(proposal.vote_end));
+
+        VoteCast { id, voter, support, weight }.publish(&e);
+        Ok(weight)
+    }
+
+    /// Queue a passed proposal into the timelock
+    ///
+    /// ## Returns:
+    /// Ledger from which the proposal can be executed
+    pub fn queue(e: Env, id: u32) -> Result<u32, GovernorError> {
+        extend_instance_ttl(&e);
+
+        let config = get_config(&e);
+        let mut proposal = get_proposal(&e, id).ok_or(GovernorError::ProposalNotFound)?;
+        if proposal_state(&e, &config, &proposal) != ProposalState::Succeeded {
+            return Err(GovernorError::InvalidState);
+        }
+
+        // Never 0, which marks an unqueued proposal
+        proposal.eta = (e.ledger().sequence() + config.timelock_delay).max(1);
+        set_proposal(&e, &proposal);
+        if proposal.sensitive {
+            set_sensitive_queued(&e, get_sensitive_queued(&e) + 1);
+        }
+
+        ProposalQueued { id, eta: proposal.eta }.publish(&e);
+        Ok(proposal.eta)
+    }
+
+    /// Execute the calls of a queued proposal once its timelock delay has elapsed
+    ///
+    /// Permissionless. The calls run in order with this contract as the invoker; if any of
+    /// them fails the whole execution reverts and the proposal stays queued. Rejected with
+    /// `InvalidState` once `GRACE_PERIOD` ledgers have passed since the eta.
+    pub fn execute(e: Env, id: u32) -> Result<(), GovernorError> {
+        extend_instance_ttl(&e);
+
+        let mut proposal = get_proposal(&e, id).ok_or(GovernorError::ProposalNotFound)?;
+        if proposal_state(&e, &get_config(&e), &proposal) != ProposalState::Queued {
+            return Err(GovernorError::InvalidState);
+        }
+        if e.ledger().sequence() < proposal.eta {
+            return Err(GovernorError::TimelockNotExpired);
+        }
+
+        // Mark as executed before calling out, so a call cannot re-enter and execute it again
+        proposal.executed = true;
+        set_proposal(&e, &proposal);
+        if proposal.sensitive {
+            set_sensitive_queued(&e, get_sensitive_queued(&e) - 1);
+        }
+
+        for call in proposal.calls.iter() {
+            e.invoke_contract::<Val>(&call.contract, &call.fn_name, call.args);
+        }
+
+        ProposalExecuted { id }.publish(&e);
+        Ok(())
+    }
+
+    /// Close a queued proposal that was not executed within `GRACE_PERIOD` ledgers of its eta
+    ///
+    /// Permissionless. A sensitive proposal stops counting towards `sensitive_change_pending`,
+    /// closing the zap's `rage_quit` exit it held open. Rejected with `InvalidState` unless the
+    /// proposal expired and was not closed yet.
+    pub fn expire(e: Env, id: u32) -> Result<(), GovernorError> {
+        extend_instance_ttl(&e);
+
+        let mut proposal = get_proposal(&e, id).ok_or(GovernorError::ProposalNotFound)?;
+        if proposal.expired || proposal_state(&e, &get_config(&e), &proposal) != ProposalState::Expired {
+            return Err(GovernorError::InvalidState);
+        }
+
+        proposal.expired = true;
+        set_proposal(&e, &proposal);
+        if proposal.sensitive {
+            set_sensitive_queued(&e, get_sensitive_queued(&e) - 1);
+        }
+
+        ProposalExpired { id }.publish(&e);
+        Ok(())
+    }
+
+    /// Proposal details
+    pub fn proposal(e: Env, id: u32) -> Option<Proposal> {
+        get_proposal(&e, id)
+    }
+
+    /// Stage of a proposal at the current ledger
+    pub fn state(e: Env, id: u32) -> Result<ProposalState, GovernorError> {
+        let proposal = get_proposal(&e, id).ok_or(GovernorError::ProposalNotFound)?;
+        Ok(proposal_state(&e, &get_config(&e), &proposal))
+    }
+
+    /// Whether a sensitive proposal is waiting in the timelock
+    pub fn sensitive_change_pending(e: Env) -> bool {
+        get_sensitive_queued(&e) > 0
+    }
+
+    /// Number of proposals created so far
+    pub fn proposal_count(e: Env) -> u32 {
+        get_proposal_count(&e)
+    }
+
+    /// Voting tokens deposited by a voter
+    pub fn deposit_of(e: Env, voter: Address) -> i128 {
+        get_deposit(&e, &voter)
+    }
+
+    /// Vault shares a voter has escrowed behind their votes
+    pub fn escrowed_shares_of(e: Env, voter: Address) -> i128 {
+        get_escrowed_shares(&e, &voter)
+    }
+
+    /// Governance parameters
+    pub fn config(e: Env) -> Config {
+        get_config(&e)
+    }
+}
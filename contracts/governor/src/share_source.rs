@@ -0,0 +1,9 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+// Interface of a contract keeping per-user vault share checkpoints, such as `defindex-zap`
+#[allow(dead_code)]
+#[contractclient(name = "ShareSourceClient")]
+pub trait ShareSource {
+    fn shares_at(e: Env, user: Address, ledger: u32) -> i128;
+    fn vault(e: Env) -> Address;
+}
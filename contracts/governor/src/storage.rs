@@ -0,0 +1,205 @@
+use soroban_sdk::{contracttype, Address, Env, String, Symbol, Val, Vec};
+
+#[derive(Clone)]
+#[contracttype]
+
+enum DataKey {
+    Config,
+    ProposalCount,
+    Proposal(u32),
+    Voted(u32, Address),
+    Deposit(Address),
+    LockedUntil(Address),
+    EscrowedShares(Address),
+    SensitiveQueued,
+}
+
+/// Governance parameters, fixed at deployment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// Token deposited to obtain voting power
+    pub voting_token: Address,
+    /// Length of the voting window, in ledgers
+    pub voting_period: u32,
+    /// Ledgers between queueing a proposal and being able to execute it
+    pub timelock_delay: u32,
+    /// Smallest `for + against` participation for a proposal to pass
+    pub quorum: i128,
+    /// Smallest voting power required to create a proposal
+    pub proposal_threshold: i128,
+    /// Contract with vault share checkpoints; when set, voting power is the voter's shares at
+    /// the proposal's snapshot ledger instead of their deposit
+    pub share_source: Option<Address>,
+}
+
+/// Contract call executed by a proposal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Call {
+    pub contract: Address,
+    pub fn_name: Symbol,
+    pub args: Vec<Val>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub id: u32,
+    pub proposer: Address,
+    pub description: String,
+    pub calls: Vec<Call>,
+    /// Ledger whose vault share balances count as voting power (with a share source)
+    pub snapshot_ledger: u32,
+    /// Last ledger at which votes are accepted
+    pub vote_end: u32,
+    pub for_votes: i128,
+    pub against_votes: i128,
+    /// Ledger from which a queued proposal can be executed (0 until queued)
+    pub eta: u32,
+    pub executed: bool,
+    /// Set by `expire`, once the proposal no longer counts as queued
+    pub expired: bool,
+    /// Flags a sensitive parameter change (fees, router, ...), derived from the calls, that
+    /// opens the zap's rage-quit exit while it waits in the timelock
+    pub sensitive: bool,
+}
+
+#[allow(dead_code)]
+const DAY_IN_LEDGERS: u32 = 17280;
+#[allow(dead_code)]
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+#[allow(dead_code)]
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+const PERSISTENT_BUMP_AMOUNT: u32 = 60 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+pub fn extend_instance_ttl(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Governance parameters
+pub fn set_config(e: &Env, config: &Config) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Config, config);
+}
+
+pub fn get_config(e: &Env) -> Config {
+    e.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .unwrap()
+}
+
+// Proposals (ids are sequential, starting at 0)
+pub fn set_proposal_count(e: &Env, count: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ProposalCount, &count);
+}
+
+pub fn get_proposal_count(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::ProposalCount)
+        .unwrap_or(0)
+}
+
+pub fn set_proposal(e: &Env, proposal: &Proposal) {
+    let key = DataKey::Proposal(proposal.id);
+    e.storage().persistent().set(&key, proposal);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_proposal(e: &Env, id: u32) -> Option<Proposal> {
+    let key = DataKey::Proposal(id);
+    let proposal = e.storage().persistent().get(&key);
+    if proposal.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    proposal
+}
+
+// Whether a voter already voted on a proposal
+pub fn set_voted(e: &Env, id: u32, voter: &Address) {
+    let key = DataKey::Voted(id, voter.clone());
+    e.storage().persistent().set(&key, &true);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_voted(e: &Env, id: u32, voter: &Address) -> bool {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Voted(id, voter.clone()))
+        .unwrap_or(false)
+}
+
+// Voting tokens deposited by each voter
+pub fn set_deposit(e: &Env, voter: &Address, amount: i128) {
+    let key = DataKey::Deposit(voter.clone());
+    e.storage().persistent().set(&key, &amount);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_deposit(e: &Env, voter: &Address) -> i128 {
+    let key = DataKey::Deposit(voter.clone());
+    let amount = e.storage().persistent().get(&key);
+    if amount.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    amount.unwrap_or(0)
+}
+
+// Ledger until which a voter's deposit or escrowed shares back open votes
+pub fn set_locked_until(e: &Env, voter: &Address, ledger: u32) {
+    let key = DataKey::LockedUntil(voter.clone());
+    e.storage().persistent().set(&key, &ledger);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_locked_until(e: &Env, voter: &Address) -> u32 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::LockedUntil(voter.clone()))
+        .unwrap_or(0)
+}
+
+// Vault shares each voter escrowed behind their votes (with a share source)
+pub fn set_escrowed_shares(e: &Env, voter: &Address, shares: i128) {
+    let key = DataKey::EscrowedShares(voter.clone());
+    e.storage().persistent().set(&key, &shares);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_escrowed_shares(e: &Env, voter: &Address) -> i128 {
+    let key = DataKey::EscrowedShares(voter.clone());
+    let shares = e.storage().persistent().get(&key);
+    if shares.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    shares.unwrap_or(0)
+}
+
+// Number of sensitive proposals queued but not executed yet
+pub fn set_sensitive_queued(e: &Env, count: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::SensitiveQueued, &count);
+}
+
+pub fn get_sensitive_queued(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::SensitiveQueued)
+        .unwrap_or(0)
+}
@@ -0,0 +1,20 @@
+use soroban_sdk::contracttype;
+
+/// Lifecycle stage of a proposal
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ProposalState {
+    /// Accepting votes
+    Active = 0,
+    /// Voting ended without quorum or majority
+    Defeated = 1,
+    /// Voting passed; waiting to be queued
+    Succeeded = 2,
+    /// Waiting for the timelock delay to elapse
+    Queued = 3,
+    /// Calls executed
+    Executed = 4,
+    /// Queued but not executed within the grace period
+    Expired = 5,
+}
@@ -0,0 +1,22 @@
+use common::pay::SwapResult;
+use soroban_sdk::{contractclient, Address, Env, Vec};
+
+// Interface of the `soroswap-auth` aggregator used to execute the rebalancing swaps
+#[allow(dead_code)]
+#[contractclient(name = "AggregatorClient")]
+pub trait Aggregator {
+    fn accept_terms(e: Env, caller: Address, version: u32);
+    #[allow(clippy::too_many_arguments)]
+    fn swap(
+        e: Env,
+        caller: Address,
+        token_in: Address,
+        token_out: Address,
+        amount: i128,
+        amount_out_min: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+        path: Option<Vec<Address>>,
+        referrer: Option<Address>,
+    ) -> SwapResult;
+}
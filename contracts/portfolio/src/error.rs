@@ -0,0 +1,29 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PortfolioError {
+    /// Unauthorized caller
+    Unauthorized = 1,
+    /// Invalid parameters
+    InvalidArgument = 2,
+
+    NegativeNotAllowed = 3,
+    /// Owner has not defined a portfolio
+    PortfolioNotFound = 4,
+    /// Asset is not part of the owner's portfolio
+    AssetNotInPortfolio = 5,
+    /// Withdrawal exceeds the owner's balance
+    InsufficientBalance = 6,
+    /// No asset drifted past the owner's threshold
+    WithinThreshold = 7,
+    /// An asset could not be priced against the base asset
+    PriceUnavailable = 8,
+    /// A swap returned less than the owner's slippage bound allows
+    SlippageExceeded = 9,
+    /// The owner has a two-phase rebalance pending
+    RebalanceInProgress = 10,
+    /// The owner has no two-phase rebalance pending
+    NoRebalanceInProgress = 11,
+}
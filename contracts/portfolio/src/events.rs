@@ -0,0 +1,52 @@
+use soroban_sdk::{contractevent, Address};
+
+/// Emitted after a rebalance, summarizing what was traded
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rebalanced {
+    #[topic]
+    pub owner: Address,
+    pub caller: Address,
+    /// Portfolio value before the rebalance, in base asset units
+    pub total_value: i128,
+    /// Largest absolute drift from target before the rebalance, in basis points
+    pub max_drift_bps: u32,
+    /// Number of swaps routed through the aggregator
+    pub swaps: u32,
+}
+
+/// Emitted when a two-phase rebalance is opened
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RebalanceStarted {
+    #[topic]
+    pub owner: Address,
+    pub caller: Address,
+    /// Number of legs left for the keepers
+    pub legs: u32,
+}
+
+/// Emitted after every `execute_rebalance` call that leaves legs pending
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RebalanceProgressed {
+    #[topic]
+    pub owner: Address,
+    pub caller: Address,
+    /// Legs executed by this call
+    pub executed: u32,
+    /// Legs still pending
+    pub remaining: u32,
+}
+
+/// Emitted when the owner cancels a two-phase rebalance
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RebalanceCancelled {
+    #[topic]
+    pub owner: Address,
+    /// Legs that had already been executed
+    pub executed: u32,
+    /// Legs whose escrowed input went back to the owner's balances
+    pub refunded: u32,
+}
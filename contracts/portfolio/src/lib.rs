@@ -0,0 +1,593 @@
+#![no_std]
+//! # Portfolio - Target-Allocation Rebalancing
+//!
+//! This contract composes the workshop pieces into a self-balancing portfolio. A user defines
+//! target weights across a set of assets and deposits them into the contract; `rebalance()`
+//! measures how far the holdings drifted from the targets and routes the swaps that bring them
+//! back through the `soroswap-auth` aggregator.
+//!
+//! ## Key Characteristics:
+//! - The contract holds the assets in custody and keeps a per-owner balance of each one
+//! - Holdings are valued in the base asset (the first asset) with SEP-40 oracle prices, so a
+//!   manipulated pool can neither trigger a rebalance nor loosen a swap's minimum
+//! - Overweight assets are sold into the base asset first, then the base asset buys the
+//!   underweight ones
+//! - The owner, or a keeper the owner designates, can trigger the rebalance
+//!
+//! ## User-Set Bounds:
+//! A keeper can only act within the bounds the owner stored with the portfolio:
+//! - `drift_threshold_bps`: the rebalance is rejected unless some asset drifted at least this far
+//! - `max_slippage_bps`: every swap must return at least its oracle value minus this tolerance;
+//!   the aggregator receives that minimum, so the venue itself rejects a worse fill
+//!
+//! ## Two-Phase Rebalancing:
+//! A portfolio with many assets may need more swaps than fit in one transaction's budget.
+//! `start_rebalance()` does the valuation, moves the amounts to be traded from the owner's
+//! balances into a pending job and records the swaps; `execute_rebalance()` then runs them a few
+//! at a time, from the owner or the keeper, until the job settles. `rebalance_job()` shows the
+//! progress, and the owner can `cancel_rebalance()` to get the unspent escrow back.
+//!
+//! ## Authorization Chain:
+//! The aggregator pulls the input tokens from its caller, which is this contract. That transfer
+//! is made by the aggregator, not by this contract directly, so every swap is preceded by an
+//! `authorize_as_current_contract` entry covering exactly that `token.transfer`.
+
+use common::{
+    auth::AuthBuilder,
+    math::{Bps, BPS_DENOMINATOR},
+};
+use soroban_sdk::{
+    Address, Env, Vec, contract, contractimpl, token
+};
+
+mod aggregator;
+mod oracle;
+mod storage;
+mod error;
+mod events;
+
+use aggregator::AggregatorClient;
+use oracle::{Asset, PriceOracleClient};
+use storage::{
+    extend_instance_ttl, get_admin, get_aggregator_address, get_balance, get_oracle, get_portfolio,
+    set_admin, set_aggregator_address, set_balance, set_oracle, set_portfolio,
+    get_rebalance_job, remove_rebalance_job, set_rebalance_job,
+    Portfolio, RebalanceJob, RebalanceLeg,
+};
+use error::PortfolioError;
+use events::{RebalanceCancelled, RebalanceProgressed, RebalanceStarted, Rebalanced};
+
+/// Validates that the amount is non-negative
+pub fn check_nonnegative_amount(amount: i128) -> Result<(), PortfolioError> {
+    if amount < 0 {
+        Err(PortfolioError::NegativeNotAllowed)
+    } else {
+        Ok(())
+    }
+}
+
+/// Largest number of assets in a portfolio, bounding the work done by `rebalance`
+const MAX_ASSETS: u32 = 8;
+
+/// Oldest oracle price accepted, in seconds of ledger time
+const MAX_PRICE_AGE: u64 = 15 * 60;
+
+/// Oracle price of `asset`, rejected when missing, non-positive or older than `MAX_PRICE_AGE`
+fn price(e: &Env, oracle: &PriceOracleClient, asset: &Address) -> Result<i128, PortfolioError> {
+    let data = oracle
+        .lastprice(&Asset::Stellar(asset.clone()))
+        .ok_or(PortfolioError::PriceUnavailable)?;
+    if data.price <= 0 || e.ledger().timestamp().saturating_sub(data.timestamp) > MAX_PRICE_AGE {
+        return Err(PortfolioError::PriceUnavailable);
+    }
+    Ok(data.price)
+}
+
+/// Value of `amount` of `asset` in units of `base`, at the oracle's prices
+///
+/// Pool prices are never used, so neither the drift check nor the swap minimums derived from
+/// this value move with a manipulated pool.
+fn quote(e: &Env, asset: &Address, base: &Address, amount: i128) -> Result<i128, PortfolioError> {
+    if asset == base || amount == 0 {
+        return Ok(amount);
+    }
+
+    let oracle = PriceOracleClient::new(e, &get_oracle(e));
+    let price_in = price(e, &oracle, asset)?;
+    let price_out = price(e, &oracle, base)?;
+    amount
+        .checked_mul(price_in)
+        .map(|value| value / price_out)
+        .ok_or(PortfolioError::PriceUnavailable)
+}
+
+/// Swaps `amount` of `token_in` to `token_out` through the aggregator
+///
+/// Returns the amount of `token_out` that actually arrived, which must reach `expected_out`
+/// minus the owner's slippage tolerance.
+fn swap_via_aggregator(
+    e: &Env,
+    token_in: &Address,
+    token_out: &Address,
+    amount: i128,
+    expected_out: i128,
+    max_slippage_bps: Bps,
+    deadline_delta: Option<u32>,
+) -> Result<i128, PortfolioError> {
+    let aggregator_address = get_aggregator_address(e);
+    let this = e.current_contract_address();
+
+    // The minimum goes to the aggregator so the venue itself rejects a sandwiched fill; the
+    // balance check below still guards against an aggregator that under-delivers
+    let min_out = max_slippage_bps.complement().apply(expected_out);
+    let token_out_client = token::Client::new(e, token_out);
+    let balance_before = token_out_client.balance(&this);
+    // The aggregator requires a deadline; the swap runs within this invocation, so the current
+    // ledger timestamp bounds it when the caller gave no delta
+    let deadline = deadline_delta.is_none().then(|| e.ledger().timestamp());
+
+    // The aggregator takes custody with token.transfer(from=this contract, to=aggregator);
+    // that nested call needs this contract's explicit authorization, which only covers the
+    // next contract call, so the aggregator call must follow directly
+    AuthBuilder::new(e)
+        .transfer(token_in, &this, &aggregator_address, amount)
+        .authorize();
+    AggregatorClient::new(e, &aggregator_address).swap(
+        &this,
+        token_in,
+        token_out,
+        &amount,
+        &min_out,
+        &deadline,
+        &deadline_delta,
+        &None,
+        &None,
+    );
+    let received = token_out_client.balance(&this) - balance_before;
+
+    if received < min_out {
+        return Err(PortfolioError::SlippageExceeded);
+    }
+    Ok(received)
+}
+
+/// Validates a portfolio definition
+fn check_portfolio(portfolio: &Portfolio) -> Result<(), PortfolioError> {
+    let count = portfolio.assets.len();
+    if !(2..=MAX_ASSETS).contains(&count) || portfolio.weights.len() != count {
+        return Err(PortfolioError::InvalidArgument);
+    }
+    if portfolio.drift_threshold_bps == Bps::ZERO
+        || !portfolio.drift_threshold_bps.is_valid()
+        || !portfolio.max_slippage_bps.is_valid()
+    {
+        return Err(PortfolioError::InvalidArgument);
+    }
+
+    let mut total_weight: u32 = 0;
+    for weight in portfolio.weights.iter() {
+        total_weight = total_weight.saturating_add(weight.value());
+    }
+    if total_weight != BPS_DENOMINATOR {
+        return Err(PortfolioError::InvalidArgument);
+    }
+
+    for (index, asset) in portfolio.assets.iter().enumerate() {
+        if portfolio.assets.last_index_of(&asset) != Some(index as u32) {
+            return Err(PortfolioError::InvalidArgument);
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `caller` may rebalance the owner's portfolio
+fn check_operator(portfolio: &Portfolio, caller: &Address, owner: &Address) -> Result<(), PortfolioError> {
+    if caller != owner && portfolio.keeper.as_ref() != Some(caller) {
+        return Err(PortfolioError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Values the owner's holdings, escrows what has to be traded and lays out the swaps
+///
+/// Every holding is valued in the base asset. If no asset drifted from its target by at least
+/// `drift_threshold_bps`, the rebalance is rejected so keepers cannot churn the portfolio. The
+/// excess of every overweight asset and the whole base balance leave the owner's balances for
+/// the returned job; the remaining base asset is credited back when the job closes.
+fn open_rebalance(e: &Env, caller: &Address, owner: &Address, portfolio: &Portfolio) -> Result<RebalanceJob, PortfolioError> {
+    // Value every holding in the base asset
+    let base = portfolio.assets.get_unchecked(0);
+    let mut balances: Vec<i128> = Vec::new(e);
+    let mut values: Vec<i128> = Vec::new(e);
+    let mut total_value: i128 = 0;
+    for asset in portfolio.assets.iter() {
+        let balance = get_balance(e, owner, &asset);
+        let value = quote(e, &asset, &base, balance)?;
+        balances.push_back(balance);
+        values.push_back(value);
+        total_value += value;
+    }
+    if total_value == 0 {
+        return Err(PortfolioError::WithinThreshold);
+    }
+
+    // Largest distance between an asset's current weight and its target
+    let mut targets: Vec<i128> = Vec::new(e);
+    let mut max_drift_bps: u32 = 0;
+    for (index, weight) in portfolio.weights.iter().enumerate() {
+        let value = values.get_unchecked(index as u32);
+        targets.push_back(weight.apply(total_value));
+        let current_bps = value * BPS_DENOMINATOR as i128 / total_value;
+        max_drift_bps = max_drift_bps.max((current_bps - weight.value() as i128).unsigned_abs() as u32);
+    }
+    if max_drift_bps < portfolio.drift_threshold_bps.value() {
+        return Err(PortfolioError::WithinThreshold);
+    }
+
+    let mut legs: Vec<RebalanceLeg> = Vec::new(e);
+
+    // Sell the excess of every overweight asset into the base asset
+    for index in 1..portfolio.assets.len() {
+        let value = values.get_unchecked(index);
+        let target = targets.get_unchecked(index);
+        if value <= target {
+            continue;
+        }
+
+        let asset = portfolio.assets.get_unchecked(index);
+        let balance = balances.get_unchecked(index);
+        let amount = balance * (value - target) / value;
+        if amount == 0 {
+            continue;
+        }
+
+        set_balance(e, owner, &asset, balance - amount);
+        legs.push_back(RebalanceLeg { token_in: asset, token_out: base.clone(), amount });
+    }
+
+    // Spend the base asset on every underweight asset, as far as it goes
+    for index in 1..portfolio.assets.len() {
+        let amount = targets.get_unchecked(index) - values.get_unchecked(index);
+        if amount <= 0 {
+            continue;
+        }
+        legs.push_back(RebalanceLeg {
+            token_in: base.clone(),
+            token_out: portfolio.assets.get_unchecked(index),
+            amount,
+        });
+    }
+
+    set_balance(e, owner, &base, 0);
+    Ok(RebalanceJob {
+        caller: caller.clone(),
+        base,
+        legs,
+        next_leg: 0,
+        base_escrow: balances.get_unchecked(0),
+        swaps: 0,
+        total_value,
+        max_drift_bps,
+    })
+}
+
+/// Executes the next leg of a rebalance
+///
+/// Sells add their proceeds to the job's base escrow; buys spend from it, as far as it goes,
+/// and credit what they bought to the owner.
+fn execute_leg(
+    e: &Env,
+    owner: &Address,
+    job: &mut RebalanceJob,
+    max_slippage_bps: Bps,
+    deadline_delta: Option<u32>,
+) -> Result<(), PortfolioError> {
+    let leg = job.legs.get_unchecked(job.next_leg);
+    job.next_leg += 1;
+
+    if leg.token_in == job.base {
+        let amount = leg.amount.min(job.base_escrow);
+        if amount <= 0 {
+            return Ok(());
+        }
+        let expected_out = quote(e, &leg.token_in, &leg.token_out, amount)?;
+        let received = swap_via_aggregator(e, &leg.token_in, &leg.token_out, amount, expected_out, max_slippage_bps, deadline_delta)?;
+        set_balance(e, owner, &leg.token_out, get_balance(e, owner, &leg.token_out) + received);
+        job.base_escrow -= amount;
+    } else {
+        let expected_out = quote(e, &leg.token_in, &leg.token_out, leg.amount)?;
+        let received = swap_via_aggregator(e, &leg.token_in, &leg.token_out, leg.amount, expected_out, max_slippage_bps, deadline_delta)?;
+        job.base_escrow += received;
+    }
+    job.swaps += 1;
+    Ok(())
+}
+
+/// Credits the job's remaining base asset to the owner and reports the rebalance
+fn close_rebalance(e: &Env, owner: &Address, job: RebalanceJob) {
+    set_balance(e, owner, &job.base, get_balance(e, owner, &job.base) + job.base_escrow);
+
+    Rebalanced {
+        owner: owner.clone(),
+        caller: job.caller,
+        total_value: job.total_value,
+        max_drift_bps: job.max_drift_bps,
+        swaps: job.swaps,
+    }
+    .publish(e);
+}
+
+#[contract]
+struct PortfolioContract;
+
+#[contractimpl]
+impl PortfolioContract {
+    /// Initialize the contract with the admin, the aggregator and the price oracle
+    ///
+    /// The aggregator executes the rebalancing swaps; the SEP-40 oracle (e.g. Reflector) prices
+    /// the holdings and the minimum output of every swap.
+    pub fn __constructor(e: Env, admin: Address, aggregator_address: Address, oracle: Address) {
+        set_admin(&e, admin);
+        set_aggregator_address(&e, aggregator_address);
+        set_oracle(&e, oracle);
+    }
+
+    /// Acknowledge the aggregator's terms of use on behalf of this contract (admin only)
+    ///
+    /// Required before rebalancing whenever the aggregator publishes a terms version.
+    pub fn accept_aggregator_terms(e: Env, version: u32) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        AggregatorClient::new(&e, &get_aggregator_address(&e))
+            .accept_terms(&e.current_contract_address(), &version);
+    }
+
+    /// Define or replace the owner's target allocation and rebalancing bounds
+    ///
+    /// ## Parameters:
+    /// - `owner`: Portfolio owner (must sign the transaction)
+    /// - `assets`: Held assets, at most 8; the first one is the base asset
+    /// - `weights`: Target weight of each asset in basis points, adding up to 10_000
+    /// - `drift_threshold_bps`: Smallest drift that justifies a rebalance
+    /// - `max_slippage_bps`: Largest accepted shortfall of each swap against its oracle value
+    /// - `keeper`: Optional account allowed to trigger `rebalance`
+    pub fn set_portfolio(
+        e: Env,
+        owner: Address,
+        assets: Vec<Address>,
+        weights: Vec<Bps>,
+        drift_threshold_bps: Bps,
+        max_slippage_bps: Bps,
+        keeper: Option<Address>,
+    ) -> Result<(), PortfolioError> {
+        owner.require_auth();
+        extend_instance_ttl(&e);
+        if get_rebalance_job(&e, &owner).is_some() {
+            return Err(PortfolioError::RebalanceInProgress);
+        }
+
+        let portfolio = Portfolio {
+            assets,
+            weights,
+            drift_threshold_bps,
+            max_slippage_bps,
+            keeper,
+        };
+        check_portfolio(&portfolio)?;
+        set_portfolio(&e, &owner, &portfolio);
+        Ok(())
+    }
+
+    /// Portfolio definition of an owner
+    pub fn portfolio(e: Env, owner: Address) -> Option<Portfolio> {
+        get_portfolio(&e, &owner)
+    }
+
+    /// Amount of `asset` held in custody for an owner
+    pub fn balance(e: Env, owner: Address, asset: Address) -> i128 {
+        get_balance(&e, &owner, &asset)
+    }
+
+    /// Deposit one of the portfolio assets into custody
+    ///
+    /// ## Returns:
+    /// Amount credited to the owner (what actually arrived)
+    pub fn deposit(e: Env, owner: Address, asset: Address, amount: i128) -> Result<i128, PortfolioError> {
+        owner.require_auth();
+        check_nonnegative_amount(amount)?;
+        extend_instance_ttl(&e);
+
+        let portfolio = get_portfolio(&e, &owner).ok_or(PortfolioError::PortfolioNotFound)?;
+        if !portfolio.assets.contains(&asset) {
+            return Err(PortfolioError::AssetNotInPortfolio);
+        }
+
+        let token_client = token::Client::new(&e, &asset);
+        let this = e.current_contract_address();
+        let balance_before = token_client.balance(&this);
+        token_client.transfer(&owner, &this, &amount);
+        let received = token_client.balance(&this) - balance_before;
+
+        set_balance(&e, &owner, &asset, get_balance(&e, &owner, &asset) + received);
+        Ok(received)
+    }
+
+    /// Withdraw an asset from custody back to the owner
+    ///
+    /// Any asset with a balance can be withdrawn, including one dropped from the portfolio.
+    pub fn withdraw(e: Env, owner: Address, asset: Address, amount: i128) -> Result<(), PortfolioError> {
+        owner.require_auth();
+        check_nonnegative_amount(amount)?;
+        extend_instance_ttl(&e);
+
+        let balance = get_balance(&e, &owner, &asset);
+        if amount > balance {
+            return Err(PortfolioError::InsufficientBalance);
+        }
+        set_balance(&e, &owner, &asset, balance - amount);
+
+        token::Client::new(&e, &asset).transfer(&e.current_contract_address(), &owner, &amount);
+        Ok(())
+    }
+
+    /// Bring the owner's holdings back to their target weights
+    ///
+    /// Every holding is valued in the base asset. If no asset drifted from its target by at
+    /// least `drift_threshold_bps`, the call is rejected so keepers cannot churn the portfolio.
+    /// Otherwise the overweight assets are sold into the base asset and the base asset then
+    /// buys the underweight ones, each swap bounded by `max_slippage_bps`. A `Rebalanced`
+    /// event reports the outcome.
+    ///
+    /// ## Parameters:
+    /// - `caller`: The owner or the owner's keeper (must sign the transaction)
+    /// - `owner`: Portfolio to rebalance
+    /// - `deadline_delta`: Optional deadline of each swap, in ledgers from now
+    ///
+    /// ## Returns:
+    /// Number of swaps executed
+    pub fn rebalance(e: Env, caller: Address, owner: Address, deadline_delta: Option<u32>) -> Result<u32, PortfolioError> {
+        caller.require_auth();
+        extend_instance_ttl(&e);
+
+        let portfolio = get_portfolio(&e, &owner).ok_or(PortfolioError::PortfolioNotFound)?;
+        check_operator(&portfolio, &caller, &owner)?;
+        if get_rebalance_job(&e, &owner).is_some() {
+            return Err(PortfolioError::RebalanceInProgress);
+        }
+
+        let mut job = open_rebalance(&e, &caller, &owner, &portfolio)?;
+        while job.next_leg < job.legs.len() {
+            execute_leg(&e, &owner, &mut job, portfolio.max_slippage_bps, deadline_delta)?;
+        }
+        let swaps = job.swaps;
+        close_rebalance(&e, &owner, job);
+        Ok(swaps)
+    }
+
+    /// Open a two-phase rebalance, for portfolios whose swaps exceed one transaction's budget
+    ///
+    /// Does the same valuation and drift check as `rebalance`, moves the amounts to be traded
+    /// from the owner's balances into a pending job and records its swaps without executing
+    /// them. While the job is pending the portfolio definition cannot change.
+    ///
+    /// ## Parameters:
+    /// - `caller`: The owner or the owner's keeper (must sign the transaction)
+    /// - `owner`: Portfolio to rebalance
+    ///
+    /// ## Returns:
+    /// Number of legs left for `execute_rebalance`
+    pub fn start_rebalance(e: Env, caller: Address, owner: Address) -> Result<u32, PortfolioError> {
+        caller.require_auth();
+        extend_instance_ttl(&e);
+
+        let portfolio = get_portfolio(&e, &owner).ok_or(PortfolioError::PortfolioNotFound)?;
+        check_operator(&portfolio, &caller, &owner)?;
+        if get_rebalance_job(&e, &owner).is_some() {
+            return Err(PortfolioError::RebalanceInProgress);
+        }
+
+        let job = open_rebalance(&e, &caller, &owner, &portfolio)?;
+        let legs = job.legs.len();
+        if legs == 0 {
+            close_rebalance(&e, &owner, job);
+            return Ok(0);
+        }
+        set_rebalance_job(&e, &owner, &job);
+
+        RebalanceStarted { owner, caller, legs }.publish(&e);
+        Ok(legs)
+    }
+
+    /// Execute up to `max_legs` pending legs of a two-phase rebalance
+    ///
+    /// Each swap is priced with the oracle when it executes and bounded by the owner's
+    /// `max_slippage_bps`. The call that executes the last leg credits the remaining base asset
+    /// to the owner and emits `Rebalanced`; earlier calls emit `RebalanceProgressed`.
+    ///
+    /// ## Parameters:
+    /// - `caller`: The owner or the owner's keeper (must sign the transaction)
+    /// - `owner`: Portfolio being rebalanced
+    /// - `max_legs`: Largest number of legs to execute in this call
+    /// - `deadline_delta`: Optional deadline of each swap, in ledgers from now
+    ///
+    /// ## Returns:
+    /// Number of legs still pending
+    pub fn execute_rebalance(
+        e: Env,
+        caller: Address,
+        owner: Address,
+        max_legs: u32,
+        deadline_delta: Option<u32>,
+    ) -> Result<u32, PortfolioError> {
+        caller.require_auth();
+        extend_instance_ttl(&e);
+        if max_legs == 0 {
+            return Err(PortfolioError::InvalidArgument);
+        }
+
+        let portfolio = get_portfolio(&e, &owner).ok_or(PortfolioError::PortfolioNotFound)?;
+        check_operator(&portfolio, &caller, &owner)?;
+        let mut job = get_rebalance_job(&e, &owner).ok_or(PortfolioError::NoRebalanceInProgress)?;
+
+        let mut executed: u32 = 0;
+        while executed < max_legs && job.next_leg < job.legs.len() {
+            execute_leg(&e, &owner, &mut job, portfolio.max_slippage_bps, deadline_delta)?;
+            executed += 1;
+        }
+
+        let remaining = job.legs.len() - job.next_leg;
+        if remaining == 0 {
+            remove_rebalance_job(&e, &owner);
+            close_rebalance(&e, &owner, job);
+        } else {
+            set_rebalance_job(&e, &owner, &job);
+            RebalanceProgressed { owner, caller, executed, remaining }.publish(&e);
+        }
+        Ok(remaining)
+    }
+
+    /// Cancel the owner's pending two-phase rebalance
+    ///
+    /// Swaps already executed stand. The escrowed input of every pending sell and the job's base
+    /// asset are credited back to the owner's balances, where they can be withdrawn.
+    pub fn cancel_rebalance(e: Env, owner: Address) -> Result<(), PortfolioError> {
+        owner.require_auth();
+        extend_instance_ttl(&e);
+
+        let job = get_rebalance_job(&e, &owner).ok_or(PortfolioError::NoRebalanceInProgress)?;
+        remove_rebalance_job(&e, &owner);
+
+        let mut refunded: u32 = 0;
+        for index in job.next_leg..job.legs.len() {
+            let leg = job.legs.get_unchecked(index);
+            if leg.token_in != job.base {
+                set_balance(&e, &owner, &leg.token_in, get_balance(&e, &owner, &leg.token_in) + leg.amount);
+            }
+            refunded += 1;
+        }
+        set_balance(&e, &owner, &job.base, get_balance(&e, &owner, &job.base) + job.base_escrow);
+
+        RebalanceCancelled {
+            owner,
+            executed: job.next_leg,
+            refunded,
+        }
+        .publish(&e);
+        Ok(())
+    }
+
+    /// Pending two-phase rebalance of an owner, with its progress
+    pub fn rebalance_job(e: Env, owner: Address) -> Option<RebalanceJob> {
+        get_rebalance_job(&e, &owner)
+    }
+
+    /// Address of the aggregator executing the swaps
+    pub fn aggregator(e: Env) -> Address {
+        get_aggregator_address(&e)
+    }
+
+    /// Address of the price oracle valuing the holdings
+    pub fn oracle(e: Env) -> Address {
+        get_oracle(&e)
+    }
+}
@@ -0,0 +1,180 @@
+use common::math::Bps;
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+#[derive(Clone)]
+#[contracttype]
+
+enum DataKey {
+    Admin,
+    AggregatorAddress,
+    Oracle,
+    Portfolio(Address),
+    Balance(Address, Address),
+    RebalanceJob(Address),
+}
+
+/// Target allocation and rebalancing bounds chosen by a portfolio owner
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Portfolio {
+    /// Held assets; the first one is the base asset used for valuation and routing
+    pub assets: Vec<Address>,
+    /// Target weight of each asset, in basis points (sums to 10_000)
+    pub weights: Vec<Bps>,
+    /// Smallest drift of any asset, in basis points, that justifies a rebalance
+    pub drift_threshold_bps: Bps,
+    /// Largest accepted shortfall of each swap against its oracle value, in basis points
+    pub max_slippage_bps: Bps,
+    /// Account allowed to trigger `rebalance` besides the owner
+    pub keeper: Option<Address>,
+}
+
+/// One swap of a rebalance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RebalanceLeg {
+    pub token_in: Address,
+    pub token_out: Address,
+    /// Amount of `token_in` to sell; buys spend at most this much of the base asset
+    pub amount: i128,
+}
+
+/// Rebalance opened by `start_rebalance` and settled by keeper calls
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RebalanceJob {
+    /// Account that opened the rebalance
+    pub caller: Address,
+    /// Base asset of the portfolio when the rebalance was opened
+    pub base: Address,
+    /// Sells into the base asset first, then buys with it
+    pub legs: Vec<RebalanceLeg>,
+    /// Index of the next leg to execute
+    pub next_leg: u32,
+    /// Base asset held by the job: the owner's base balance plus the proceeds of the sells
+    pub base_escrow: i128,
+    /// Swaps actually executed so far (buys left without base asset are skipped)
+    pub swaps: u32,
+    /// Portfolio value when the rebalance was opened, in base asset units
+    pub total_value: i128,
+    /// Largest drift from target when the rebalance was opened, in basis points
+    pub max_drift_bps: u32,
+}
+
+#[allow(dead_code)]
+const DAY_IN_LEDGERS: u32 = 17280;
+#[allow(dead_code)]
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+#[allow(dead_code)]
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+const PERSISTENT_BUMP_AMOUNT: u32 = 60 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+pub fn extend_instance_ttl(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Admin Address
+pub fn set_admin(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Admin, &address);
+}
+
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap()
+}
+
+// Aggregator Address (executes the rebalancing swaps)
+pub fn set_aggregator_address(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::AggregatorAddress, &address);
+}
+
+pub fn get_aggregator_address(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::AggregatorAddress)
+        .unwrap()
+}
+
+// Price oracle (prices the holdings and bounds the swaps)
+pub fn set_oracle(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Oracle, &address);
+}
+
+pub fn get_oracle(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Oracle)
+        .unwrap()
+}
+
+// Portfolio definition of each owner
+pub fn set_portfolio(e: &Env, owner: &Address, portfolio: &Portfolio) {
+    let key = DataKey::Portfolio(owner.clone());
+    e.storage().persistent().set(&key, portfolio);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_portfolio(e: &Env, owner: &Address) -> Option<Portfolio> {
+    let key = DataKey::Portfolio(owner.clone());
+    let portfolio = e.storage().persistent().get(&key);
+    if portfolio.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    portfolio
+}
+
+// Amount of each asset held in custody for an owner
+pub fn set_balance(e: &Env, owner: &Address, asset: &Address, amount: i128) {
+    let key = DataKey::Balance(owner.clone(), asset.clone());
+    e.storage().persistent().set(&key, &amount);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_balance(e: &Env, owner: &Address, asset: &Address) -> i128 {
+    let key = DataKey::Balance(owner.clone(), asset.clone());
+    let amount = e.storage().persistent().get(&key);
+    if amount.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    amount.unwrap_or(0)
+}
+
+// Pending two-phase rebalance of each owner
+pub fn set_rebalance_job(e: &Env, owner: &Address, job: &RebalanceJob) {
+    let key = DataKey::RebalanceJob(owner.clone());
+    e.storage().persistent().set(&key, job);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_rebalance_job(e: &Env, owner: &Address) -> Option<RebalanceJob> {
+    let key = DataKey::RebalanceJob(owner.clone());
+    let job = e.storage().persistent().get(&key);
+    if job.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    job
+}
+
+pub fn remove_rebalance_job(e: &Env, owner: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::RebalanceJob(owner.clone()));
+}
@@ -0,0 +1,11 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RiskRegistryError {
+    /// Invalid parameters
+    InvalidArgument = 1,
+    /// Score above `MAX_SCORE`
+    ScoreOutOfRange = 2,
+}
@@ -0,0 +1,18 @@
+use soroban_sdk::{contractevent, Address};
+
+/// Emitted when the curator scores (or re-scores) a vault
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScoreUpdated {
+    #[topic]
+    pub vault: Address,
+    pub score: u32,
+}
+
+/// Emitted when the curator removes a vault's score
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScoreRemoved {
+    #[topic]
+    pub vault: Address,
+}
@@ -0,0 +1,98 @@
+#![no_std]
+//! # Risk Registry - Curated Vault Risk Scores
+//!
+//! A curator appointed by the admin assigns each vault a risk score from 0 (lowest risk) to
+//! `MAX_SCORE`. Integrations read the scores to inform users; the zap also lets every user set
+//! a maximum risk and refuses deposits into vaults scored above it.
+//!
+//! Vaults without a score are unknown, not safe: consumers should treat them as exceeding any
+//! tolerance.
+
+use soroban_sdk::{Address, Env, Vec, contract, contractimpl};
+
+mod storage;
+mod error;
+mod events;
+
+use storage::{
+    extend_instance_ttl, get_admin, get_curator, get_score, remove_score, set_admin, set_curator,
+    set_score, RiskScore,
+};
+use error::RiskRegistryError;
+use events::{ScoreRemoved, ScoreUpdated};
+
+/// Highest (riskiest) score
+const MAX_SCORE: u32 = 100;
+
+#[contract]
+struct RiskRegistry;
+
+#[contractimpl]
+impl RiskRegistry {
+    /// Initialize the registry
+    ///
+    /// ## Parameters:
+    /// - `admin`: Account appointing the curator
+    /// - `curator`: Account maintaining the scores
+    pub fn __constructor(e: Env, admin: Address, curator: Address) {
+        set_admin(&e, admin);
+        set_curator(&e, curator);
+    }
+
+    /// Replace the curator (admin only)
+    pub fn set_curator(e: Env, curator: Address) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_curator(&e, curator);
+    }
+
+    /// Score several vaults at once (curator only)
+    ///
+    /// ## Parameters:
+    /// - `scores`: `(vault, score)` pairs; a vault listed twice keeps its last score
+    pub fn set_scores(e: Env, scores: Vec<(Address, u32)>) -> Result<(), RiskRegistryError> {
+        get_curator(&e).require_auth();
+        extend_instance_ttl(&e);
+        if scores.is_empty() {
+            return Err(RiskRegistryError::InvalidArgument);
+        }
+
+        let ledger = e.ledger().sequence();
+        for (vault, score) in scores.iter() {
+            if score > MAX_SCORE {
+                return Err(RiskRegistryError::ScoreOutOfRange);
+            }
+            set_score(&e, &vault, &RiskScore { score, updated_ledger: ledger });
+            ScoreUpdated { vault, score }.publish(&e);
+        }
+        Ok(())
+    }
+
+    /// Remove a vault's score, making it unknown again (curator only)
+    pub fn remove_score(e: Env, vault: Address) {
+        get_curator(&e).require_auth();
+        extend_instance_ttl(&e);
+        remove_score(&e, &vault);
+        ScoreRemoved { vault }.publish(&e);
+    }
+
+    /// Risk assessment of a vault, if it has been scored
+    pub fn risk(e: Env, vault: Address) -> Option<RiskScore> {
+        get_score(&e, &vault)
+    }
+
+    /// Risk score of a vault, if it has been scored
+    pub fn score(e: Env, vault: Address) -> Option<u32> {
+        get_score(&e, &vault).map(|risk| risk.score)
+    }
+
+    /// Highest (riskiest) score
+    pub fn max_score(_e: Env) -> u32 {
+        MAX_SCORE
+    }
+
+    /// Account maintaining the scores
+    pub fn curator(e: Env) -> Address {
+        get_curator(&e)
+    }
+}
@@ -0,0 +1,92 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+#[derive(Clone)]
+#[contracttype]
+
+enum DataKey {
+    Admin,
+    Curator,
+    Score(Address),
+}
+
+/// Risk assessment of a vault
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RiskScore {
+    /// 0 (lowest risk) to `MAX_SCORE`
+    pub score: u32,
+    /// Ledger sequence of the last assessment
+    pub updated_ledger: u32,
+}
+
+#[allow(dead_code)]
+const DAY_IN_LEDGERS: u32 = 17280;
+#[allow(dead_code)]
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+#[allow(dead_code)]
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+const PERSISTENT_BUMP_AMOUNT: u32 = 60 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+pub fn extend_instance_ttl(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Admin Address (appoints the curator)
+pub fn set_admin(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Admin, &address);
+}
+
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap()
+}
+
+// Curator Address (maintains the scores)
+pub fn set_curator(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Curator, &address);
+}
+
+pub fn get_curator(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Curator)
+        .unwrap()
+}
+
+// Risk score per vault
+pub fn set_score(e: &Env, vault: &Address, score: &RiskScore) {
+    let key = DataKey::Score(vault.clone());
+    e.storage().persistent().set(&key, score);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_score(e: &Env, vault: &Address) -> Option<RiskScore> {
+    let key = DataKey::Score(vault.clone());
+    let score = e.storage().persistent().get(&key);
+    if score.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    score
+}
+
+pub fn remove_score(e: &Env, vault: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::Score(vault.clone()));
+}
@@ -0,0 +1,19 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ScoreboardError {
+    /// Invalid parameters
+    InvalidArgument = 1,
+    /// The participant is already registered
+    AlreadyRegistered = 2,
+    /// The participant is not registered
+    NotRegistered = 3,
+    /// The competition has ended
+    CompetitionClosed = 4,
+    /// A starting portfolio asset has no oracle price
+    PriceUnavailable = 5,
+    /// The starting portfolio is worth nothing
+    EmptyPortfolio = 6,
+}
@@ -0,0 +1,20 @@
+use soroban_sdk::{contractevent, Address};
+
+/// Emitted when a participant joins with their starting portfolio value
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParticipantRegistered {
+    #[topic]
+    pub participant: Address,
+    pub start_value: i128,
+}
+
+/// Emitted when new trades of a participant are counted
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParticipantSynced {
+    #[topic]
+    pub participant: Address,
+    pub trades: u32,
+    pub pnl: i128,
+}
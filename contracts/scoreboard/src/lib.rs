@@ -0,0 +1,298 @@
+#![no_std]
+//! # Scoreboard - On-Chain Trading Competition
+//!
+//! Turns the workshop into a scored competition:
+//! 1. `register`: a participant joins; the portfolio they hold in the competition assets is
+//!    valued with the oracle and snapshotted as their starting value
+//! 2. Participants trade through the aggregator and the zap, which store a receipt per trade
+//!    (receipts must be enabled on both)
+//! 3. `sync`: anyone counts a participant's new receipts; each trade adds the oracle value of
+//!    its output minus the value of its input, at the time of the trade, to the realized PnL
+//! 4. `leaderboard`: participants ranked by return on their starting value
+//!
+//! ## Valuation:
+//! Amounts are valued as `amount * price / 10^oracle decimals`, so all competition assets are
+//! assumed to use the same number of decimals (7 for Stellar assets). Trades involving a token
+//! the oracle does not price are skipped, as are trades made before registration or after
+//! `end_ledger`.
+//!
+//! Trades are valued at the oracle price of the ledger they were made in, so when a
+//! participant is synced does not change their PnL. Receipts carry a ledger sequence, which is
+//! converted to the timestamp the oracle is asked for with `SECONDS_PER_LEDGER`. Oracles only
+//! keep a limited price history: a trade synced after its price left that history is valued at
+//! the last price instead (marked to market).
+
+use common::deadline::SECONDS_PER_LEDGER;
+use soroban_sdk::{
+    Address, Env, Vec, contract, contractimpl, panic_with_error, token
+};
+
+mod oracle;
+mod receipt_source;
+mod storage;
+mod error;
+mod events;
+mod types;
+
+use oracle::{Asset, PriceOracleClient};
+use receipt_source::ReceiptSourceClient;
+use storage::{
+    extend_instance_ttl, get_admin, get_config, get_participant, get_ranking, get_synced,
+    set_admin, set_config, set_participant, set_ranking, set_synced, Config, Participant,
+};
+use error::ScoreboardError;
+use events::{ParticipantRegistered, ParticipantSynced};
+use types::Standing;
+
+/// Basis points in 100%
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Largest page returned by `leaderboard`
+const MAX_PAGE_SIZE: u32 = 50;
+
+/// Receipts processed per source by a single `sync`
+const MAX_SYNC_BATCH: u32 = 20;
+
+/// Oracle value of `amount` of `token`, `None` when the oracle does not price it
+fn value_of(oracle: &PriceOracleClient, token: &Address, amount: i128) -> Option<i128> {
+    let price = oracle.lastprice(&Asset::Stellar(token.clone()))?.price;
+    scale_value(oracle, amount, price)
+}
+
+/// Oracle value of `amount` of `token` as of `ledger`, or at the last price once the oracle's
+/// history no longer reaches back to it
+fn value_at(e: &Env, oracle: &PriceOracleClient, token: &Address, amount: i128, ledger: u32) -> Option<i128> {
+    let elapsed = e.ledger().sequence().saturating_sub(ledger) as u64 * SECONDS_PER_LEDGER;
+    let timestamp = e.ledger().timestamp().saturating_sub(elapsed);
+    let asset = Asset::Stellar(token.clone());
+    let price = oracle.price(&asset, &timestamp).or_else(|| oracle.lastprice(&asset))?.price;
+    scale_value(oracle, amount, price)
+}
+
+/// `amount * price`, in oracle quote units
+fn scale_value(oracle: &PriceOracleClient, amount: i128, price: i128) -> Option<i128> {
+    let scale = 10_i128.checked_pow(oracle.decimals())?;
+    amount.checked_mul(price)?.checked_div(scale)
+}
+
+/// Return of a participant on their starting value, in basis points
+fn return_bps(participant: &Participant) -> i128 {
+    participant.pnl * BPS_DENOMINATOR / participant.start_value
+}
+
+/// Moves `address` to its place in the ranking for its current return
+fn update_ranking(e: &Env, address: &Address, participant: &Participant) {
+    let mut ranking = get_ranking(e);
+    if let Some(index) = ranking.iter().position(|(_, ranked)| ranked == *address) {
+        ranking.remove(index as u32);
+    }
+
+    let score = return_bps(participant);
+    let index = ranking
+        .iter()
+        .position(|(ranked_score, _)| ranked_score < score)
+        .map(|index| index as u32)
+        .unwrap_or(ranking.len());
+    ranking.insert(index, (score, address.clone()));
+    set_ranking(e, &ranking);
+}
+
+#[contract]
+struct Scoreboard;
+
+#[contractimpl]
+impl Scoreboard {
+    /// Initialize the competition
+    ///
+    /// ## Parameters:
+    /// - `admin`: Account allowed to move the end of the competition
+    /// - `oracle`: SEP-40 oracle pricing every asset
+    /// - `sources`: Contracts whose receipts count as trades (the aggregator and the zap)
+    /// - `assets`: Assets making up the starting portfolio snapshot
+    /// - `end_ledger`: Last ledger at which registrations and trades count
+    pub fn __constructor(
+        e: Env,
+        admin: Address,
+        oracle: Address,
+        sources: Vec<Address>,
+        assets: Vec<Address>,
+        end_ledger: u32,
+    ) {
+        if sources.is_empty() || assets.is_empty() || end_ledger <= e.ledger().sequence() {
+            panic_with_error!(&e, ScoreboardError::InvalidArgument);
+        }
+        set_admin(&e, admin);
+        set_config(
+            &e,
+            &Config {
+                oracle,
+                sources,
+                assets,
+                end_ledger,
+            },
+        );
+    }
+
+    /// Move the end of the competition (admin only)
+    pub fn set_end_ledger(e: Env, end_ledger: u32) -> Result<(), ScoreboardError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        if end_ledger < e.ledger().sequence() {
+            return Err(ScoreboardError::InvalidArgument);
+        }
+        let mut config = get_config(&e);
+        config.end_ledger = end_ledger;
+        set_config(&e, &config);
+        Ok(())
+    }
+
+    /// Join the competition, snapshotting the current portfolio value
+    ///
+    /// ## Returns:
+    /// Starting portfolio value, in oracle quote units
+    pub fn register(e: Env, participant: Address) -> Result<i128, ScoreboardError> {
+        participant.require_auth();
+        extend_instance_ttl(&e);
+
+        let config = get_config(&e);
+        let ledger = e.ledger().sequence();
+        if ledger > config.end_ledger {
+            return Err(ScoreboardError::CompetitionClosed);
+        }
+        if get_participant(&e, &participant).is_some() {
+            return Err(ScoreboardError::AlreadyRegistered);
+        }
+
+        let oracle = PriceOracleClient::new(&e, &config.oracle);
+        let mut start_value: i128 = 0;
+        for asset in config.assets.iter() {
+            let balance = token::Client::new(&e, &asset).balance(&participant);
+            if balance > 0 {
+                start_value += value_of(&oracle, &asset, balance)
+                    .ok_or(ScoreboardError::PriceUnavailable)?;
+            }
+        }
+        if start_value <= 0 {
+            return Err(ScoreboardError::EmptyPortfolio);
+        }
+
+        // Receipts from before the registration never count
+        for source in config.sources.iter() {
+            let count = ReceiptSourceClient::new(&e, &source).user_receipt_count(&participant);
+            set_synced(&e, &participant, &source, count);
+        }
+
+        let standing = Participant {
+            start_value,
+            pnl: 0,
+            trades: 0,
+            registered_ledger: ledger,
+        };
+        set_participant(&e, &participant, &standing);
+        update_ranking(&e, &participant, &standing);
+
+        ParticipantRegistered {
+            participant,
+            start_value,
+        }
+        .publish(&e);
+        Ok(start_value)
+    }
+
+    /// Count a participant's new trades (anyone can call)
+    ///
+    /// Processes up to 20 new receipts per source; call again while trades remain.
+    ///
+    /// ## Returns:
+    /// The participant's updated standing
+    pub fn sync(e: Env, participant: Address) -> Result<Standing, ScoreboardError> {
+        extend_instance_ttl(&e);
+        let config = get_config(&e);
+        let mut standing =
+            get_participant(&e, &participant).ok_or(ScoreboardError::NotRegistered)?;
+        let oracle = PriceOracleClient::new(&e, &config.oracle);
+
+        let mut new_trades: u32 = 0;
+        for source in config.sources.iter() {
+            let source_client = ReceiptSourceClient::new(&e, &source);
+            let synced = get_synced(&e, &participant, &source);
+            let ids = source_client.user_receipts(&participant, &synced, &MAX_SYNC_BATCH);
+
+            for id in ids.iter() {
+                let Some(receipt) = source_client.get_receipt(&id) else {
+                    continue;
+                };
+                if receipt.ledger < standing.registered_ledger || receipt.ledger > config.end_ledger {
+                    continue;
+                }
+                let value_in = value_at(&e, &oracle, &receipt.token_in, receipt.amount_in, receipt.ledger);
+                let value_out = value_at(&e, &oracle, &receipt.token_out, receipt.amount_out, receipt.ledger);
+                if let (Some(value_in), Some(value_out)) = (value_in, value_out) {
+                    standing.pnl += value_out - value_in;
+                    standing.trades += 1;
+                    new_trades += 1;
+                }
+            }
+            set_synced(&e, &participant, &source, synced + ids.len());
+        }
+
+        set_participant(&e, &participant, &standing);
+        update_ranking(&e, &participant, &standing);
+
+        ParticipantSynced {
+            participant: participant.clone(),
+            trades: new_trades,
+            pnl: standing.pnl,
+        }
+        .publish(&e);
+
+        Ok(Standing {
+            return_bps: return_bps(&standing),
+            participant,
+            start_value: standing.start_value,
+            pnl: standing.pnl,
+            trades: standing.trades,
+        })
+    }
+
+    /// Page through the participants, best return first
+    ///
+    /// Rankings reflect each participant's last `sync`.
+    ///
+    /// ## Parameters:
+    /// - `start`: Rank (from 0) of the first participant to return
+    /// - `limit`: Number of participants to return, capped at 50
+    pub fn leaderboard(e: Env, start: u32, limit: u32) -> Vec<Standing> {
+        let ranking = get_ranking(&e);
+        let end = ranking.len().min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+        let mut page = Vec::new(&e);
+        for index in start..end {
+            let (return_bps, participant) = ranking.get_unchecked(index);
+            if let Some(standing) = get_participant(&e, &participant) {
+                page.push_back(Standing {
+                    participant,
+                    start_value: standing.start_value,
+                    pnl: standing.pnl,
+                    return_bps,
+                    trades: standing.trades,
+                });
+            }
+        }
+        page
+    }
+
+    /// Standing of a participant, if registered
+    pub fn participant(e: Env, participant: Address) -> Option<Participant> {
+        get_participant(&e, &participant)
+    }
+
+    /// Number of registered participants
+    pub fn participant_count(e: Env) -> u32 {
+        get_ranking(&e).len()
+    }
+
+    /// Competition parameters
+    pub fn config(e: Env) -> Config {
+        get_config(&e)
+    }
+}
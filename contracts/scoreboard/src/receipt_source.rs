@@ -0,0 +1,24 @@
+use soroban_sdk::{contractclient, contracttype, Address, Env, Vec};
+
+// Receipt views shared by the `soroswap-auth` aggregator and the `defindex-zap`
+
+/// Receipt of an executed operation, as stored by the source contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Receipt {
+    pub id: u64,
+    pub caller: Address,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+    pub ledger: u32,
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "ReceiptSourceClient")]
+pub trait ReceiptSource {
+    fn get_receipt(e: Env, id: u64) -> Option<Receipt>;
+    fn user_receipts(e: Env, user: Address, start: u32, limit: u32) -> Vec<u64>;
+    fn user_receipt_count(e: Env, user: Address) -> u32;
+}
@@ -0,0 +1,137 @@
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+#[derive(Clone)]
+#[contracttype]
+
+enum DataKey {
+    Admin,
+    Config,
+    Participant(Address),
+    Synced(Address, Address),
+    Ranking,
+}
+
+/// Competition parameters, fixed at deployment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// SEP-40 oracle pricing every asset
+    pub oracle: Address,
+    /// Contracts whose receipts count as trades (the aggregator and the zap)
+    pub sources: Vec<Address>,
+    /// Assets making up the starting portfolio snapshot
+    pub assets: Vec<Address>,
+    /// Last ledger at which registrations and trades count
+    pub end_ledger: u32,
+}
+
+/// A participant's standing in the competition
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Participant {
+    /// Portfolio value at registration, in oracle quote units
+    pub start_value: i128,
+    /// Realized profit or loss of the synced trades, in oracle quote units
+    pub pnl: i128,
+    /// Trades counted so far
+    pub trades: u32,
+    pub registered_ledger: u32,
+}
+
+#[allow(dead_code)]
+const DAY_IN_LEDGERS: u32 = 17280;
+#[allow(dead_code)]
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+#[allow(dead_code)]
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+const PERSISTENT_BUMP_AMOUNT: u32 = 60 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+pub fn extend_instance_ttl(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Admin Address
+pub fn set_admin(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Admin, &address);
+}
+
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap()
+}
+
+// Competition parameters
+pub fn set_config(e: &Env, config: &Config) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Config, config);
+}
+
+pub fn get_config(e: &Env) -> Config {
+    e.storage()
+        .instance()
+        .get(&DataKey::Config)
+        .unwrap()
+}
+
+// Standing per participant
+pub fn set_participant(e: &Env, address: &Address, participant: &Participant) {
+    let key = DataKey::Participant(address.clone());
+    e.storage().persistent().set(&key, participant);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_participant(e: &Env, address: &Address) -> Option<Participant> {
+    let key = DataKey::Participant(address.clone());
+    let participant = e.storage().persistent().get(&key);
+    if participant.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    participant
+}
+
+// Receipts of a participant already processed per source (participant, source)
+pub fn set_synced(e: &Env, participant: &Address, source: &Address, count: u32) {
+    let key = DataKey::Synced(participant.clone(), source.clone());
+    e.storage().persistent().set(&key, &count);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_synced(e: &Env, participant: &Address, source: &Address) -> u32 {
+    let key = DataKey::Synced(participant.clone(), source.clone());
+    let count = e.storage().persistent().get(&key);
+    if count.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    count.unwrap_or(0)
+}
+
+// Participants ordered by return, best first: (return in bps, participant)
+pub fn set_ranking(e: &Env, ranking: &Vec<(i128, Address)>) {
+    let key = DataKey::Ranking;
+    e.storage().persistent().set(&key, ranking);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_ranking(e: &Env) -> Vec<(i128, Address)> {
+    let key = DataKey::Ranking;
+    let ranking = e.storage().persistent().get(&key);
+    if ranking.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    ranking.unwrap_or(Vec::new(e))
+}
@@ -0,0 +1,13 @@
+use soroban_sdk::{contracttype, Address};
+
+/// A participant's row in the leaderboard
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Standing {
+    pub participant: Address,
+    pub start_value: i128,
+    pub pnl: i128,
+    /// `pnl` relative to `start_value`, in basis points
+    pub return_bps: i128,
+    pub trades: u32,
+}
@@ -0,0 +1,122 @@
+//! Uniform interface over the exchanges a swap can execute on
+//!
+//! Each `Venue` names an exchange together with the contract its pools are found through, and
+//! dispatches `quote`, `swap_exact_in` and their exact-output counterparts to that exchange's
+//! adapter module. The swap logic
+//! only deals with venues, so supporting another exchange means adding a variant and its arms
+//! here.
+
+use common::pay::SwapResult;
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::{aqua_adapter, phoenix_adapter, soroswap_adapter};
+
+/// Exchange a swap executes on, with the contract its pools are found through
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Venue {
+    /// Soroswap router
+    Soroswap(Address),
+    /// Soroswap pairs, swapped with directly; the router only locates them
+    SoroswapDirect(Address),
+    /// Phoenix factory
+    Phoenix(Address),
+    /// Aquarius router
+    Aqua(Address),
+}
+
+impl Venue {
+    /// Amounts of each token of `path` when swapping `amount` of the first, `None` when the
+    /// venue cannot serve the path
+    ///
+    /// Direct Soroswap pairs, Phoenix and Aquarius serve direct pairs only.
+    pub fn quote(&self, e: &Env, path: &Vec<Address>, amount: i128) -> Option<Vec<i128>> {
+        match self {
+            Venue::Soroswap(router) => soroswap_adapter::quote(e, router, path, amount),
+            Venue::SoroswapDirect(router) => {
+                let (token_in, token_out) = direct_pair(path)?;
+                soroswap_adapter::quote_direct(e, router, &token_in, &token_out, amount)
+            }
+            Venue::Phoenix(factory) => {
+                let (token_in, token_out) = direct_pair(path)?;
+                phoenix_adapter::quote(e, factory, &token_in, &token_out, amount)
+            }
+            Venue::Aqua(router) => {
+                let (token_in, token_out) = direct_pair(path)?;
+                aqua_adapter::quote(e, router, &token_in, &token_out, amount)
+            }
+        }
+    }
+
+    /// Swaps `amount` of the first token of `path`, held by this contract, along the path for
+    /// at least `min_out` of the last
+    ///
+    /// Returns what the swap did, including the router or pool that served it, with the output
+    /// back in this contract's custody. `None` when the venue cannot execute the swap, or not
+    /// for `min_out`; nothing has moved then. Swaps that fail after the input left this
+    /// contract abort the invocation instead.
+    pub fn swap_exact_in(
+        &self,
+        e: &Env,
+        path: &Vec<Address>,
+        amount: i128,
+        min_out: i128,
+        deadline: u64,
+    ) -> Option<SwapResult> {
+        match self {
+            Venue::Soroswap(router) => soroswap_adapter::try_swap(e, router, path, amount, min_out, deadline),
+            Venue::SoroswapDirect(router) => {
+                let (token_in, token_out) = direct_pair(path)?;
+                soroswap_adapter::try_swap_direct(e, router, &token_in, &token_out, amount, min_out)
+            }
+            Venue::Phoenix(factory) => {
+                let (token_in, token_out) = direct_pair(path)?;
+                phoenix_adapter::try_swap(e, factory, &token_in, &token_out, amount, min_out, deadline)
+            }
+            Venue::Aqua(router) => {
+                let (token_in, token_out) = direct_pair(path)?;
+                aqua_adapter::try_swap(e, router, &token_in, &token_out, amount, min_out)
+            }
+        }
+    }
+
+    /// Input of the first token of `path` needed to receive `amount_out` of the last, `None`
+    /// when the venue cannot serve exact-output swaps of the path
+    ///
+    /// Only Soroswap routers serve exact-output swaps.
+    pub fn quote_in(&self, e: &Env, path: &Vec<Address>, amount_out: i128) -> Option<i128> {
+        match self {
+            Venue::Soroswap(router) => soroswap_adapter::quote_in(e, router, path, amount_out),
+            _ => None,
+        }
+    }
+
+    /// Swaps at most `amount_in` of the first token of `path`, held by this contract, for
+    /// exactly `amount_out` of the last
+    ///
+    /// `amount_in` is the venue's own `quote_in`. `None` when the venue cannot execute the swap;
+    /// nothing has moved then.
+    pub fn swap_exact_out(
+        &self,
+        e: &Env,
+        path: &Vec<Address>,
+        amount_out: i128,
+        amount_in: i128,
+        deadline: u64,
+    ) -> Option<SwapResult> {
+        match self {
+            Venue::Soroswap(router) => {
+                soroswap_adapter::try_swap_exact_out(e, router, path, amount_out, amount_in, deadline)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Tokens of a direct-pair path, `None` for multi-hop paths
+fn direct_pair(path: &Vec<Address>) -> Option<(Address, Address)> {
+    if path.len() != 2 {
+        return None;
+    }
+    Some((path.first_unchecked(), path.last_unchecked()))
+}
@@ -0,0 +1,127 @@
+use common::{auth::AuthBuilder, math::BPS_DENOMINATOR, pay::SwapResult};
+use soroban_sdk::{contractclient, token, vec, Address, BytesN, Env, Map, Symbol, Vec};
+
+// Interface of the Aquarius router, which indexes the pools of each token set
+#[allow(dead_code)]
+#[contractclient(name = "AquaRouterClient")]
+pub trait AquaRouter {
+    /// Pools of `tokens` (sorted), keyed by pool hash
+    fn get_pools(e: Env, tokens: Vec<Address>) -> Map<BytesN<32>, Address>;
+}
+
+// Interface of an Aquarius pool; amounts are unsigned and tokens are addressed by index
+#[allow(dead_code)]
+#[contractclient(name = "AquaPoolClient")]
+pub trait AquaPool {
+    fn pool_type(e: Env) -> Symbol;
+    fn get_tokens(e: Env) -> Vec<Address>;
+    /// Fee taken from the output, in basis points
+    fn get_fee_fraction(e: Env) -> u32;
+    fn estimate_swap(e: Env, in_idx: u32, out_idx: u32, in_amount: u128) -> u128;
+    fn swap(e: Env, user: Address, in_idx: u32, out_idx: u32, in_amount: u128, out_min: u128) -> u128;
+}
+
+/// `pool_type` of the constant-product pools this adapter trades on
+const CONSTANT_PRODUCT: &str = "constant_product";
+
+/// Constant-product pool of the pair with the best quote for `amount` of `token_in`
+///
+/// Returns the pool, the indexes of `token_in` and `token_out` in it and the quoted output.
+/// Stable-swap pools of the pair are skipped.
+pub fn best_pool(
+    e: &Env,
+    router: &Address,
+    token_in: &Address,
+    token_out: &Address,
+    amount: u128,
+) -> Option<(Address, u32, u32, u128)> {
+    let tokens = if token_in < token_out {
+        vec![e, token_in.clone(), token_out.clone()]
+    } else {
+        vec![e, token_out.clone(), token_in.clone()]
+    };
+    let pools = match AquaRouterClient::new(e, router).try_get_pools(&tokens) {
+        Ok(Ok(pools)) => pools,
+        _ => return None,
+    };
+
+    let mut best: Option<(Address, u32, u32, u128)> = None;
+    for pool in pools.values().iter() {
+        let pool_client = AquaPoolClient::new(e, &pool);
+        match pool_client.try_pool_type() {
+            Ok(Ok(pool_type)) if pool_type == Symbol::new(e, CONSTANT_PRODUCT) => {}
+            _ => continue,
+        }
+        let Ok(Ok(pool_tokens)) = pool_client.try_get_tokens() else {
+            continue;
+        };
+        let in_idx = pool_tokens.first_index_of(token_in);
+        let out_idx = pool_tokens.first_index_of(token_out);
+        let (Some(in_idx), Some(out_idx)) = (in_idx, out_idx) else {
+            continue;
+        };
+        if let Ok(Ok(quoted)) = pool_client.try_estimate_swap(&in_idx, &out_idx, &amount) {
+            if best.as_ref().is_none_or(|(_, _, _, best_quote)| quoted > *best_quote) {
+                best = Some((pool, in_idx, out_idx, quoted));
+            }
+        }
+    }
+    best
+}
+
+/// Amounts of `token_in` and `token_out` when swapping `amount` over the best constant-product
+/// Aquarius pool of the pair, `None` when no pool quotes the swap
+pub fn quote(e: &Env, router: &Address, token_in: &Address, token_out: &Address, amount: i128) -> Option<Vec<i128>> {
+    let (_, _, _, quoted) = best_pool(e, router, token_in, token_out, u128::try_from(amount).ok()?)?;
+    Some(vec![e, amount, i128::try_from(quoted).ok()?])
+}
+
+/// Attempts a swap of `amount` of `token_in`, held by this contract, over the best
+/// constant-product Aquarius pool of the pair for at least `min_out`, returning what it did on
+/// success
+///
+/// The pool requires the authorization of `user` (this contract, the direct invoker) and then
+/// pulls the input with a nested `token_in.transfer(this contract, pool, amount)`, which is
+/// authorized here like the Soroswap Router's transfer into a pair. The output is delivered back
+/// to this contract and measured as a balance delta.
+pub fn try_swap(
+    e: &Env,
+    router: &Address,
+    token_in: &Address,
+    token_out: &Address,
+    amount: i128,
+    min_out: i128,
+) -> Option<SwapResult> {
+    let in_amount = u128::try_from(amount).ok()?;
+    let out_min = u128::try_from(min_out).ok()?;
+    let (pool, in_idx, out_idx, _) = best_pool(e, router, token_in, token_out, in_amount)?;
+    let pool_client = AquaPoolClient::new(e, &pool);
+    let fee_fraction = match pool_client.try_get_fee_fraction() {
+        Ok(Ok(fee_fraction)) if fee_fraction < BPS_DENOMINATOR => fee_fraction as i128,
+        _ => return None,
+    };
+
+    let this = e.current_contract_address();
+    let token_out_client = token::Client::new(e, token_out);
+    let balance_before = token_out_client.balance(&this);
+
+    // Covers the next contract call only, so the pool call must follow directly
+    AuthBuilder::new(e).transfer(token_in, &this, &pool, amount).authorize();
+    match pool_client.try_swap(&this, &in_idx, &out_idx, &in_amount, &out_min) {
+        Ok(Ok(_)) => {
+            let amount_out = token_out_client.balance(&this) - balance_before;
+            // The fee was taken from the gross output, of which `amount_out` is what remained
+            let fee = amount_out * fee_fraction / (BPS_DENOMINATOR as i128 - fee_fraction);
+            Some(SwapResult {
+                amount_in: amount,
+                amount_out,
+                path: vec![e, token_in.clone(), token_out.clone()],
+                pairs: vec![e, pool.clone()],
+                fees: vec![e, fee],
+                protocol_fee: 0,
+                venue: pool,
+            })
+        }
+        _ => None,
+    }
+}
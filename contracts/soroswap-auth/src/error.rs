@@ -17,5 +17,51 @@ pub enum SoroswapError {
     /// Invalid parameters
     InvalidArgument = 6,
 
-    NegativeNotAllowed= 7
+    NegativeNotAllowed= 7,
+    /// Caller has not accepted the current terms of use
+    TermsNotAccepted = 8,
+    /// Entrypoint disabled by the admin
+    FeatureDisabled = 9,
+    /// Swap output below the caller's minimum, or required input above the caller's maximum
+    SlippageExceeded = 10,
+    /// The maker has no live guarantee quote for the pair, or not for this amount
+    QuoteUnavailable = 11,
+    /// The maker's collateral cannot cover the shortfall or the withdrawal
+    InsufficientCollateral = 12,
+    /// The primary router's code changed since it was configured and no fallback served the
+    /// swap; the admin must configure the router again
+    RouterUpgraded = 13,
+    /// Withdrawal above the user's claimable balance
+    InsufficientClaimable = 14,
+    /// The admin paused the contract
+    ContractPaused = 15,
+    /// Protocol fee above `MAX_PROTOCOL_FEE`
+    FeeTooHigh = 16,
+    /// Token missing from the allowlist, or blocked by the denylist
+    TokenNotAllowed = 17,
+    /// Swap above the token's remaining volume cap for this ledger
+    RateLimited = 18,
+    /// Operator swap above the user's remaining daily limit
+    DailyLimitExceeded = 19,
+    /// Signed order past its expiry ledger
+    OrderExpired = 20,
+    /// Signed order nonce already executed or cancelled
+    NonceUsed = 21,
+    /// No live commitment matches the revealed swap
+    CommitmentNotFound = 22,
+    /// Reveal in the same ledger as its commitment
+    RevealTooEarly = 23,
+    /// Neither oracle has fresh prices for the pair
+    OracleStale = 24,
+    /// Swap moves the pool price more than the configured maximum
+    PriceImpactTooHigh = 25,
+    /// Swap on a DEX this deployment has not been configured for
+    DexNotConfigured = 26,
+    /// No router or fallback venue executed the swap
+    AllRoutesFailed = 27,
+    /// The caller's swap deadline has already passed
+    DeadlineExpired = 28,
+    /// The tutorial contract rejected a step report; only reported by `metrics`, the operation
+    /// itself completes
+    TutorialRejected = 29,
 }
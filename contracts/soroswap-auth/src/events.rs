@@ -0,0 +1,367 @@
+use common::math::Bps;
+use soroban_sdk::{contractevent, Address, BytesN, Vec};
+
+use crate::types::AdminOp;
+
+/// Emitted for every executed swap, so indexers can track volume without parsing router events
+///
+/// `recipient` is where the output went: the caller, the `swap_to` recipient, or this contract
+/// when `swap_and_distribute` splits it further (see `OutputDistributed`). `protocol_fee` went
+/// to the accrued fees, in `token_out` (in `token_in` for `swap_exact_out`); `amount_out` is
+/// net of it.
+#[contractevent(topics = ["swap"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapExecuted {
+    #[topic]
+    pub caller: Address,
+    #[topic]
+    pub token_in: Address,
+    #[topic]
+    pub token_out: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+    pub recipient: Address,
+    pub protocol_fee: i128,
+}
+
+/// Emitted after a swap, identifying the router (venue) that served the trade and the route
+/// it took
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapRouted {
+    #[topic]
+    pub router: Address,
+    pub caller: Address,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+    pub path: Vec<Address>,
+}
+
+/// Emitted when the admin publishes a new terms of use version
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TermsVersionUpdated {
+    pub version: u32,
+}
+
+/// Emitted when a user acknowledges a terms of use version
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TermsAccepted {
+    #[topic]
+    pub user: Address,
+    pub version: u32,
+}
+
+/// Emitted for every third-party recipient paid by `swap_to` or `swap_and_distribute`
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutputDistributed {
+    #[topic]
+    pub caller: Address,
+    #[topic]
+    pub recipient: Address,
+    pub token_out: Address,
+    pub amount: i128,
+}
+
+/// Emitted when the admin changes the protocol fee or its recipient
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeUpdated {
+    pub fee: Bps,
+    #[topic]
+    pub recipient: Address,
+}
+
+/// Emitted when the fee recipient claims the protocol fees accrued in a token
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeesClaimed {
+    #[topic]
+    pub recipient: Address,
+    #[topic]
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Emitted when `skim` credits a token balance held beyond the contract's accounting to the
+/// protocol fees
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonationSkimmed {
+    #[topic]
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Emitted when a referred swap credits part of its protocol fee to the referrer
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralFeeAccrued {
+    #[topic]
+    pub referrer: Address,
+    #[topic]
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Emitted when a referrer claims the referral fees accrued in a token
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralFeesClaimed {
+    #[topic]
+    pub referrer: Address,
+    #[topic]
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Emitted when the admin adds a token to the allowlist or removes it
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenAllowlistUpdated {
+    #[topic]
+    pub token: Address,
+    pub allowed: bool,
+}
+
+/// Emitted when the admin registers (or with `None` clears) the preferred route of a pair
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RouteUpdated {
+    #[topic]
+    pub token_in: Address,
+    #[topic]
+    pub token_out: Address,
+    pub path: Option<Vec<Address>>,
+}
+
+/// Emitted when the admin blocks a token or unblocks it
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenDenylistUpdated {
+    #[topic]
+    pub token: Address,
+    pub blocked: bool,
+}
+
+/// Emitted when the admin sets (or with `None` removes) a token's per-ledger volume cap
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VolumeCapUpdated {
+    #[topic]
+    pub token: Address,
+    pub cap: Option<i128>,
+}
+
+/// Emitted when a user (or, with `user` unset, the admin's default) changes a daily limit
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DailyLimitUpdated {
+    #[topic]
+    pub token: Address,
+    pub user: Option<Address>,
+    pub limit: Option<i128>,
+}
+
+/// Emitted when the admin sets (or with `None` removes) the price oracle
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleUpdated {
+    pub oracle: Option<Address>,
+    pub fallback: Option<Address>,
+    pub max_deviation: Bps,
+    pub max_age: u64,
+}
+
+/// Emitted when the admin changes the largest price impact a swap may have
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaxPriceImpactUpdated {
+    pub max_impact: Bps,
+}
+
+/// Emitted when the admin pauses or unpauses the contract
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseUpdated {
+    pub paused: bool,
+}
+
+/// Emitted when the admin changes the enabled features bitmask
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeaturesUpdated {
+    pub previous: u32,
+    pub features: u32,
+}
+
+/// Emitted when a user approves (or replaces) a standing order
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StandingApproved {
+    #[topic]
+    pub user: Address,
+    pub executor: Address,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub max_per_fill: i128,
+    pub min_rate: i128,
+    pub expiry: u32,
+}
+
+/// Emitted when a user revokes their standing order
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StandingRevoked {
+    #[topic]
+    pub user: Address,
+}
+
+/// Emitted when an owner approves (or replaces) an operator's allowance for a token
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorApproved {
+    #[topic]
+    pub owner: Address,
+    #[topic]
+    pub operator: Address,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub max_amount: i128,
+    pub min_rate: i128,
+    pub expiry: u32,
+}
+
+/// Emitted when an owner revokes an operator's allowance for a token
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorRevoked {
+    #[topic]
+    pub owner: Address,
+    #[topic]
+    pub operator: Address,
+    pub token: Address,
+}
+
+/// Emitted when a relayer executes a signed order
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrderExecuted {
+    #[topic]
+    pub owner: Address,
+    pub nonce: u64,
+    pub amount_out: i128,
+}
+
+/// Emitted when an owner cancels a signed order before it executes
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrderCancelled {
+    #[topic]
+    pub owner: Address,
+    pub nonce: u64,
+}
+
+/// Emitted when a caller commits to a hidden swap
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapCommitted {
+    #[topic]
+    pub caller: Address,
+    pub hash: BytesN<32>,
+}
+
+/// Emitted when a user swaps back within the round-trip window (likely wash volume)
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundTripDetected {
+    #[topic]
+    pub user: Address,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub count: u32,
+}
+
+/// Emitted once for a whole `admin_batch`, in place of the per-operation events
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminBatchApplied {
+    pub ops: Vec<AdminOp>,
+}
+
+/// Emitted when a maker posts or withdraws collateral
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralUpdated {
+    #[topic]
+    pub maker: Address,
+    #[topic]
+    pub token: Address,
+    /// Collateral after the change
+    pub collateral: i128,
+}
+
+/// Emitted when pull-delivered output is credited or withdrawn
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimableUpdated {
+    #[topic]
+    pub user: Address,
+    #[topic]
+    pub token: Address,
+    /// Claimable balance after the change
+    pub claimable: i128,
+}
+
+/// Emitted when a maker sets (or replaces) a guarantee quote
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuaranteeQuoted {
+    #[topic]
+    pub maker: Address,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub rate: i128,
+    pub remaining_in: i128,
+    pub max_cover_bps: Bps,
+    pub expiry: u32,
+}
+
+/// Emitted after a guaranteed swap, with the maker's side of the settlement
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuaranteeSettled {
+    #[topic]
+    pub maker: Address,
+    pub caller: Address,
+    pub token_out: Address,
+    /// Amount delivered to the caller, the guaranteed minimum
+    pub guaranteed: i128,
+    /// What the AMM produced
+    pub executed: i128,
+    /// `executed - guaranteed`: credited to the maker when positive, drawn from its collateral
+    /// when negative
+    pub maker_delta: i128,
+}
+
+/// Emitted when the admin proposes its successor
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposed {
+    #[topic]
+    pub admin: Address,
+    pub pending_admin: Address,
+}
+
+/// Emitted when the proposed admin accepts and takes over
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminAccepted {
+    #[topic]
+    pub previous_admin: Address,
+    pub admin: Address,
+}
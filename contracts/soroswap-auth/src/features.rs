@@ -0,0 +1,41 @@
+//! Per-entrypoint feature flags
+//!
+//! The enabled features are stored as a bitmask in instance storage. The admin can switch
+//! individual entrypoints off without pausing the whole contract.
+
+/// `swap`, `swap_on`, `swap_split`, `swap_to`, `swap_batch`, `swap_all` and `swap_pct`
+pub const FEATURE_SWAP: u32 = 1 << 0;
+/// `swap_and_distribute`
+pub const FEATURE_SWAP_AND_DISTRIBUTE: u32 = 1 << 1;
+
+/// `swap_via_proxy`
+pub const FEATURE_SWAP_VIA_PROXY: u32 = 1 << 2;
+
+/// `swap_standing`
+pub const FEATURE_SWAP_STANDING: u32 = 1 << 3;
+
+/// `swap_exact_out`
+pub const FEATURE_SWAP_EXACT_OUT: u32 = 1 << 4;
+
+/// `swap_guaranteed`
+pub const FEATURE_SWAP_GUARANTEED: u32 = 1 << 5;
+
+/// `swap_for`
+pub const FEATURE_SWAP_FOR: u32 = 1 << 6;
+
+/// `execute_order`
+pub const FEATURE_EXECUTE_ORDER: u32 = 1 << 7;
+
+/// `commit` and `reveal`
+pub const FEATURE_COMMIT_REVEAL: u32 = 1 << 8;
+
+/// Every known feature; the default when no mask has been stored yet
+pub const ALL_FEATURES: u32 = FEATURE_SWAP
+    | FEATURE_SWAP_AND_DISTRIBUTE
+    | FEATURE_SWAP_VIA_PROXY
+    | FEATURE_SWAP_STANDING
+    | FEATURE_SWAP_EXACT_OUT
+    | FEATURE_SWAP_GUARANTEED
+    | FEATURE_SWAP_FOR
+    | FEATURE_EXECUTE_ORDER
+    | FEATURE_COMMIT_REVEAL;
@@ -1,4 +1,7 @@
 #![no_std]
+// The swap entrypoints take more arguments than clippy allows by default, and the argument
+// helpers `contractimpl` generates for them cannot carry a per-function allow
+#![allow(clippy::too_many_arguments)]
 //! # Soroswap Auth - Aggregator Pattern with Authorization
 //!
 //! This contract demonstrates the **aggregator pattern** where the contract acts as an authorized
@@ -8,13 +11,13 @@
 //! - Contract receives tokens from the user first (takes custody)
 //! - Contract must explicitly authorize the Router's token transfers
 //! - Requires `authorize_as_current_contract` to create authorization context
-//! - Token flow: User → Contract → Router (Pair) → User
+//! - Token flow: User → Contract → Router (Pair) → Contract → User
 //!
 //! ## Why Authorization Context is Required:
 //! When this contract calls the Soroswap Router, the router will internally call `token.transfer()`
-//! to move tokens to the liquidity pair. Since the user only authorized THIS contract (not the
-//! router directly), we must create an authorization context that allows the router to execute
-//! the token transfer on the user's behalf.
+//! to move the tokens held by this contract into the liquidity pair. That transfer is not a direct
+//! call from this contract (the router makes it), so we must create an authorization context that
+//! allows the router to execute the token transfer on this contract's behalf.
 //!
 //! Without `authorize_as_current_contract`, the router's token transfer would fail with an
 //! authorization error, because the authorization chain would be broken.
@@ -22,21 +25,106 @@
 //! ## Authorization Chain:
 //! 1. User signs transaction → Authorizes THIS contract
 //! 2. THIS contract calls `authorize_as_current_contract` → Authorizes the Router's sub-invocation
-//! 3. Router can now transfer tokens from user to pair
+//! 3. Router can now transfer tokens from this contract to the pair
+//!
+//! The context only covers the next contract call this contract makes, so it is created right
+//! before the router call.
+//!
+//! The user's `caller.require_auth()` is bound to the exact invocation: the signed authorization
+//! entry records the function name and every argument (tokens, amount, `amount_out_min`,
+//! deadline, path), and the host rejects it for any other values. A frontend therefore cannot
+//! replay a signature with different parameters. A narrower `require_auth_for_args` tuple would
+//! only sign fewer of them (for instance leaving `path` or the minimum open to a sandwich), so
+//! the entrypoints keep the plain form.
+//!
+//! ## Features:
+//! Besides `swap`, the contract exposes exact-output, batched, split, referred, guaranteed,
+//! operator, signed-order and commit-reveal swaps, plus Phoenix and Aquarius venues through
+//! `swap_on`. Admin-configured protections (protocol fee, token lists, volume caps, daily
+//! limits, oracle and price-impact guards, router fallback) are described on their setters.
 
+use common::{
+    auth::AuthBuilder,
+    deadline::{self, SECONDS_PER_LEDGER},
+    math::{Bps, BPS_DENOMINATOR},
+    pay::SwapResult,
+    soroswap_pair::{reserves_toward, SoroswapPairClient},
+    tutorial::{record_step, STEP_ORDER_FILL, STEP_PATH_SWAP, STEP_SIMPLE_SWAP},
+};
 use soroban_sdk::{
-    Address, Env, IntoVal, Symbol, Val, Vec, auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation}, contract, contractimpl, token, vec
+    Address, BytesN, Env, Executable, IntoVal, Symbol, Val, Vec, contract, contractimpl, token,
+    vec, xdr::ToXdr,
 };
 
 mod soroswap_router;
+mod soroswap_factory;
+mod swap_proxy;
+mod reflector;
+mod adapter;
+mod soroswap_adapter;
+mod phoenix_adapter;
+mod aqua_adapter;
 mod storage;
 mod error;
+mod events;
+mod features;
+mod types;
+
+#[cfg(test)]
+mod test;
 
 use soroswap_router::SoroswapRouterClient;
+use soroswap_factory::SoroswapFactoryClient;
+use swap_proxy::SwapProxyClient;
+use reflector::{Asset, ReflectorClient};
+use adapter::Venue;
 use storage::{
-    extend_instance_ttl, get_soroswap_router_address, set_soroswap_router_address,
+    get_metrics, set_metrics, EntrypointMetrics, get_current_entrypoint, set_current_entrypoint, get_guarantee_quote, get_maker_collateral,
+    remove_guarantee_quote, set_guarantee_quote, set_maker_collateral, GuaranteeQuote,
+    extend_instance_ttl, get_accepted_terms, get_admin, get_fallback_routers, get_receipt,
+    get_receipt_count, get_receipts_enabled, get_soroswap_router_address, get_terms_version,
+    get_user_receipt, get_user_receipt_count, set_accepted_terms, set_admin, set_fallback_routers,
+    set_receipt, set_receipt_count, set_receipts_enabled, set_soroswap_router_address,
+    set_terms_version, set_user_receipt, set_user_receipt_count, get_features, set_features, Receipt,
+    get_standing_approval, remove_standing_approval, set_standing_approval, StandingApproval,
+    accepted_terms_entry_size, receipt_entries_size, standing_approval_entry_size, pair_entries_size,
+    accrued_fees_entry_size, daily_spend_entry_size, order_nonce_entry_size,
+    get_last_leg, get_round_trip_window, get_round_trips, set_last_leg, set_round_trip_window,
+    set_round_trips, get_router_wasm_hash, set_router_wasm_hash, get_claimable, set_claimable,
+    get_pair_executions, set_pair_executions, get_tutorial, set_tutorial, get_pending_admin,
+    remove_pending_admin, set_pending_admin, get_paused, set_paused, get_protocol_fee,
+    set_protocol_fee, FeeConfig, get_accrued_fees, set_accrued_fees, get_referral_fees,
+    get_referral_share, set_referral_fees, set_referral_share, get_token_list_mode,
+    get_token_allowed, remove_token_allowed, set_token_list_mode, set_token_allowed, TokenListMode,
+    get_token_denied, remove_token_denied, set_token_denied, get_ledger_volume, get_volume_cap,
+    set_ledger_volume, set_volume_cap, get_daily_limit, get_daily_spend, get_default_daily_limit,
+    set_daily_limit, set_daily_spend, set_default_daily_limit, SpendWindow, get_operator_allowance,
+    operator_allowance_entry_size, remove_operator_allowance, set_operator_allowance,
+    OperatorAllowance, get_order_nonce_used, set_order_nonce_used, get_commitment, remove_commitment,
+    set_commitment, COMMITMENT_TTL, get_oracle, remove_oracle, set_oracle, OracleConfig,
+    get_max_price_impact, set_max_price_impact, get_phoenix_factory, set_phoenix_factory,
+    get_aqua_router, set_aqua_router, get_quote_budget, set_quote_budget, get_fallback_dexes,
+    set_fallback_dexes, get_pair_address, remove_pair_address, get_route, remove_route, set_route,
+    get_owed_total,
 };
 use error::SoroswapError;
+use events::{
+    AdminAccepted, AdminBatchApplied, AdminProposed, ClaimableUpdated, CollateralUpdated,
+    DailyLimitUpdated, DonationSkimmed, FeaturesUpdated, FeeUpdated, FeesClaimed, GuaranteeQuoted, GuaranteeSettled,
+    MaxPriceImpactUpdated, OperatorApproved, OperatorRevoked, OracleUpdated, OrderCancelled,
+    OrderExecuted, OutputDistributed, PauseUpdated, ReferralFeeAccrued, ReferralFeesClaimed,
+    RoundTripDetected, RouteUpdated, StandingApproved, StandingRevoked, SwapCommitted, SwapExecuted, SwapRouted,
+    TermsAccepted, TermsVersionUpdated, TokenAllowlistUpdated, TokenDenylistUpdated, VolumeCapUpdated,
+};
+use features::{
+    ALL_FEATURES, FEATURE_COMMIT_REVEAL, FEATURE_EXECUTE_ORDER, FEATURE_SWAP, FEATURE_SWAP_AND_DISTRIBUTE,
+    FEATURE_SWAP_EXACT_OUT, FEATURE_SWAP_FOR, FEATURE_SWAP_GUARANTEED, FEATURE_SWAP_STANDING,
+    FEATURE_SWAP_VIA_PROXY,
+};
+use types::{
+    AdminOp, AuthOperation, AuthSpec, CommittedSwap, Dex, HistoryPage, PairQuality, PairReserves, PushOrPull,
+    RentEstimate, SplitLeg, SplitSwapResult, SwapOrder, SwapParams, SwapRequest, SwapSimulation,
+};
 
 /// Validates that the amount is non-negative
 ///
@@ -49,116 +137,3337 @@ pub fn check_nonnegative_amount(amount: i128) -> Result<(), SoroswapError> {
     }
 }
 
+/// Highest protocol fee the admin can configure (1%)
+const MAX_PROTOCOL_FEE: Bps = Bps::from_const(100);
+
+/// Recent direct swaps per pair kept for `pair_quality`
+const PAIR_QUALITY_WINDOW: u32 = 32;
+
+/// Most swaps a single `swap_batch` executes, bounding its budget
+const MAX_BATCH_SWAPS: u32 = 8;
+
+/// Most legs a single `swap_split` executes, bounding its budget
+const MAX_SPLIT_LEGS: u32 = 4;
+
+/// Fixed-point scale of guarantee quote, standing-order and operator rates
+const RATE_PRECISION: i128 = 10_000_000;
+
+/// Longest caller-supplied swap path, in tokens (three hops)
+const MAX_PATH_LENGTH: u32 = 4;
+
+/// Largest page returned by the paginated market data views
+const MAX_PAGE_SIZE: u32 = 50;
+
+/// Entrypoints that execute a swap, as estimated by `estimate_rent`
+const SWAP_ENTRYPOINTS: [&str; 15] = [
+    "swap",
+    "swap_on",
+    "swap_split",
+    "swap_batch",
+    "swap_to",
+    "swap_all",
+    "swap_pct",
+    "swap_exact_out",
+    "swap_and_distribute",
+    "swap_for",
+    "reveal",
+    "execute_order",
+    "swap_standing",
+    "swap_via_proxy",
+    "swap_guaranteed",
+];
+
+/// Length of the operator daily-limit window, in seconds of ledger time
+const DAILY_LIMIT_WINDOW: u64 = 24 * 60 * 60;
+
+/// Resolves the deadline passed to the routers (see `common::deadline`)
+///
+/// `deadline` is the caller's absolute bound and `deadline_delta` a relative one; when both are
+/// given the earlier applies. At least one is required, so a delayed transaction cannot fill
+/// at a stale price: fails with `InvalidArgument` when neither is given and with
+/// `DeadlineExpired` once `deadline` has passed.
+pub fn resolve_deadline(e: &Env, deadline: Option<u64>, deadline_delta: Option<u32>) -> Result<u64, SoroswapError> {
+    if deadline.is_none() && deadline_delta.is_none() {
+        return Err(SoroswapError::InvalidArgument);
+    }
+    if deadline.is_some_and(|deadline| deadline::is_expired(e, deadline)) {
+        return Err(SoroswapError::DeadlineExpired);
+    }
+    let resolved = deadline::resolve_deadline(e, deadline_delta).ok_or(SoroswapError::InvalidArgument)?;
+    Ok(deadline.map_or(resolved, |deadline| deadline.min(resolved)))
+}
+
+/// Router deadline of a deferred execution, derived from the intent's own freshness bound
+///
+/// Fills of an intent signed earlier (e.g. a standing approval) must not carry a deadline
+/// fixed at signing time, which would already have passed. The deadline is recomputed at
+/// execution instead: the ledgers left until `expiry_ledger` (inclusive), converted from the
+/// current ledger timestamp.
+fn intent_deadline(e: &Env, expiry_ledger: u32) -> u64 {
+    let remaining = expiry_ledger.saturating_sub(e.ledger().sequence()) as u64 + 1;
+    e.ledger()
+        .timestamp()
+        .saturating_add(remaining * SECONDS_PER_LEDGER)
+}
+
+/// Validates that the caller accepted the current terms of use
+///
+/// The gate is only active once the admin publishes a terms version (non-zero); bumping the
+/// version invalidates every earlier acknowledgment.
+pub fn check_terms_accepted(e: &Env, caller: &Address) -> Result<(), SoroswapError> {
+    let version = get_terms_version(e);
+    if version != 0 && get_accepted_terms(e, caller) != version {
+        Err(SoroswapError::TermsNotAccepted)
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates that the admin has not paused the contract
+pub fn check_not_paused(e: &Env) -> Result<(), SoroswapError> {
+    if get_paused(e) {
+        Err(SoroswapError::ContractPaused)
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates that the token list mode accepts both ends of a swap
+pub fn check_tokens_allowed(e: &Env, token_in: &Address, token_out: &Address) -> Result<(), SoroswapError> {
+    let accepted = match get_token_list_mode(e) {
+        TokenListMode::Open => true,
+        TokenListMode::Allowlist => get_token_allowed(e, token_in) && get_token_allowed(e, token_out),
+        TokenListMode::Denylist => !get_token_denied(e, token_in) && !get_token_denied(e, token_out),
+    };
+    if accepted {
+        Ok(())
+    } else {
+        Err(SoroswapError::TokenNotAllowed)
+    }
+}
+
+/// Adds `amount` to this ledger's volume of `token`, failing when it exceeds the token's cap
+fn record_volume(e: &Env, token: &Address, amount: i128) -> Result<(), SoroswapError> {
+    let Some(cap) = get_volume_cap(e, token) else {
+        return Ok(());
+    };
+    let ledger = e.ledger().sequence();
+    let volume = get_ledger_volume(e, token, ledger) + amount;
+    if volume > cap {
+        return Err(SoroswapError::RateLimited);
+    }
+    set_ledger_volume(e, token, ledger, volume);
+    Ok(())
+}
+
+/// Daily limit on what operators swap of `token` for `user`: the lower of the user's own and
+/// the admin's default
+fn daily_limit(e: &Env, user: &Address, token: &Address) -> Option<i128> {
+    match (get_daily_limit(e, user, token), get_default_daily_limit(e, token)) {
+        (Some(own), Some(default)) => Some(own.min(default)),
+        (own, default) => own.or(default),
+    }
+}
+
+/// The user's spend window for `token`, reopened when a day has passed since it started
+fn current_spend(e: &Env, user: &Address, token: &Address) -> SpendWindow {
+    let now = e.ledger().timestamp();
+    match get_daily_spend(e, user, token) {
+        Some(window) if now < window.start.saturating_add(DAILY_LIMIT_WINDOW) => window,
+        _ => SpendWindow { start: now, spent: 0 },
+    }
+}
+
+/// Counts `amount` of `token` an operator swaps for `user` against their daily limit
+fn record_delegated_spend(e: &Env, user: &Address, token: &Address, amount: i128) -> Result<(), SoroswapError> {
+    let Some(limit) = daily_limit(e, user, token) else {
+        return Ok(());
+    };
+    let mut window = current_spend(e, user, token);
+    window.spent += amount;
+    if window.spent > limit {
+        return Err(SoroswapError::DailyLimitExceeded);
+    }
+    set_daily_spend(e, user, token, &window);
+    Ok(())
+}
+
+/// Fresh, positive price of `token` on `feed`
+fn feed_price(e: &Env, feed: &Address, token: &Address, max_age: u64) -> Option<i128> {
+    let record = ReflectorClient::new(e, feed)
+        .try_lastprice(&Asset::Stellar(token.clone()))
+        .ok()?
+        .ok()??;
+    let fresh = record.timestamp.saturating_add(max_age) >= e.ledger().timestamp();
+    (fresh && record.price > 0).then_some(record.price)
+}
+
+/// Least output `feed` accepts for swapping `amount` of `token_in` into `token_out`
+///
+/// `None` when the feed has no fresh price for either token. Both prices come from the same
+/// feed, so its base asset and decimals cancel out; the token decimals do not.
+fn feed_min_out(
+    e: &Env,
+    feed: &Address,
+    config: &OracleConfig,
+    token_in: &Address,
+    token_out: &Address,
+    amount: i128,
+) -> Option<i128> {
+    let price_in = feed_price(e, feed, token_in, config.max_age)?;
+    let price_out = feed_price(e, feed, token_out, config.max_age)?;
+
+    let decimals_in = token::Client::new(e, token_in).decimals();
+    let decimals_out = token::Client::new(e, token_out).decimals();
+    let value = amount.checked_mul(price_in)?;
+    let value = if decimals_out >= decimals_in {
+        value.checked_mul(10_i128.checked_pow(decimals_out - decimals_in)?)?
+    } else {
+        value / 10_i128.checked_pow(decimals_in - decimals_out)?
+    };
+    config.max_deviation.complement().checked_apply(value / price_out)
+}
+
+/// Least output the oracle guard accepts for swapping `amount` of `token_in` into `token_out`
+///
+/// `None` while no oracle is configured. The primary feed is tried first, then the fallback;
+/// fails with `OracleStale` when neither prices the pair with fresh data.
+fn oracle_min_out(
+    e: &Env,
+    token_in: &Address,
+    token_out: &Address,
+    amount: i128,
+) -> Result<Option<i128>, SoroswapError> {
+    let Some(config) = get_oracle(e) else {
+        return Ok(None);
+    };
+    feed_min_out(e, &config.oracle, &config, token_in, token_out, amount)
+        .or_else(|| {
+            let fallback = config.fallback.as_ref()?;
+            feed_min_out(e, fallback, &config, token_in, token_out, amount)
+        })
+        .map(Some)
+        .ok_or(SoroswapError::OracleStale)
+}
+
+/// Price impact of swapping `amount` along `path` on `router`: how far the router's quote falls
+/// below the output at the pools' spot prices
+///
+/// `None` when the router cannot quote the path or a pool is empty.
+fn price_impact(e: &Env, router: &Address, path: &Vec<Address>, amount: i128) -> Option<Bps> {
+    let router_client = SoroswapRouterClient::new(e, router);
+    let quoted = router_client.try_router_get_amounts_out(&amount, path).ok()?.ok()?.last()?;
+
+    let mut spot = amount;
+    for hop in 0..path.len() - 1 {
+        let token_in = path.get_unchecked(hop);
+        let pair = soroswap_adapter::pair_for(e, router, &token_in, &path.get_unchecked(hop + 1))?;
+        let reserves = reserves_toward(e, &pair, &token_in)?;
+        if reserves.reserve_in <= 0 {
+            return None;
+        }
+        spot = spot.checked_mul(reserves.reserve_out)? / reserves.reserve_in;
+    }
+
+    if spot <= 0 || quoted >= spot {
+        return Some(Bps::ZERO);
+    }
+    Bps::new(((spot - quoted).checked_mul(BPS_DENOMINATOR as i128)? / spot) as u32)
+}
+
+/// Validates that swapping `amount` along `path` stays within the configured price impact
+fn check_price_impact(e: &Env, path: &Vec<Address>, amount: i128) -> Result<(), SoroswapError> {
+    let Some(max_impact) = get_max_price_impact(e) else {
+        return Ok(());
+    };
+    match price_impact(e, &get_soroswap_router_address(e), path, amount) {
+        Some(impact) if impact > max_impact => Err(SoroswapError::PriceImpactTooHigh),
+        _ => Ok(()),
+    }
+}
+
+/// SHA-256 of the swap parameters' XDR followed by the salt
+fn swap_commitment_hash(e: &Env, params: &CommittedSwap, salt: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = params.clone().to_xdr(e);
+    preimage.append(&salt.clone().into());
+    e.crypto().sha256(&preimage).into()
+}
+
+/// Validates that the admin has not disabled the given entrypoint
+pub fn check_feature_enabled(e: &Env, feature: u32) -> Result<(), SoroswapError> {
+    if get_features(e) & feature == 0 {
+        Err(SoroswapError::FeatureDisabled)
+    } else {
+        Ok(())
+    }
+}
+
+/// Persists a receipt for an executed operation when receipts are enabled
+///
+/// The receipt is also appended to the caller's receipt index. Returns the receipt id, or
+/// `None` when receipt persistence is turned off.
+fn record_receipt(
+    e: &Env,
+    caller: &Address,
+    token_in: &Address,
+    token_out: &Address,
+    amount_in: i128,
+    amount_out: i128,
+) -> Option<u64> {
+    if !get_receipts_enabled(e) {
+        return None;
+    }
+
+    let id = get_receipt_count(e);
+    set_receipt(
+        e,
+        &Receipt {
+            id,
+            caller: caller.clone(),
+            token_in: token_in.clone(),
+            token_out: token_out.clone(),
+            amount_in,
+            amount_out,
+            ledger: e.ledger().sequence(),
+        },
+    );
+    set_receipt_count(e, id + 1);
+
+    let index = get_user_receipt_count(e, caller);
+    set_user_receipt(e, caller, index, id);
+    set_user_receipt_count(e, caller, index + 1);
+
+    Some(id)
+}
+
+/// Validates and applies one configuration change, without emitting events
+fn apply_admin_op(e: &Env, op: &AdminOp) -> Result<(), SoroswapError> {
+    match op {
+        AdminOp::Router(router) => configure_router(e, router.clone()),
+        AdminOp::FallbackRouters(routers) => set_fallback_routers(e, routers.clone()),
+        AdminOp::Features(features) => {
+            if features & !ALL_FEATURES != 0 {
+                return Err(SoroswapError::InvalidArgument);
+            }
+            set_features(e, *features);
+        }
+        AdminOp::TermsVersion(version) => set_terms_version(e, *version),
+        AdminOp::ReceiptsEnabled(enabled) => set_receipts_enabled(e, *enabled),
+        AdminOp::RoundTripWindow(ledgers) => {
+            if *ledgers == 0 {
+                return Err(SoroswapError::InvalidArgument);
+            }
+            set_round_trip_window(e, *ledgers);
+        }
+        AdminOp::Tutorial(tutorial) => set_tutorial(e, tutorial),
+        AdminOp::Paused(paused) => set_paused(e, *paused),
+        AdminOp::TokenListMode(mode) => set_token_list_mode(e, *mode),
+        AdminOp::ReferralShare(share) => {
+            if !share.is_valid() {
+                return Err(SoroswapError::InvalidArgument);
+            }
+            set_referral_share(e, *share);
+        }
+        AdminOp::Oracle(config) => {
+            if !config.max_deviation.is_valid() || config.max_age == 0 {
+                return Err(SoroswapError::InvalidArgument);
+            }
+            set_oracle(e, config);
+        }
+        AdminOp::MaxPriceImpact(max_impact) => {
+            if !max_impact.is_valid() {
+                return Err(SoroswapError::InvalidArgument);
+            }
+            set_max_price_impact(e, *max_impact);
+        }
+        AdminOp::PhoenixFactory(factory) => set_phoenix_factory(e, factory),
+        AdminOp::AquaRouter(router) => set_aqua_router(e, router),
+        AdminOp::FallbackDexes(dexes) => {
+            if dexes.iter().any(|dex| matches!(dex, Dex::Soroswap | Dex::Best)) {
+                return Err(SoroswapError::InvalidArgument);
+            }
+            set_fallback_dexes(e, dexes);
+        }
+        AdminOp::QuoteBudget(venues) => {
+            if *venues == 0 {
+                return Err(SoroswapError::InvalidArgument);
+            }
+            set_quote_budget(e, *venues);
+        }
+        AdminOp::Fee(config) => {
+            if config.fee > MAX_PROTOCOL_FEE {
+                return Err(SoroswapError::FeeTooHigh);
+            }
+            set_protocol_fee(e, config);
+        }
+    }
+    Ok(())
+}
+
+/// Orders a token pair so both directions share the same round-trip counter
+fn sorted_pair<'a>(token_a: &'a Address, token_b: &'a Address) -> (&'a Address, &'a Address) {
+    if token_a <= token_b {
+        (token_a, token_b)
+    } else {
+        (token_b, token_a)
+    }
+}
+
+/// Counts a round trip when the caller swapped the opposite way within the window
+///
+/// Workshop analytics only: nothing is blocked. Trading-competition organizers read the
+/// counters to discount obvious wash volume.
+fn track_round_trip(e: &Env, caller: &Address, token_in: &Address, token_out: &Address) {
+    let ledger = e.ledger().sequence();
+    if let Some(previous) = get_last_leg(e, caller, token_out, token_in) {
+        if ledger - previous <= get_round_trip_window(e) {
+            let (token_a, token_b) = sorted_pair(token_in, token_out);
+            let count = get_round_trips(e, caller, token_a, token_b) + 1;
+            set_round_trips(e, caller, token_a, token_b, count);
+
+            RoundTripDetected {
+                user: caller.clone(),
+                token_in: token_in.clone(),
+                token_out: token_out.clone(),
+                count,
+            }
+            .publish(e);
+        }
+    }
+    set_last_leg(e, caller, token_in, token_out, ledger);
+}
+
+/// Wasm hash deployed at `router`, `None` when it is not a deployed Wasm contract
+fn router_wasm_hash(router: &Address) -> Option<BytesN<32>> {
+    match router.executable() {
+        Some(Executable::Wasm(hash)) => Some(hash),
+        _ => None,
+    }
+}
+
+/// Stores the primary router together with the Wasm hash it currently runs
+fn configure_router(e: &Env, router: Address) {
+    set_router_wasm_hash(e, router_wasm_hash(&router));
+    set_soroswap_router_address(e, router);
+}
+
+/// Whether the primary router still runs the code it was configured with
+///
+/// Routers configured without a recorded hash (not a Wasm contract at the time) are not
+/// checked.
+fn primary_router_current(e: &Env) -> bool {
+    match get_router_wasm_hash(e) {
+        Some(hash) => router_wasm_hash(&get_soroswap_router_address(e)) == Some(hash),
+        None => true,
+    }
+}
+
+/// Routers a swap tries, in order: the primary (unless it was upgraded), then the fallbacks
+fn candidate_routers(e: &Env) -> Vec<Address> {
+    let mut routers: Vec<Address> = Vec::new(e);
+    if primary_router_current(e) {
+        routers.push_back(get_soroswap_router_address(e));
+    }
+    routers.append(&get_fallback_routers(e));
+    routers
+}
+
+/// Error reported when no router (nor fallback venue) served a swap
+fn no_router_error(e: &Env) -> SoroswapError {
+    if primary_router_current(e) {
+        SoroswapError::AllRoutesFailed
+    } else {
+        SoroswapError::RouterUpgraded
+    }
+}
+
+/// Error reported when none of `venues` swapped `amount` along `path` for at least `min_out`
+///
+/// With a minimum set, the venues are quoted (up to `quote_budget` of them): when the best
+/// quote falls short of the minimum, the swap failed on slippage rather than on routing and
+/// `SlippageExceeded` is reported. Otherwise the venues could not serve the path and `unrouted`
+/// is.
+fn venue_failure(
+    e: &Env,
+    venues: &Vec<Venue>,
+    path: &Vec<Address>,
+    amount: i128,
+    min_out: i128,
+    unrouted: SoroswapError,
+) -> SoroswapError {
+    if min_out > 0 {
+        let budget = get_quote_budget(e).unwrap_or(u32::MAX);
+        let best = venues
+            .iter()
+            .take(budget as usize)
+            .filter_map(|venue| venue.quote(e, path, amount).and_then(|amounts| amounts.last()))
+            .max();
+        if best.is_some_and(|best| best < min_out) {
+            return SoroswapError::SlippageExceeded;
+        }
+    }
+    unrouted
+}
+
+/// Adds a direct swap's realized-versus-quoted delta to the pair's rolling window
+fn record_execution(e: &Env, token_in: &Address, token_out: &Address, quoted: i128, realized: i128) {
+    if quoted <= 0 {
+        return;
+    }
+    let delta_bps = ((realized - quoted) * BPS_DENOMINATOR as i128 / quoted)
+        .clamp(i32::MIN as i128, i32::MAX as i128) as i32;
+
+    let (token_a, token_b) = sorted_pair(token_in, token_out);
+    let mut deltas = get_pair_executions(e, token_a, token_b);
+    if deltas.len() >= PAIR_QUALITY_WINDOW {
+        deltas.pop_front();
+    }
+    deltas.push_back(delta_bps);
+    set_pair_executions(e, token_a, token_b, &deltas);
+}
+
+/// Output of swapping `amount` along `path`, net of the protocol fee, as the first router able
+/// to quote it would deliver
+///
+/// Routers are asked in the order swaps try them. The quote assumes a plain token; fee-on-transfer
+/// tokens deliver less.
+fn quote_path(e: &Env, path: &Vec<Address>, amount: i128) -> Result<i128, SoroswapError> {
+    let (_, amounts) = quote_on_routers(e, path, amount)?;
+    let gross = amounts.last().unwrap_or(0);
+    Ok(gross - protocol_fee_on(e, gross))
+}
+
+/// First router, in the order swaps try them, that quotes `amount` along `path`, with its
+/// amounts for each token of the path
+fn quote_on_routers(e: &Env, path: &Vec<Address>, amount: i128) -> Result<(Address, Vec<i128>), SoroswapError> {
+    for router in candidate_routers(e).iter() {
+        if let Some(amounts) = soroswap_adapter::quote(e, &router, path, amount) {
+            return Ok((router, amounts));
+        }
+    }
+    Err(no_router_error(e))
+}
+
+/// Protocol fee charged on `amount` (0 while no fee is configured)
+fn protocol_fee_on(e: &Env, amount: i128) -> i128 {
+    get_protocol_fee(e).map_or(0, |config| config.fee.apply(amount))
+}
+
+/// Accrues the protocol fee on `amount` of `token`, held by this contract, for `claim_fees`
+///
+/// Returns the fee taken (0 while no fee is configured).
+fn take_protocol_fee(e: &Env, token: &Address, amount: i128) -> i128 {
+    let fee = protocol_fee_on(e, amount);
+    if fee > 0 {
+        set_accrued_fees(e, token, get_accrued_fees(e, token) + fee);
+    }
+    fee
+}
+
+/// Moves the referral share of `protocol_fee`, already accrued in `token`, to `referrer`
+fn credit_referrer(e: &Env, referrer: &Address, token: &Address, protocol_fee: i128) {
+    let share = get_referral_share(e).apply(protocol_fee);
+    if share == 0 {
+        return;
+    }
+    set_accrued_fees(e, token, get_accrued_fees(e, token) - share);
+    set_referral_fees(e, referrer, token, get_referral_fees(e, referrer, token) + share);
+
+    ReferralFeeAccrued {
+        referrer: referrer.clone(),
+        token: token.clone(),
+        amount: share,
+    }
+    .publish(e);
+}
+
+/// Pulls `amount` of `token` from `from` into this contract's custody
+///
+/// Returns the amount actually received, measured as a balance delta. For tokens that charge
+/// a fee on transfer this is less than `amount`, and it is the figure the swap (and its
+/// authorization entries) must use.
+fn take_custody(e: &Env, token: &Address, from: &Address, amount: i128) -> i128 {
+    let token_client = token::Client::new(e, token);
+    let this = e.current_contract_address();
+    let balance_before = token_client.balance(&this);
+    token_client.transfer(from, &this, &amount);
+    token_client.balance(&this) - balance_before
+}
+
+/// Swap route through the direct pair `token_in -> token_out`
+fn direct_path(e: &Env, token_in: &Address, token_out: &Address) -> Vec<Address> {
+    vec![e, token_in.clone(), token_out.clone()]
+}
+
+/// Route of a swap that supplies no path: the pair's preferred route if the admin registered
+/// one (`set_route`), the direct pair otherwise
+fn default_path(e: &Env, token_in: &Address, token_out: &Address) -> Vec<Address> {
+    get_route(e, token_in, token_out).unwrap_or_else(|| direct_path(e, token_in, token_out))
+}
+
+/// Default route of a swap on `dex`: preferred routes are multi-hop, which only the Soroswap
+/// router serves, so the other exchanges always get the direct pair
+fn default_path_on(e: &Env, dex: Dex, token_in: &Address, token_out: &Address) -> Vec<Address> {
+    match dex {
+        Dex::Soroswap | Dex::Best => default_path(e, token_in, token_out),
+        Dex::SoroswapDirect | Dex::Phoenix | Dex::Aqua => direct_path(e, token_in, token_out),
+    }
+}
+
+/// Validates a route: it must start at `token_in`, end at `token_out` and have at most
+/// `MAX_PATH_LENGTH` tokens
+fn check_path(path: &Vec<Address>, token_in: &Address, token_out: &Address) -> Result<(), SoroswapError> {
+    if !(2..=MAX_PATH_LENGTH).contains(&path.len())
+        || path.first_unchecked() != *token_in
+        || path.last_unchecked() != *token_out
+    {
+        return Err(SoroswapError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Resolves the route of a swap: the caller's path if given (validated by `check_path`), the
+/// default route otherwise
+fn resolve_path(
+    e: &Env,
+    token_in: &Address,
+    token_out: &Address,
+    path: Option<Vec<Address>>,
+) -> Result<Vec<Address>, SoroswapError> {
+    let Some(path) = path else {
+        return Ok(default_path(e, token_in, token_out));
+    };
+    check_path(&path, token_in, token_out)?;
+    Ok(path)
+}
+
+/// Bounds a swap is executed within
+#[derive(Clone, Copy)]
+struct SwapLimits {
+    /// Least output the caller accepts, net of the protocol fee (0 for no bound)
+    min_out: i128,
+    /// Router deadline, as resolved by `resolve_deadline`
+    deadline: u64,
+}
+
+/// Least output a venue must deliver for a swap, passed to it as its own minimum
+///
+/// Grossed up by the protocol fee so that `min_out` remains after it, and never below the
+/// oracle minimum. A venue falling short reverts on its own, and the next venue is tried.
+fn venue_min_out(e: &Env, min_out: i128, oracle_min: Option<i128>) -> i128 {
+    let gross = match get_protocol_fee(e) {
+        Some(config) if min_out > 0 => {
+            // The fee is capped well below 100%, so `kept` is positive
+            let kept = (BPS_DENOMINATOR - config.fee.value()) as i128;
+            min_out
+                .checked_mul(BPS_DENOMINATOR as i128)
+                .map_or(i128::MAX, |scaled| (scaled + kept - 1) / kept)
+        }
+        _ => min_out,
+    };
+    gross.max(oracle_min.unwrap_or(0))
+}
+
+/// Takes custody of `amount` of the first token of `path` from the caller and swaps it along
+/// the path
+///
+/// The output stays in this contract's custody; callers decide how to deliver it. `recipient`
+/// is where they deliver it, as reported in the `SwapExecuted` event.
+fn swap_into_custody(
+    e: &Env,
+    caller: &Address,
+    recipient: &Address,
+    path: &Vec<Address>,
+    amount: i128,
+    limits: SwapLimits,
+) -> Result<SwapResult, SoroswapError> {
+    // Transfer tokens from the user to this contract (contract takes custody)
+    // The user's signature authorizes this transfer
+    let received = take_custody(e, &path.first_unchecked(), caller, amount);
+
+    swap_from_custody(e, caller, recipient, path, amount, received, limits)
+}
+
+/// Swaps `received` of the first token of `path`, already in this contract's custody, along
+/// the path
+///
+/// `amount` is what the caller committed, which is what receipts and events report. The
+/// primary router is tried first, then the fallbacks in their configured order; each is given
+/// the `venue_min_out` of the swap, and when none delivers it the swap fails with
+/// `SlippageExceeded` if their quotes fall short of it (see `venue_failure`). The protocol fee
+/// is taken from the output; the rest stays in this contract's custody.
+fn swap_from_custody(
+    e: &Env,
+    caller: &Address,
+    recipient: &Address,
+    path: &Vec<Address>,
+    amount: i128,
+    received: i128,
+    limits: SwapLimits,
+) -> Result<SwapResult, SoroswapError> {
+    let token_in = &path.first_unchecked();
+    let token_out = &path.last_unchecked();
+    check_tokens_allowed(e, token_in, token_out)?;
+    let oracle_min = oracle_min_out(e, token_in, token_out, received)?;
+    check_price_impact(e, path, received)?;
+
+    // Primary router first, then the fallbacks in their configured order
+    let venue_min = venue_min_out(e, limits.min_out, oracle_min);
+    let venues = venues(e, Dex::Soroswap)?;
+    let result = swap_on_venues(e, &venues, path, received, venue_min, limits.deadline)
+        .ok_or_else(|| venue_failure(e, &venues, path, received, venue_min, no_router_error(e)))?;
+    settle_swap(e, caller, recipient, result, amount, oracle_min, limits.min_out)
+}
+
+/// Venues a swap on `dex` tries, in order
+///
+/// Soroswap has the primary router (unless it was upgraded), the fallback routers and then the
+/// configured fallback exchanges, which only serve direct pairs. The other exchanges have the
+/// single factory or router their pools are looked up with, and fail with `DexNotConfigured`
+/// while it is not set. `Best` has every configured exchange: the first Soroswap router, then
+/// Phoenix and Aquarius when set.
+fn venues(e: &Env, dex: Dex) -> Result<Vec<Venue>, SoroswapError> {
+    let mut tried: Vec<Venue> = Vec::new(e);
+    match dex {
+        Dex::Soroswap => {
+            for router in candidate_routers(e).iter() {
+                tried.push_back(Venue::Soroswap(router));
+            }
+            for dex in get_fallback_dexes(e).iter() {
+                // Fallbacks whose factory or router is not set are skipped
+                if let Ok(fallback) = venues(e, dex) {
+                    tried.append(&fallback);
+                }
+            }
+        }
+        Dex::SoroswapDirect => tried.push_back(Venue::SoroswapDirect(get_soroswap_router_address(e))),
+        Dex::Phoenix => tried.push_back(Venue::Phoenix(
+            get_phoenix_factory(e).ok_or(SoroswapError::DexNotConfigured)?,
+        )),
+        Dex::Aqua => tried.push_back(Venue::Aqua(get_aqua_router(e).ok_or(SoroswapError::DexNotConfigured)?)),
+        Dex::Best => {
+            if let Some(router) = candidate_routers(e).first() {
+                tried.push_back(Venue::Soroswap(router));
+            }
+            if let Some(factory) = get_phoenix_factory(e) {
+                tried.push_back(Venue::Phoenix(factory));
+            }
+            if let Some(router) = get_aqua_router(e) {
+                tried.push_back(Venue::Aqua(router));
+            }
+        }
+    }
+    Ok(tried)
+}
+
+/// `venues` that quote `amount` along `path`, best quote first
+///
+/// At most `quote_budget` venues are quoted, in their given order; the rest are dropped.
+/// Contracts cannot read their remaining instruction budget, so the number of venues quoted
+/// is what bounds the cost.
+fn rank_by_quote(e: &Env, venues: &Vec<Venue>, path: &Vec<Address>, amount: i128) -> Vec<Venue> {
+    let budget = get_quote_budget(e).unwrap_or(u32::MAX);
+    let mut ranked: Vec<(Venue, i128)> = Vec::new(e);
+    for venue in venues.iter().take(budget as usize) {
+        let Some(quoted) = venue.quote(e, path, amount).and_then(|amounts| amounts.last()) else {
+            continue;
+        };
+        let position = ranked.iter().position(|(_, other)| quoted > other).unwrap_or(ranked.len() as usize);
+        ranked.insert(position as u32, (venue, quoted));
+    }
+    let mut best_first: Vec<Venue> = Vec::new(e);
+    for (venue, _) in ranked.iter() {
+        best_first.push_back(venue);
+    }
+    best_first
+}
+
+/// Swaps `amount` along `path` on the first of `venues` that executes it for at least `min_out`
+///
+/// Each venue that fails first is counted as a caught failure of the entrypoint.
+fn swap_on_venues(
+    e: &Env,
+    venues: &Vec<Venue>,
+    path: &Vec<Address>,
+    amount: i128,
+    min_out: i128,
+    deadline: u64,
+) -> Option<SwapResult> {
+    for venue in venues.iter() {
+        if let Some(result) = venue.swap_exact_in(e, path, amount, min_out, deadline) {
+            return Some(result);
+        }
+        record_failure(e, SoroswapError::SwapFailed);
+    }
+    None
+}
+
+/// Swaps `received` of the first token of the direct `path`, already in this contract's
+/// custody, on `dex`
+///
+/// Soroswap goes through `swap_from_custody`. The other exchanges work alike, with the pool
+/// as the venue, except that the price impact guard (which measures Soroswap pools) only
+/// applies to direct Soroswap pair swaps. They fail with `DexNotConfigured` while their factory
+/// or router is not set, and with `AllRoutesFailed` when no pool of the pair executes the swap
+/// (`SlippageExceeded` when the pools quote less than the minimum).
+fn swap_from_custody_on(
+    e: &Env,
+    caller: &Address,
+    dex: Dex,
+    path: &Vec<Address>,
+    amount: i128,
+    received: i128,
+    limits: SwapLimits,
+) -> Result<SwapResult, SoroswapError> {
+    if dex == Dex::Soroswap {
+        return swap_from_custody(e, caller, caller, path, amount, received, limits);
+    }
+    let mut venues = venues(e, dex)?;
+    let token_in = &path.first_unchecked();
+    let token_out = &path.last_unchecked();
+    check_tokens_allowed(e, token_in, token_out)?;
+    let oracle_min = oracle_min_out(e, token_in, token_out, received)?;
+    if dex == Dex::SoroswapDirect {
+        check_price_impact(e, path, received)?;
+    }
+
+    if dex == Dex::Best {
+        venues = rank_by_quote(e, &venues, path, received);
+    }
+    let venue_min = venue_min_out(e, limits.min_out, oracle_min);
+    let result = swap_on_venues(e, &venues, path, received, venue_min, limits.deadline)
+        .ok_or_else(|| venue_failure(e, &venues, path, received, venue_min, SoroswapError::AllRoutesFailed))?;
+    settle_swap(e, caller, caller, result, amount, oracle_min, limits.min_out)
+}
+
+/// Books a swap a venue executed: checks the oracle minimum (on the gross output) and the
+/// caller's `min_out` (net of the protocol fee), records volume, receipt and round trip, takes
+/// the protocol fee and publishes the swap events
+fn settle_swap(
+    e: &Env,
+    caller: &Address,
+    recipient: &Address,
+    mut result: SwapResult,
+    amount: i128,
+    oracle_min: Option<i128>,
+    min_out: i128,
+) -> Result<SwapResult, SoroswapError> {
+    let token_in = &result.path.first_unchecked();
+    let token_out = &result.path.last_unchecked();
+    result.amount_in = amount;
+    if oracle_min.is_some_and(|oracle_min| result.amount_out < oracle_min) {
+        return Err(SoroswapError::SlippageExceeded);
+    }
+    record_volume(e, token_in, amount)?;
+    record_volume(e, token_out, result.amount_out)?;
+    result.protocol_fee = take_protocol_fee(e, token_out, result.amount_out);
+    result.amount_out -= result.protocol_fee;
+    if result.amount_out < min_out {
+        return Err(SoroswapError::SlippageExceeded);
+    }
+    record_receipt(e, caller, token_in, token_out, amount, result.amount_out);
+    track_round_trip(e, caller, token_in, token_out);
+
+    SwapRouted {
+        router: result.venue.clone(),
+        caller: caller.clone(),
+        token_in: token_in.clone(),
+        token_out: token_out.clone(),
+        amount_in: amount,
+        amount_out: result.amount_out,
+        path: result.path.clone(),
+    }
+    .publish(e);
+    SwapExecuted {
+        caller: caller.clone(),
+        token_in: token_in.clone(),
+        token_out: token_out.clone(),
+        amount_in: amount,
+        amount_out: result.amount_out,
+        recipient: recipient.clone(),
+        protocol_fee: result.protocol_fee,
+    }
+    .publish(e);
+
+    Ok(result)
+}
+
+/// Hands `amount` of `token`, held by this contract, to `user` as `delivery` requests
+fn deliver(e: &Env, user: &Address, token: &Address, amount: i128, delivery: PushOrPull) {
+    match delivery {
+        PushOrPull::Push => {
+            token::Client::new(e, token).transfer(&e.current_contract_address(), user, &amount)
+        }
+        PushOrPull::Pull => {
+            let claimable = get_claimable(e, user, token) + amount;
+            set_claimable(e, user, token, claimable);
+
+            ClaimableUpdated {
+                user: user.clone(),
+                token: token.clone(),
+                claimable,
+            }
+            .publish(e);
+        }
+    }
+}
+
+/// Builds an auth spec for `contract.fn_name(args)` at `depth`
+fn auth_spec(e: &Env, contract: &Address, fn_name: &str, args: Vec<Val>, depth: u32) -> AuthSpec {
+    AuthSpec {
+        contract: contract.clone(),
+        fn_name: Symbol::new(e, fn_name),
+        args,
+        depth,
+    }
+}
+
+/// The custody transfer every swap makes on the caller's behalf, nested under the root call
+fn custody_transfer_spec(e: &Env, params: &SwapParams) -> AuthSpec {
+    auth_spec(
+        e,
+        &params.token_in,
+        "transfer",
+        (params.caller.clone(), e.current_contract_address(), params.amount).into_val(e),
+        1,
+    )
+}
+
+/// Counts a call of a user-facing entrypoint
+///
+/// A failing call reverts its storage writes, so only completed calls are counted. The
+/// entrypoint is also remembered for `record_failure`.
+fn record_call(e: &Env, entrypoint: &str) {
+    let name = Symbol::new(e, entrypoint);
+    let mut metrics = get_metrics(e, &name).unwrap_or(EntrypointMetrics {
+        calls: 0,
+        last_ledger: 0,
+        failures: 0,
+        last_error: None,
+    });
+    metrics.calls += 1;
+    metrics.last_ledger = e.ledger().sequence();
+    set_metrics(e, &name, &metrics);
+    set_current_entrypoint(e, &name);
+}
+
+/// Counts a failure the entrypoint in progress caught and recovered from
+///
+/// Only failures within a call that still completes can be counted: when the call itself
+/// fails, this write is reverted with the others.
+fn record_failure(e: &Env, error: SoroswapError) {
+    let Some(name) = get_current_entrypoint(e) else {
+        return;
+    };
+    if let Some(mut metrics) = get_metrics(e, &name) {
+        metrics.failures += 1;
+        metrics.last_error = Some(error as u32);
+        set_metrics(e, &name, &metrics);
+    }
+}
+
+/// Reports a tutorial step for `participant`, counting a rejected report as a caught failure
+fn report_step(e: &Env, participant: &Address, step: u32) {
+    if !record_step(e, &get_tutorial(e), participant, step) {
+        record_failure(e, SoroswapError::TutorialRejected);
+    }
+}
+
 #[contract]
 struct SoroswapAuth;
 
 #[contractimpl]
 impl SoroswapAuth {
-    /// Initialize the contract with the Soroswap Router address
+    /// Initialize the contract with the admin and the Soroswap Router address
     ///
-    /// This address is stored and used for all subsequent swap operations
-    pub fn __constructor(e: Env, router_address: Address) {
-        set_soroswap_router_address(&e, router_address);
+    /// The router address is stored and used for all subsequent swap operations. `fallback_routers`
+    /// are tried in order whenever the primary router fails to execute a swap: each router is
+    /// called with `try_` calls, so a paused pair or failing router never aborts the invocation,
+    /// and the `SwapRouted` event reports the one that served the trade. When every venue fails,
+    /// the swap returns `AllRoutesFailed`.
+    pub fn __constructor(e: Env, admin: Address, router_address: Address, fallback_routers: Vec<Address>) {
+        set_admin(&e, admin);
+        configure_router(&e, router_address);
+        set_fallback_routers(&e, fallback_routers);
     }
 
-    /// Execute a token swap via Soroswap Router with explicit authorization context
-    ///
-    /// ## Authorization Flow (Why This is Different from Simple Proxy):
-    /// Unlike the simple proxy pattern, this contract acts as an intermediary that requires
-    /// explicit authorization handling:
-    ///
-    /// 1. User authorizes THIS contract via `caller.require_auth()`
-    /// 2. Contract optionally receives tokens from user (takes custody)
-    /// 3. Contract creates authorization context with `authorize_as_current_contract`
-    /// 4. This context allows the Router to transfer tokens on the user's behalf
-    /// 5. Router executes the swap and sends output tokens to the user
+    /// Propose a new admin (admin only)
     ///
-    /// ## Why `authorize_as_current_contract` is Needed:
-    /// The Soroswap Router will internally call `token.transfer(from=caller, to=pair, amount)`
-    /// to move tokens into the liquidity pair for the swap. However, the caller's original
-    /// signature only authorized calling THIS contract - not the router directly.
-    ///
-    /// We create a `SubContractInvocation` that explicitly authorizes this specific token
-    /// transfer, effectively saying: "I (this contract) am authorized by the caller, and I
-    /// authorize this specific transfer operation during the router call."
-    ///
-    /// Without this authorization context, the router's token transfer would fail because
-    /// the authorization chain would be broken (caller → this contract → ❌ router).
-    ///
-    /// ## Parameters:
-    /// - `caller`: The user executing the swap (must sign the transaction)
-    /// - `token_in`: Token being sold
-    /// - `token_out`: Token being purchased
-    /// - `amount`: Amount of `token_in` to swap
-    ///
-    /// ## Returns:
-    /// Amount of `token_out` received from the swap
-    pub fn swap(e: Env, caller: Address, token_in: Address, token_out: Address, amount: i128) -> Result<i128, SoroswapError> {
-        // Verify the caller has signed this transaction
-        caller.require_auth();
-        check_nonnegative_amount(amount)?;
+    /// Nothing changes until the proposed address calls `accept_admin`, so a mistyped address
+    /// cannot lock the admin out; proposing again replaces the pending proposal.
+    pub fn propose_admin(e: Env, new_admin: Address) {
+        let admin = get_admin(&e);
+        admin.require_auth();
         extend_instance_ttl(&e);
+        set_pending_admin(&e, &new_admin);
 
-        // Transfer tokens from the user to this contract (contract takes custody)
-        // The user's signature authorizes this transfer
-        let token_client = token::Client::new(&e, &token_in);
-        token_client.transfer(&caller, e.current_contract_address(), &amount);
+        AdminProposed {
+            admin,
+            pending_admin: new_admin,
+        }
+        .publish(&e);
+    }
 
-        // Get the stored Soroswap Router address and create client
-        let soroswap_router_address = get_soroswap_router_address(&e);
-        let soroswap_router_client = SoroswapRouterClient::new(&e, &soroswap_router_address);
+    /// Take over as admin (proposed admin only)
+    pub fn accept_admin(e: Env) -> Result<(), SoroswapError> {
+        let pending_admin = get_pending_admin(&e).ok_or(SoroswapError::Unauthorized)?;
+        pending_admin.require_auth();
+        extend_instance_ttl(&e);
 
-        // Get the pair address for this token pair
-        let pair_address = soroswap_router_client.router_pair_for(&token_in, &token_out);
+        let previous_admin = get_admin(&e);
+        set_admin(&e, pending_admin.clone());
+        remove_pending_admin(&e);
 
-        // Build the swap path (direct pair: token_in -> token_out)
-        let mut path: Vec<Address> = Vec::new(&e);
-        path.push_back(token_in.clone());
-        path.push_back(token_out.clone());
+        AdminAccepted {
+            previous_admin,
+            admin: pending_admin,
+        }
+        .publish(&e);
+        Ok(())
+    }
 
-        // Prepare the arguments for the token transfer that will happen inside the router
-        // This represents: token.transfer(from=caller, to=pair, amount=amount)
-        let mut transfer_args: Vec<Val> = vec![&e];
-        transfer_args.push_back(caller.into_val(&e));         // From: original caller
-        transfer_args.push_back(pair_address.into_val(&e));   // To: liquidity pair
-        transfer_args.push_back(amount.into_val(&e));         // Amount to transfer
-
-        // CRITICAL: Create authorization context for the sub-contract invocation
-        // This tells the Soroban runtime: "When the router calls token.transfer() with these
-        // exact arguments, I (the current contract) authorize it on behalf of my caller"
-        //
-        // The SubContractInvocation specifies:
-        // - Which contract will be called (token_in)
-        // - Which function will be invoked ("transfer")
-        // - What arguments will be passed (transfer_args)
-        //
-        // This creates a secure authorization chain: caller → this contract → router → token
-        e.authorize_as_current_contract(vec![
-            &e,
-            InvokerContractAuthEntry::Contract(SubContractInvocation {
-                context: ContractContext {
-                    contract: token_in.clone(),                // The token contract being authorized
-                    fn_name: Symbol::new(&e, "transfer"),      // The function being authorized
-                    args: transfer_args.clone(),               // The exact arguments allowed
-                },
-                sub_invocations: vec![&e],                     // No further nested invocations
-            }),
-        ]);
-
-        // Execute the swap through the router
-        // The authorization context above allows the router to transfer tokens from the caller
-        // to the pair, even though the caller didn't directly authorize the router
-        let swap_result = soroswap_router_client.swap_exact_tokens_for_tokens(
-            &amount,     // Exact amount to swap
-            &0,          // Minimum amount out (0 for simplicity; use slippage calculation in production)
-            &path,       // Swap route
-            &caller,     // Recipient of output tokens (tokens go back to the original caller)
-            &u64::MAX,   // Deadline (max for simplicity; use actual timestamp in production)
-        );
+    /// Current admin
+    pub fn admin(e: Env) -> Address {
+        get_admin(&e)
+    }
 
-        // Return the amount of token_out received
-        let total_swapped_amount = swap_result.last().unwrap();
+    /// Admin proposed by `propose_admin` and not accepted yet
+    pub fn pending_admin(e: Env) -> Option<Address> {
+        get_pending_admin(&e)
+    }
 
-        Ok(total_swapped_amount)
+    /// Apply several configuration changes atomically (admin only)
+    ///
+    /// The operations are applied in order with a single admin signature; if any is invalid the
+    /// whole batch reverts. A single `AdminBatchApplied` event listing the operations replaces
+    /// the events the individual setters would emit.
+    pub fn admin_batch(e: Env, ops: Vec<AdminOp>) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        if ops.is_empty() {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        for op in ops.iter() {
+            apply_admin_op(&e, &op)?;
+        }
+        AdminBatchApplied { ops }.publish(&e);
+        Ok(())
+    }
+
+    /// Publish a new terms of use version (admin only)
+    ///
+    /// Once a non-zero version is set, users must call `accept_terms` with that exact version
+    /// before their next operation. Setting `0` disables the gate.
+    pub fn set_terms_version(e: Env, version: u32) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_terms_version(&e, version);
+        TermsVersionUpdated { version }.publish(&e);
+    }
+
+    /// Acknowledge the current terms of use
+    ///
+    /// The accepted version is recorded in persistent storage as an on-chain acknowledgment record.
+    pub fn accept_terms(e: Env, caller: Address, version: u32) -> Result<(), SoroswapError> {
+        caller.require_auth();
+        extend_instance_ttl(&e);
+        record_call(&e, "accept_terms");
+        if version == 0 || version != get_terms_version(&e) {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        set_accepted_terms(&e, &caller, version);
+        TermsAccepted { user: caller, version }.publish(&e);
+        Ok(())
+    }
+
+    /// Current terms of use version (`0` when no acknowledgment is required)
+    pub fn terms_version(e: Env) -> u32 {
+        get_terms_version(&e)
+    }
+
+    /// Terms of use version last accepted by `user` (`0` if never accepted)
+    pub fn accepted_terms(e: Env, user: Address) -> u32 {
+        get_accepted_terms(&e, &user)
+    }
+
+    /// Replace the enabled features bitmask (admin only)
+    ///
+    /// Clearing a bit disables the matching entrypoint without pausing the rest of the contract.
+    /// Unknown bits are rejected.
+    pub fn set_features(e: Env, features: u32) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        let previous = get_features(&e);
+        apply_admin_op(&e, &AdminOp::Features(features))?;
+        FeaturesUpdated { previous, features }.publish(&e);
+        Ok(())
+    }
+
+    /// Bitmask of the currently enabled features
+    pub fn features(e: Env) -> u32 {
+        get_features(&e)
+    }
+
+    /// Set the protocol fee and its recipient (admin only)
+    ///
+    /// The fee is taken from the output of every router-served swap (from the input of
+    /// `swap_exact_out`) and accrues here per token until `claim_fees`; fees accrued before a
+    /// recipient change go to the new recipient. A zero fee turns it off. Fails with
+    /// `FeeTooHigh` above `MAX_PROTOCOL_FEE`.
+    pub fn set_fee(e: Env, fee: Bps, recipient: Address) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        let config = FeeConfig { fee, recipient: recipient.clone() };
+        apply_admin_op(&e, &AdminOp::Fee(config))?;
+        FeeUpdated { fee, recipient }.publish(&e);
+        Ok(())
+    }
+
+    /// Protocol fee and its recipient, if configured
+    pub fn fee(e: Env) -> Option<FeeConfig> {
+        get_protocol_fee(&e)
+    }
+
+    /// Set the share of the protocol fee credited to the referrer of a `swap` (admin only)
+    pub fn set_referral_share(e: Env, share: Bps) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        apply_admin_op(&e, &AdminOp::ReferralShare(share))
+    }
+
+    /// Share of the protocol fee credited to referrers
+    pub fn referral_share(e: Env) -> Bps {
+        get_referral_share(&e)
+    }
+
+    /// Choose which token list swaps are checked against (admin only)
+    ///
+    /// `Open` (the default) suits any deployment, `Allowlist` curated aggregators and `Denylist`
+    /// open ones blocking known scam assets. A swap whose `token_in` or `token_out` is rejected
+    /// fails with `TokenNotAllowed`; intermediate tokens of a multi-hop path are not checked.
+    pub fn set_token_list_mode(e: Env, mode: TokenListMode) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        apply_admin_op(&e, &AdminOp::TokenListMode(mode))
+    }
+
+    /// Allow `token` as a swap's `token_in` or `token_out` in allowlist mode (admin only)
+    pub fn add_token(e: Env, token: Address) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_token_allowed(&e, &token);
+        TokenAllowlistUpdated { token, allowed: true }.publish(&e);
+    }
+
+    /// Remove `token` from the allowlist (admin only)
+    pub fn remove_token(e: Env, token: Address) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        remove_token_allowed(&e, &token);
+        TokenAllowlistUpdated { token, allowed: false }.publish(&e);
+    }
+
+    /// Block `token` while the denylist is enforced (admin only)
+    pub fn block_token(e: Env, token: Address) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_token_denied(&e, &token);
+        TokenDenylistUpdated { token, blocked: true }.publish(&e);
+    }
+
+    /// Remove `token` from the denylist (admin only)
+    pub fn unblock_token(e: Env, token: Address) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        remove_token_denied(&e, &token);
+        TokenDenylistUpdated { token, blocked: false }.publish(&e);
+    }
+
+    /// Cap (or with `None` uncap) the amount of `token` swapped in a single ledger (admin only)
+    ///
+    /// A blast-radius limiter against exploits: the volume counts both sides of every swap, and
+    /// a swap taking either token above its cap fails with `RateLimited`.
+    pub fn set_volume_cap(e: Env, token: Address, cap: Option<i128>) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        if cap.is_some_and(|cap| cap < 0) {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        extend_instance_ttl(&e);
+        set_volume_cap(&e, &token, cap);
+        VolumeCapUpdated { token, cap }.publish(&e);
+        Ok(())
+    }
+
+    /// Per-ledger volume cap of `token`, if any
+    pub fn volume_cap(e: Env, token: Address) -> Option<i128> {
+        get_volume_cap(&e, &token)
+    }
+
+    /// Amount of `token` swapped so far in the current ledger
+    pub fn ledger_volume(e: Env, token: Address) -> i128 {
+        get_ledger_volume(&e, &token, e.ledger().sequence())
+    }
+
+    /// Token list swaps are checked against
+    pub fn token_list_mode(e: Env) -> TokenListMode {
+        get_token_list_mode(&e)
+    }
+
+    /// Whether `token` is on the allowlist
+    pub fn token_allowed(e: Env, token: Address) -> bool {
+        get_token_allowed(&e, &token)
+    }
+
+    /// Whether `token` is on the denylist
+    pub fn token_blocked(e: Env, token: Address) -> bool {
+        get_token_denied(&e, &token)
+    }
+
+    /// Bound router-served swaps by a Reflector price feed (admin only)
+    ///
+    /// Swaps must return at least the oracle value of their input, less `max_deviation`; the
+    /// router gets the higher of that and the caller's own minimum. Prices older than `max_age`
+    /// seconds are ignored, and `fallback` is used when `oracle` has none. Without a fresh price
+    /// the swap fails with `OracleStale` instead of executing unprotected.
+    pub fn set_oracle(
+        e: Env,
+        oracle: Address,
+        fallback: Option<Address>,
+        max_deviation: Bps,
+        max_age: u64,
+    ) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        let config = OracleConfig {
+            oracle: oracle.clone(),
+            fallback: fallback.clone(),
+            max_deviation,
+            max_age,
+        };
+        apply_admin_op(&e, &AdminOp::Oracle(config))?;
+        OracleUpdated {
+            oracle: Some(oracle),
+            fallback,
+            max_deviation,
+            max_age,
+        }
+        .publish(&e);
+        Ok(())
+    }
+
+    /// Stop checking swaps against the price oracle (admin only)
+    pub fn remove_oracle(e: Env) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        remove_oracle(&e);
+        OracleUpdated {
+            oracle: None,
+            fallback: None,
+            max_deviation: Bps::ZERO,
+            max_age: 0,
+        }
+        .publish(&e);
+    }
+
+    /// Price oracle bounding swap outputs, if configured
+    pub fn oracle(e: Env) -> Option<OracleConfig> {
+        get_oracle(&e)
+    }
+
+    /// Least `token_out` the oracle guard accepts for swapping `amount` of `token_in`
+    ///
+    /// `None` while no oracle is configured; fails with `OracleStale` like the swap would.
+    pub fn oracle_min_out(
+        e: Env,
+        token_in: Address,
+        token_out: Address,
+        amount: i128,
+    ) -> Result<Option<i128>, SoroswapError> {
+        oracle_min_out(&e, &token_in, &token_out, amount)
+    }
+
+    /// Set the largest price impact a router-served swap may have (admin only)
+    ///
+    /// The router quote is compared with the spot output at the current reserves, and a swap
+    /// quoted more than `max_impact` below spot fails with `PriceImpactTooHigh` before anything
+    /// moves. The impact includes the pairs' 0.3% fee per hop, so thresholds should leave room
+    /// for it. `Bps::MAX` effectively turns the guard off.
+    pub fn set_max_price_impact(e: Env, max_impact: Bps) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        apply_admin_op(&e, &AdminOp::MaxPriceImpact(max_impact))?;
+        MaxPriceImpactUpdated { max_impact }.publish(&e);
+        Ok(())
+    }
+
+    /// Largest price impact a swap may have, if limited
+    pub fn max_price_impact(e: Env) -> Option<Bps> {
+        get_max_price_impact(&e)
+    }
+
+    /// Price impact of swapping `amount` along `path` on the primary router, if it can be quoted
+    pub fn price_impact(e: Env, path: Vec<Address>, amount: i128) -> Result<Option<Bps>, SoroswapError> {
+        check_nonnegative_amount(amount)?;
+        if !(2..=MAX_PATH_LENGTH).contains(&path.len()) {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        Ok(price_impact(&e, &get_soroswap_router_address(&e), &path, amount))
+    }
+
+    /// Block every swap entrypoint until `unpause` (admin only)
+    ///
+    /// Claimable balances stay withdrawable, so users can still exit while the contract is paused.
+    pub fn pause(e: Env) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        apply_admin_op(&e, &AdminOp::Paused(true))?;
+        PauseUpdated { paused: true }.publish(&e);
+        Ok(())
+    }
+
+    /// Re-enable the swap entrypoints after `pause` (admin only)
+    pub fn unpause(e: Env) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        apply_admin_op(&e, &AdminOp::Paused(false))?;
+        PauseUpdated { paused: false }.publish(&e);
+        Ok(())
+    }
+
+    /// Whether the admin paused the contract
+    pub fn paused(e: Env) -> bool {
+        get_paused(&e)
+    }
+
+    /// Turn receipt persistence on or off (admin only)
+    pub fn set_receipts_enabled(e: Env, enabled: bool) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_receipts_enabled(&e, enabled);
+    }
+
+    /// Set the ledger window within which swapping back counts as a round trip (admin only)
+    pub fn set_round_trip_window(e: Env, ledgers: u32) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        apply_admin_op(&e, &AdminOp::RoundTripWindow(ledgers))
+    }
+
+    /// Turn tutorial mode on by setting the `tutorial` contract, or off with `None` (admin only)
+    ///
+    /// While on, `swap` and `swap_standing` report the workshop steps their users complete.
+    pub fn set_tutorial(e: Env, tutorial: Option<Address>) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        apply_admin_op(&e, &AdminOp::Tutorial(tutorial))
+    }
+
+    /// Set the Phoenix factory whose pools `swap_on` can use, or with `None` disable Phoenix
+    /// (admin only)
+    pub fn set_phoenix_factory(e: Env, factory: Option<Address>) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        apply_admin_op(&e, &AdminOp::PhoenixFactory(factory))
+    }
+
+    /// Phoenix factory whose pools `swap_on` can use, if configured
+    pub fn phoenix_factory(e: Env) -> Option<Address> {
+        get_phoenix_factory(&e)
+    }
+
+    /// Set the Aquarius router whose pools `swap_on` can use, or with `None` disable Aquarius
+    /// (admin only)
+    ///
+    /// Aquarius pools take no deadline, so the caller's deadline is only checked before the
+    /// swap, which executes in the submitted ledger.
+    pub fn set_aqua_router(e: Env, router: Option<Address>) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        apply_admin_op(&e, &AdminOp::AquaRouter(router))
+    }
+
+    /// Aquarius router whose pools `swap_on` can use, if configured
+    pub fn aqua_router(e: Env) -> Option<Address> {
+        get_aqua_router(&e)
+    }
+
+    /// Set the exchanges tried, in order, when every Soroswap router fails a direct-pair swap
+    /// (admin only)
+    ///
+    /// Only Phoenix and Aquarius can be fallbacks; an exchange whose factory or router is not
+    /// configured is skipped.
+    pub fn set_fallback_dexes(e: Env, dexes: Vec<Dex>) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        apply_admin_op(&e, &AdminOp::FallbackDexes(dexes))
+    }
+
+    /// Exchanges tried when every Soroswap router fails a direct-pair swap
+    pub fn fallback_dexes(e: Env) -> Vec<Dex> {
+        get_fallback_dexes(&e)
+    }
+
+    /// Register the preferred route of swaps from `token_in` to `token_out` (admin only)
+    ///
+    /// Swaps of the pair that supply no path use `path` from then on. `path` must start at
+    /// `token_in`, end at `token_out` and have at most `MAX_PATH_LENGTH` tokens.
+    pub fn set_route(e: Env, token_in: Address, token_out: Address, path: Vec<Address>) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        check_path(&path, &token_in, &token_out)?;
+        extend_instance_ttl(&e);
+        set_route(&e, &token_in, &token_out, &path);
+        RouteUpdated {
+            token_in,
+            token_out,
+            path: Some(path),
+        }
+        .publish(&e);
+        Ok(())
+    }
+
+    /// Remove the preferred route from `token_in` to `token_out`, restoring the direct pair
+    /// (admin only)
+    pub fn clear_route(e: Env, token_in: Address, token_out: Address) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        remove_route(&e, &token_in, &token_out);
+        RouteUpdated {
+            token_in,
+            token_out,
+            path: None,
+        }
+        .publish(&e);
+    }
+
+    /// Preferred route from `token_in` to `token_out`, if registered
+    pub fn route(e: Env, token_in: Address, token_out: Address) -> Option<Vec<Address>> {
+        get_route(&e, &token_in, &token_out)
+    }
+
+    /// Drop the cached pair address of a token pair for `router` (admin only)
+    ///
+    /// Pair addresses are resolved once per router and token pair and then kept in persistent
+    /// storage, saving a cross-contract call per hop. The next swap over the pair asks the router
+    /// again. The order of `token_a` and `token_b` does not matter.
+    pub fn invalidate_pair(e: Env, router: Address, token_a: Address, token_b: Address) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        let (token_a, token_b) = sorted_pair(&token_a, &token_b);
+        remove_pair_address(&e, &router, token_a, token_b);
+    }
+
+    /// Cached pair address of a token pair for `router`, if it has been resolved
+    pub fn cached_pair(e: Env, router: Address, token_a: Address, token_b: Address) -> Option<Address> {
+        let (token_a, token_b) = sorted_pair(&token_a, &token_b);
+        get_pair_address(&e, &router, token_a, token_b)
+    }
+
+    /// Set how many venues a `Dex::Best` swap quotes at most, bounding its cost (admin only)
+    ///
+    /// Each quote is a cross-contract call and contracts cannot read their remaining instruction
+    /// budget, so this caps the venues quoted, in the order Soroswap, Phoenix, Aquarius.
+    pub fn set_quote_budget(e: Env, venues: u32) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        apply_admin_op(&e, &AdminOp::QuoteBudget(venues))
+    }
+
+    /// Most venues a `Dex::Best` swap quotes, `None` for every configured venue
+    pub fn quote_budget(e: Env) -> Option<u32> {
+        get_quote_budget(&e)
+    }
+
+    /// Tutorial contract recording workshop steps, if tutorial mode is on
+    pub fn tutorial(e: Env) -> Option<Address> {
+        get_tutorial(&e)
+    }
+
+    /// Ledger window within which swapping back counts as a round trip
+    pub fn round_trip_window(e: Env) -> u32 {
+        get_round_trip_window(&e)
+    }
+
+    /// Round trips (a swap, then one back within the window) of a user on a pair
+    ///
+    /// The order of `token_a` and `token_b` does not matter. Counters live in temporary storage
+    /// and reset about 30 days after the last round trip.
+    pub fn round_trips(e: Env, user: Address, token_a: Address, token_b: Address) -> u32 {
+        let (token_a, token_b) = sorted_pair(&token_a, &token_b);
+        get_round_trips(&e, &user, token_a, token_b)
+    }
+
+    /// Execution quality of the pair over its last 32 direct swaps through this contract
+    ///
+    /// Compares what arrived with what the router reported for the swap. The order of `token_a`
+    /// and `token_b` does not matter; both directions share one window. Routing logic can
+    /// down-weight pairs that consistently realize less than quoted.
+    pub fn pair_quality(e: Env, token_a: Address, token_b: Address) -> PairQuality {
+        let (token_a, token_b) = sorted_pair(&token_a, &token_b);
+        let deltas = get_pair_executions(&e, token_a, token_b);
+
+        let mut quality = PairQuality {
+            samples: deltas.len(),
+            negative_samples: 0,
+            mean_delta_bps: 0,
+            worst_delta_bps: deltas.first().unwrap_or(0),
+        };
+        let mut total: i64 = 0;
+        for delta in deltas.iter() {
+            total += delta as i64;
+            if delta < 0 {
+                quality.negative_samples += 1;
+            }
+            quality.worst_delta_bps = quality.worst_delta_bps.min(delta);
+        }
+        if quality.samples > 0 {
+            quality.mean_delta_bps = (total / quality.samples as i64) as i32;
+        }
+        quality
+    }
+
+    /// Retrieve a stored receipt by id
+    pub fn get_receipt(e: Env, id: u64) -> Option<Receipt> {
+        get_receipt(&e, id)
+    }
+
+    /// List the receipt ids recorded for `user`, oldest first
+    ///
+    /// Returns at most `limit` ids starting at position `start` of the user's receipt index.
+    pub fn user_receipts(e: Env, user: Address, start: u32, limit: u32) -> Vec<u64> {
+        let end = get_user_receipt_count(&e, &user).min(start.saturating_add(limit));
+        let mut ids: Vec<u64> = Vec::new(&e);
+        for index in start..end {
+            if let Some(id) = get_user_receipt(&e, &user, index) {
+                ids.push_back(id);
+            }
+        }
+        ids
+    }
+
+    /// Number of receipts recorded for `user`, the length of their receipt index
+    pub fn user_receipt_count(e: Env, user: Address) -> u32 {
+        get_user_receipt_count(&e, &user)
+    }
+
+    /// Page through a user's receipts, newest first
+    ///
+    /// Pass `cursor = None` for the first page, then the returned `next_cursor` for each
+    /// following one until it comes back as `None`. At most `limit` receipts (capped at 50) are
+    /// returned per page. Meant for activity feeds rendered straight from simulation calls.
+    pub fn history(e: Env, user: Address, cursor: Option<u32>, limit: u32) -> HistoryPage {
+        let count = get_user_receipt_count(&e, &user);
+        // Positions at or above `end` are skipped: either newer than the cursor or not recorded
+        let end = match cursor {
+            Some(position) => position.saturating_add(1).min(count),
+            None => count,
+        };
+        let start = end.saturating_sub(limit.min(MAX_PAGE_SIZE));
+
+        let mut receipts: Vec<Receipt> = Vec::new(&e);
+        for index in (start..end).rev() {
+            if let Some(receipt) = get_user_receipt(&e, &user, index).and_then(|id| get_receipt(&e, id)) {
+                receipts.push_back(receipt);
+            }
+        }
+
+        HistoryPage {
+            receipts,
+            next_cursor: if start > 0 { Some(start - 1) } else { None },
+        }
+    }
+
+    /// Usage counters of a user-facing entrypoint, `None` before its first completed call
+    ///
+    /// Covers `accept_terms`, `approve_standing` and the swaps. Only completed calls are
+    /// counted, together with the failures they caught and recovered from (venues that failed
+    /// before another served the swap, rejected tutorial reports). A failing call reverts every
+    /// storage write it made, counters included, so failures that revert the call remain
+    /// visible only in the transaction results.
+    pub fn metrics(e: Env, entrypoint: Symbol) -> Option<EntrypointMetrics> {
+        get_metrics(&e, &entrypoint)
+    }
+
+    /// Whether the primary router still runs the code it was configured with
+    ///
+    /// The router's Wasm hash is recorded whenever it is configured. `false` means its instance
+    /// disappeared or its code was upgraded, so swaps skip it (its interface may have changed)
+    /// until the admin configures the router again; a swap no other router serves then fails
+    /// with `RouterUpgraded`.
+    pub fn router_current(e: Env) -> bool {
+        primary_router_current(&e)
+    }
+
+    /// Estimate the persistent entries and bytes an operation creates for a first-time user
+    ///
+    /// `op` is the entrypoint name: `accept_terms`, `approve_standing`, `approve_operator`, or one
+    /// of `SWAP_ENTRYPOINTS`. A swap is estimated at its most expensive: a pair's first swap
+    /// through this contract caches the pair address and opens its execution window, the first
+    /// protocol fee in a token opens its accrual, and receipts (while enabled) add a receipt, a
+    /// user index and, for the user's first receipt, a count entry. Operator swaps (`swap_for`,
+    /// `swap_standing`) also open the user's daily spend window for the token, and a signed
+    /// order (`execute_order`) marks its nonce used. A batch creates the entries of each of its
+    /// swaps. Pull delivery also creates a claimable balance entry per output token on first use,
+    /// and a referred swap a referral fee entry per referrer and token, which together open the
+    /// token's owed total. Other state (token balances, allowances) lives in the token contracts.
+    pub fn estimate_rent(e: Env, op: Symbol) -> Result<RentEstimate, SoroswapError> {
+        let (new_entries, approx_bytes) = if op == Symbol::new(&e, "accept_terms") {
+            (1, accepted_terms_entry_size(&e))
+        } else if op == Symbol::new(&e, "approve_standing") {
+            (1, standing_approval_entry_size(&e))
+        } else if op == Symbol::new(&e, "approve_operator") {
+            (1, operator_allowance_entry_size(&e))
+        } else if SWAP_ENTRYPOINTS.iter().any(|name| op == Symbol::new(&e, name)) {
+            let mut new_entries = 2;
+            let mut approx_bytes = pair_entries_size(&e);
+            if get_protocol_fee(&e).is_some() {
+                new_entries += 1;
+                approx_bytes += accrued_fees_entry_size(&e);
+            }
+            if get_receipts_enabled(&e) {
+                new_entries += 3;
+                approx_bytes += receipt_entries_size(&e);
+            }
+            if op == Symbol::new(&e, "swap_for") || op == Symbol::new(&e, "swap_standing") {
+                new_entries += 1;
+                approx_bytes += daily_spend_entry_size(&e);
+            }
+            if op == Symbol::new(&e, "execute_order") {
+                new_entries += 1;
+                approx_bytes += order_nonce_entry_size(&e);
+            }
+            (new_entries, approx_bytes)
+        } else {
+            return Err(SoroswapError::InvalidArgument);
+        };
+
+        Ok(RentEstimate {
+            new_entries,
+            approx_bytes,
+        })
+    }
+
+    /// List Soroswap pairs registered in the factory behind the configured router
+    ///
+    /// Returns at most `limit` pair addresses (capped at 50) starting at index `start`, so the
+    /// frontend can page through every market with this contract alone.
+    pub fn all_pairs(e: Env, start: u32, limit: u32) -> Vec<Address> {
+        let soroswap_router_client = SoroswapRouterClient::new(&e, &get_soroswap_router_address(&e));
+        let factory_client = SoroswapFactoryClient::new(&e, &soroswap_router_client.get_factory());
+
+        let end = factory_client
+            .all_pairs_length()
+            .min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+        let mut pairs: Vec<Address> = Vec::new(&e);
+        for index in start..end {
+            pairs.push_back(factory_client.all_pairs(&index));
+        }
+        pairs
+    }
+
+    /// Tokens and current reserves of a Soroswap pair
+    pub fn pair_reserves(e: Env, pair: Address) -> PairReserves {
+        let pair_client = SoroswapPairClient::new(&e, &pair);
+        let (reserve_0, reserve_1) = pair_client.get_reserves();
+        PairReserves {
+            token_0: pair_client.token_0(),
+            token_1: pair_client.token_1(),
+            pair,
+            reserve_0,
+            reserve_1,
+        }
+    }
+
+    /// Expected `token_out` for swapping `amount` of `token_in` over the default route
+    ///
+    /// Read-only: nothing is transferred. Uses the same routers, in the same order, as `swap`
+    /// and deducts the protocol fee, so frontends can simulate the exact output of a swap.
+    pub fn quote(e: Env, token_in: Address, token_out: Address, amount: i128) -> Result<i128, SoroswapError> {
+        check_nonnegative_amount(amount)?;
+        quote_path(&e, &default_path(&e, &token_in, &token_out), amount)
+    }
+
+    /// Expected output for swapping `amount` of the first token of `path` along it
+    ///
+    /// Multi-hop variant of `quote`; `path` follows the same rules as the `path` of `swap`.
+    pub fn quote_path(e: Env, path: Vec<Address>, amount: i128) -> Result<i128, SoroswapError> {
+        check_nonnegative_amount(amount)?;
+        if path.len() < 2 {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        let path = resolve_path(&e, &path.first_unchecked(), &path.last_unchecked(), Some(path))?;
+        quote_path(&e, &path, amount)
+    }
+
+    /// Preview a swap: expected output, fees, price impact, route and pairs
+    ///
+    /// Read-only, like `quote`, and built from the same router selection, fee and price-impact
+    /// logic the swap itself runs, so wallets can show a complete preview. `path` is optional as
+    /// in `swap`.
+    pub fn simulate_swap(
+        e: Env,
+        token_in: Address,
+        token_out: Address,
+        amount: i128,
+        path: Option<Vec<Address>>,
+    ) -> Result<SwapSimulation, SoroswapError> {
+        check_nonnegative_amount(amount)?;
+        let path = resolve_path(&e, &token_in, &token_out, path)?;
+        let (router, amounts) = quote_on_routers(&e, &path, amount)?;
+        let first_pair = soroswap_adapter::pair_for(&e, &router, &token_in, &path.get_unchecked(1))
+            .ok_or(SoroswapError::SwapFailed)?;
+        let (pairs, fees) = soroswap_adapter::hop_pairs_and_fees(&e, &router, &path, first_pair, &amounts)
+            .ok_or(SoroswapError::SwapFailed)?;
+
+        let gross = amounts.last().unwrap_or(0);
+        let protocol_fee = protocol_fee_on(&e, gross);
+        Ok(SwapSimulation {
+            price_impact: price_impact(&e, &router, &path, amount).map(Bps::value),
+            router,
+            path,
+            pairs,
+            amount_in: amount,
+            amount_out: gross - protocol_fee,
+            fees,
+            protocol_fee,
+        })
+    }
+
+    /// Input amounts required along `path` to receive `amount_out` of the last token
+    ///
+    /// Thin validated wrapper over the router's `router_get_amounts_in`: the path needs at least
+    /// two tokens and `amount_out` must be positive.
+    pub fn amounts_in(e: Env, path: Vec<Address>, amount_out: i128) -> Result<Vec<i128>, SoroswapError> {
+        check_nonnegative_amount(amount_out)?;
+        if amount_out == 0 || path.len() < 2 {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        let soroswap_router_client = SoroswapRouterClient::new(&e, &get_soroswap_router_address(&e));
+        Ok(soroswap_router_client.router_get_amounts_in(&amount_out, &path))
+    }
+
+    /// Whether the factory behind the configured router has a pair for `token_a` / `token_b`
+    pub fn pair_exists(e: Env, token_a: Address, token_b: Address) -> Result<bool, SoroswapError> {
+        if token_a == token_b {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        let soroswap_router_client = SoroswapRouterClient::new(&e, &get_soroswap_router_address(&e));
+        let factory_client = SoroswapFactoryClient::new(&e, &soroswap_router_client.get_factory());
+        Ok(factory_client.pair_exists(&token_a, &token_b))
+    }
+
+    /// Execute a token swap via Soroswap Router with explicit authorization context
+    ///
+    /// ## Authorization Flow (Why This is Different from Simple Proxy):
+    /// Unlike the simple proxy pattern, this contract acts as an intermediary that requires
+    /// explicit authorization handling:
+    ///
+    /// 1. User authorizes THIS contract via `caller.require_auth()`
+    /// 2. Contract receives tokens from user (takes custody)
+    /// 3. Contract creates authorization context with `authorize_as_current_contract`
+    /// 4. This context allows the Router to transfer tokens out of this contract
+    /// 5. Router executes the swap and sends output tokens to this contract
+    /// 6. Contract forwards the output tokens to the user
+    ///
+    /// ## Why `authorize_as_current_contract` is Needed:
+    /// The Soroswap Router will internally call `token.transfer(from=this contract, to=pair, amount)`
+    /// to move tokens into the liquidity pair for the swap. This contract only calls the router
+    /// directly - the token transfer is a nested call, so it is not implicitly authorized.
+    ///
+    /// We create a `SubContractInvocation` that explicitly authorizes this specific token
+    /// transfer, effectively saying: "I (this contract) authorize this specific transfer
+    /// operation during the router call."
+    ///
+    /// Without this authorization context, the router's token transfer would fail because
+    /// the authorization chain would be broken (caller → this contract → ❌ router → token).
+    ///
+    /// ## Router Fallback:
+    /// The primary router is tried first, then each fallback router in order, then (for direct
+    /// pairs) each fallback exchange set with `set_fallback_dexes`. The first venue that
+    /// succeeds serves the trade and is reported in a `SwapRouted` event. If every venue fails,
+    /// the whole invocation reverts with `SlippageExceeded` when their best quote is below the
+    /// minimum, and otherwise with `AllRoutesFailed`, or `RouterUpgraded` when the primary
+    /// router was skipped because its code changed since it was configured.
+    ///
+    /// ## Parameters:
+    /// - `caller`: The user executing the swap (must sign the transaction)
+    /// - `token_in`: Token being sold
+    /// - `token_out`: Token being purchased
+    /// - `amount`: Amount of `token_in` to swap
+    /// - `amount_out_min`: Least `token_out` the caller accepts, net of the protocol fee. It is
+    ///   passed on to each router as its minimum (grossed up by the fee), so a router that
+    ///   would deliver less reverts and the next one is tried; if none delivers it the swap
+    ///   fails. This is what keeps sandwich attacks bounded
+    /// - `deadline`: Optional latest ledger timestamp at which the swap may execute
+    /// - `deadline_delta`: Optional swap deadline, in ledgers from now. At least one of the two
+    ///   deadlines is required, here and on every other swap
+    /// - `path`: Optional multi-hop route from `token_in` to `token_out`, for pairs without a
+    ///   direct pool; at most `MAX_PATH_LENGTH` tokens. Defaults to the pair's default route
+    /// - `referrer`: Optional integrator or frontend that brought the swap. It is credited
+    ///   `referral_share` of the protocol fee, claimed with `claim_referral_fees`; the caller
+    ///   cannot refer themselves
+    ///
+    /// ## Returns:
+    /// `SwapResult` with the amounts in and out, the path, the pair of each hop, the
+    /// liquidity-provider fee of each hop, the protocol fee and the router that served it
+    pub fn swap(
+        e: Env,
+        caller: Address,
+        token_in: Address,
+        token_out: Address,
+        amount: i128,
+        amount_out_min: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+        path: Option<Vec<Address>>,
+        referrer: Option<Address>,
+    ) -> Result<SwapResult, SoroswapError> {
+        // Verify the caller has signed this transaction
+        caller.require_auth();
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_SWAP)?;
+        check_nonnegative_amount(amount)?;
+        check_nonnegative_amount(amount_out_min)?;
+        if referrer.as_ref() == Some(&caller) {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        check_terms_accepted(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        let path = resolve_path(&e, &token_in, &token_out, path)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "swap");
+
+        let limits = SwapLimits { min_out: amount_out_min, deadline };
+        let result = swap_into_custody(&e, &caller, &caller, &path, amount, limits)?;
+        if let Some(referrer) = referrer {
+            credit_referrer(&e, &referrer, &token_out, result.protocol_fee);
+        }
+
+        // Forward the output tokens from custody back to the original caller
+        token::Client::new(&e, &token_out).transfer(
+            &e.current_contract_address(),
+            &caller,
+            &result.amount_out,
+        );
+
+        let step = if path.len() > 2 { STEP_PATH_SWAP } else { STEP_SIMPLE_SWAP };
+        report_step(&e, &caller, step);
+
+        // Return what the swap did: amounts, route, pairs and fees
+        Ok(result)
+    }
+
+    /// Swap over the default route on the exchange selected by `dex`
+    ///
+    /// `Dex::Soroswap` works like `swap` without a path, and `Dex::SoroswapDirect` swaps with
+    /// the Soroswap pair itself, skipping the router. `Dex::Phoenix` swaps through the pool
+    /// the configured Phoenix factory (`set_phoenix_factory`) lists for the pair, and `Dex::Aqua`
+    /// through the best-quoting constant-product pool the configured Aquarius router
+    /// (`set_aqua_router`) lists. Both get the same token-list, oracle, volume and protocol-fee
+    /// handling, and fail with `DexNotConfigured` until configured. `Dex::Best` quotes every
+    /// configured exchange (up to `quote_budget` of them) and executes on the best quote,
+    /// falling back to the next best should it fail. The result's `venue` and the `SwapRouted`
+    /// event report the router or pool that served the trade.
+    ///
+    /// Preferred routes (`set_route`) only apply to `Dex::Soroswap` and `Dex::Best`; the other
+    /// exchanges always swap over the direct pair.
+    ///
+    /// ## Parameters:
+    /// - `caller`: The user executing the swap (must sign the transaction)
+    /// - `token_in`: Token being sold
+    /// - `token_out`: Token being purchased
+    /// - `amount`: Amount of `token_in` to swap
+    /// - `amount_out_min`: Least `token_out` the caller accepts, net of the protocol fee
+    /// - `deadline`: Optional latest ledger timestamp at which the swap may execute
+    /// - `deadline_delta`: Optional swap deadline, in ledgers from now
+    /// - `dex`: Exchange executing the swap
+    ///
+    /// ## Returns:
+    /// `SwapResult` of the swap, as for `swap`
+    pub fn swap_on(
+        e: Env,
+        caller: Address,
+        token_in: Address,
+        token_out: Address,
+        amount: i128,
+        amount_out_min: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+        dex: Dex,
+    ) -> Result<SwapResult, SoroswapError> {
+        caller.require_auth();
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_SWAP)?;
+        check_nonnegative_amount(amount)?;
+        check_nonnegative_amount(amount_out_min)?;
+        check_terms_accepted(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "swap_on");
+
+        let path = default_path_on(&e, dex, &token_in, &token_out);
+        let received = take_custody(&e, &token_in, &caller, amount);
+        let limits = SwapLimits { min_out: amount_out_min, deadline };
+        let result = swap_from_custody_on(&e, &caller, dex, &path, amount, received, limits)?;
+        token::Client::new(&e, &token_out).transfer(&e.current_contract_address(), &caller, &result.amount_out);
+        report_step(&e, &caller, STEP_SIMPLE_SWAP);
+
+        Ok(result)
+    }
+
+    /// Split one swap into legs executed on different venues, with a combined minimum output
+    ///
+    /// Spreading a large order over several pools moves each of them less than a single swap
+    /// would. The input is taken into custody once and divided by the legs' weights, which must
+    /// add up to exactly 10_000; the last leg also swaps the rounding remainder. Each leg then
+    /// swaps like `swap_on` on its `dex`, along its own path: multi-hop paths are served by
+    /// Soroswap only, so a multi-hop leg on another exchange fails with `AllRoutesFailed`. Fails
+    /// with `SlippageExceeded` when the legs together deliver less than `min_out`. The output is
+    /// transferred to the caller in one transfer.
+    ///
+    /// ## Parameters:
+    /// - `params`: Caller (must sign the transaction), tokens, amount of `token_in` and optional
+    ///   deadline, as for `swap`
+    /// - `legs`: The legs, at most `MAX_SPLIT_LEGS`
+    /// - `min_out`: Least combined output, net of protocol fees
+    ///
+    /// ## Returns:
+    /// `SplitSwapResult` with the combined output and the `SwapResult` of each leg
+    pub fn swap_split(
+        e: Env,
+        params: SwapParams,
+        legs: Vec<SplitLeg>,
+        min_out: i128,
+    ) -> Result<SplitSwapResult, SoroswapError> {
+        let SwapParams { caller, token_in, token_out, amount, deadline, deadline_delta } = params;
+        caller.require_auth();
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_SWAP)?;
+        check_nonnegative_amount(amount)?;
+        check_nonnegative_amount(min_out)?;
+        let mut total_weight: u32 = 0;
+        for leg in legs.iter() {
+            total_weight = total_weight.saturating_add(leg.weight.value());
+        }
+        if legs.is_empty() || legs.len() > MAX_SPLIT_LEGS || total_weight != BPS_DENOMINATOR {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        check_terms_accepted(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "swap_split");
+
+        let received = take_custody(&e, &token_in, &caller, amount);
+        let mut results: Vec<SwapResult> = Vec::new(&e);
+        let mut amount_out = 0;
+        let (mut amount_left, mut received_left) = (amount, received);
+        for (index, leg) in legs.iter().enumerate() {
+            let path = match leg.path {
+                Some(path) => resolve_path(&e, &token_in, &token_out, Some(path))?,
+                None => default_path_on(&e, leg.dex, &token_in, &token_out),
+            };
+            // The last leg also takes the rounding remainder
+            let (leg_amount, leg_received) = if index as u32 == legs.len() - 1 {
+                (amount_left, received_left)
+            } else {
+                (leg.weight.apply(amount), leg.weight.apply(received))
+            };
+            amount_left -= leg_amount;
+            received_left -= leg_received;
+
+            let limits = SwapLimits { min_out: 0, deadline };
+            let result = swap_from_custody_on(&e, &caller, leg.dex, &path, leg_amount, leg_received, limits)?;
+            amount_out += result.amount_out;
+            results.push_back(result);
+        }
+        if amount_out < min_out {
+            return Err(SoroswapError::SlippageExceeded);
+        }
+
+        token::Client::new(&e, &token_out).transfer(&e.current_contract_address(), &caller, &amount_out);
+        report_step(&e, &caller, STEP_SIMPLE_SWAP);
+
+        Ok(SplitSwapResult { amount_out, legs: results })
+    }
+
+    /// Execute several independent direct-pair swaps atomically
+    ///
+    /// Each request is swapped like `swap`, in order, with its own custody transfer and router
+    /// authorization and minimum output; if any of them fails the whole batch reverts. Saves the fees and round
+    /// trips of separate transactions when rebalancing several tokens.
+    ///
+    /// ## Parameters:
+    /// - `caller`: The user executing the swaps (must sign the transaction)
+    /// - `requests`: The swaps, at most 8
+    /// - `deadline`: Optional latest ledger timestamp at which the swaps may execute
+    /// - `deadline_delta`: Optional deadline shared by every swap, in ledgers from now
+    /// - `delivery`: Whether the outputs are transferred right away or credited as claimable
+    ///
+    /// ## Returns:
+    /// Amount of `token_out` received by each request, in request order
+    pub fn swap_batch(
+        e: Env,
+        caller: Address,
+        requests: Vec<SwapRequest>,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+        delivery: PushOrPull,
+    ) -> Result<Vec<i128>, SoroswapError> {
+        caller.require_auth();
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_SWAP)?;
+        if requests.is_empty() || requests.len() > MAX_BATCH_SWAPS {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        check_terms_accepted(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "swap_batch");
+
+        let mut outputs: Vec<i128> = Vec::new(&e);
+        for request in requests.iter() {
+            check_nonnegative_amount(request.amount)?;
+            check_nonnegative_amount(request.amount_out_min)?;
+            let path = default_path(&e, &request.token_in, &request.token_out);
+            let limits = SwapLimits { min_out: request.amount_out_min, deadline };
+            let total_swapped_amount = swap_into_custody(&e, &caller, &caller, &path, request.amount, limits)?.amount_out;
+            deliver(&e, &caller, &request.token_out, total_swapped_amount, delivery);
+            outputs.push_back(total_swapped_amount);
+        }
+        Ok(outputs)
+    }
+
+    /// Swap and deliver the output to `to` instead of the caller
+    ///
+    /// Works like `swap` over the default route; pays a third party in a different token in one
+    /// transaction. An `OutputDistributed` event records both the caller and the recipient.
+    ///
+    /// ## Parameters:
+    /// - `caller`: The user paying `token_in` (must sign the transaction)
+    /// - `to`: Recipient of `token_out`; cannot be this contract
+    /// - `token_in`: Token being sold
+    /// - `token_out`: Token being purchased
+    /// - `amount`: Amount of `token_in` to swap
+    /// - `amount_out_min`: Least `token_out` delivered to `to`, net of the protocol fee
+    /// - `deadline`: Optional latest ledger timestamp at which the swap may execute
+    /// - `deadline_delta`: Optional swap deadline, in ledgers from now
+    ///
+    /// ## Returns:
+    /// Amount of `token_out` delivered to `to`
+    pub fn swap_to(
+        e: Env,
+        caller: Address,
+        to: Address,
+        token_in: Address,
+        token_out: Address,
+        amount: i128,
+        amount_out_min: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+    ) -> Result<i128, SoroswapError> {
+        caller.require_auth();
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_SWAP)?;
+        check_nonnegative_amount(amount)?;
+        check_nonnegative_amount(amount_out_min)?;
+        let this = e.current_contract_address();
+        if to == this {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        check_terms_accepted(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "swap_to");
+
+        let path = default_path(&e, &token_in, &token_out);
+        let limits = SwapLimits { min_out: amount_out_min, deadline };
+        let total_swapped_amount = swap_into_custody(&e, &caller, &to, &path, amount, limits)?.amount_out;
+        token::Client::new(&e, &token_out).transfer(&this, &to, &total_swapped_amount);
+
+        OutputDistributed {
+            caller,
+            recipient: to,
+            token_out,
+            amount: total_swapped_amount,
+        }
+        .publish(&e);
+        Ok(total_swapped_amount)
+    }
+
+    /// Swap the caller's whole `token_in` balance, less `dust`
+    ///
+    /// Works like `swap` over the default route, with the amount read from the token contract at
+    /// execution time so frontends don't have to pre-fetch it and race incoming transfers.
+    /// Fails with `InvalidArgument` when nothing is left to swap.
+    ///
+    /// ## Parameters:
+    /// - `caller`: The user executing the swap (must sign the transaction)
+    /// - `token_in`: Token being sold
+    /// - `token_out`: Token being purchased
+    /// - `dust`: Amount of `token_in` left in the caller's account (0 to swap everything)
+    /// - `amount_out_min`: Least `token_out` the caller accepts, net of the protocol fee
+    /// - `deadline`: Optional latest ledger timestamp at which the swap may execute
+    /// - `deadline_delta`: Optional swap deadline, in ledgers from now
+    /// - `delivery`: Whether the output is transferred right away or credited as claimable
+    ///
+    /// ## Returns:
+    /// Amount of `token_in` swapped and amount of `token_out` received
+    pub fn swap_all(
+        e: Env,
+        caller: Address,
+        token_in: Address,
+        token_out: Address,
+        dust: i128,
+        amount_out_min: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+        delivery: PushOrPull,
+    ) -> Result<(i128, i128), SoroswapError> {
+        caller.require_auth();
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_SWAP)?;
+        check_nonnegative_amount(dust)?;
+        check_nonnegative_amount(amount_out_min)?;
+        check_terms_accepted(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "swap_all");
+
+        let amount = token::Client::new(&e, &token_in).balance(&caller) - dust;
+        if amount <= 0 {
+            return Err(SoroswapError::InvalidArgument);
+        }
+
+        let path = default_path(&e, &token_in, &token_out);
+        let limits = SwapLimits { min_out: amount_out_min, deadline };
+        let total_swapped_amount = swap_into_custody(&e, &caller, &caller, &path, amount, limits)?.amount_out;
+        deliver(&e, &caller, &token_out, total_swapped_amount, delivery);
+        Ok((amount, total_swapped_amount))
+    }
+
+    /// Swap a basis-point fraction of the caller's `token_in` balance
+    ///
+    /// Works like `swap_all` with the amount set to `bps` of the balance (rounded down), for
+    /// rebalancing UIs that think in percentages. Fails with `InvalidArgument` when `bps` is
+    /// zero or above 100% or the fraction rounds to nothing, and with `SlippageExceeded` when
+    /// less than `min_out` is received.
+    ///
+    /// ## Parameters:
+    /// - `caller`: The user executing the swap (must sign the transaction)
+    /// - `token_in`: Token being sold
+    /// - `token_out`: Token being purchased
+    /// - `bps`: Share of the caller's `token_in` balance to swap
+    /// - `min_out`: Least `token_out` the caller accepts
+    /// - `deadline`: Optional latest ledger timestamp at which the swap may execute
+    /// - `deadline_delta`: Optional swap deadline, in ledgers from now
+    ///
+    /// ## Returns:
+    /// Amount of `token_in` swapped and amount of `token_out` received
+    pub fn swap_pct(
+        e: Env,
+        caller: Address,
+        token_in: Address,
+        token_out: Address,
+        bps: Bps,
+        min_out: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+    ) -> Result<(i128, i128), SoroswapError> {
+        caller.require_auth();
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_SWAP)?;
+        check_nonnegative_amount(min_out)?;
+        if bps == Bps::ZERO || !bps.is_valid() {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        check_terms_accepted(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "swap_pct");
+
+        let amount = bps
+            .checked_apply(token::Client::new(&e, &token_in).balance(&caller))
+            .ok_or(SoroswapError::InvalidArgument)?;
+        if amount == 0 {
+            return Err(SoroswapError::InvalidArgument);
+        }
+
+        let path = default_path(&e, &token_in, &token_out);
+        let limits = SwapLimits { min_out, deadline };
+        let total_swapped_amount = swap_into_custody(&e, &caller, &caller, &path, amount, limits)?.amount_out;
+        token::Client::new(&e, &token_out).transfer(&e.current_contract_address(), &caller, &total_swapped_amount);
+        Ok((amount, total_swapped_amount))
+    }
+
+    /// Buy exactly `amount_out` of `token_out`, spending at most `amount_in_max` of `token_in`
+    ///
+    /// Takes custody of `amount_in_max`, quotes the required input on each Soroswap venue (primary
+    /// router first, then the fallbacks) and authorizes only the quoted amount for the router's
+    /// nested transfer. The protocol fee is charged on the spent input, so a venue is only used
+    /// when its quote plus the fee fits in `amount_in_max`; otherwise the next one is tried.
+    /// Whatever `token_in` is left is refunded to the caller. Fails with `SlippageExceeded` when
+    /// no venue's quote plus the fee fits.
+    ///
+    /// ## Parameters:
+    /// - `caller`: The user executing the swap (must sign the transaction)
+    /// - `token_in`: Token being sold
+    /// - `token_out`: Token being purchased
+    /// - `amount_out`: Exact amount of `token_out` to receive
+    /// - `amount_in_max`: Largest amount of `token_in` the caller accepts to spend
+    /// - `deadline`: Optional latest ledger timestamp at which the swap may execute
+    /// - `deadline_delta`: Optional swap deadline, in ledgers from now
+    ///
+    /// ## Returns:
+    /// Amount of `token_in` spent, including the protocol fee
+    pub fn swap_exact_out(
+        e: Env,
+        caller: Address,
+        token_in: Address,
+        token_out: Address,
+        amount_out: i128,
+        amount_in_max: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+    ) -> Result<i128, SoroswapError> {
+        caller.require_auth();
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_SWAP_EXACT_OUT)?;
+        check_nonnegative_amount(amount_out)?;
+        check_nonnegative_amount(amount_in_max)?;
+        if amount_out == 0 || token_in == token_out {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        check_terms_accepted(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "swap_exact_out");
+        check_tokens_allowed(&e, &token_in, &token_out)?;
+
+        let this = e.current_contract_address();
+        let received = take_custody(&e, &token_in, &caller, amount_in_max);
+        let path = default_path(&e, &token_in, &token_out);
+
+        // Primary router first, then the fallbacks; a failed attempt leaves the custody intact
+        let mut error = no_router_error(&e);
+        for venue in venues(&e, Dex::Soroswap)?.iter() {
+            let Some(quoted_in) = venue.quote_in(&e, &path, amount_out) else {
+                continue;
+            };
+            // The fee is charged on top of the spent input, so both must fit in the custody
+            if quoted_in.saturating_add(protocol_fee_on(&e, quoted_in)) > received {
+                error = SoroswapError::SlippageExceeded;
+                continue;
+            }
+            let Some(result) = venue.swap_exact_out(&e, &path, amount_out, quoted_in, deadline) else {
+                continue;
+            };
+
+            let amount_received = result.amount_out;
+            let protocol_fee = take_protocol_fee(&e, &token_in, result.amount_in);
+            let spent = result.amount_in + protocol_fee;
+            record_volume(&e, &token_in, spent)?;
+            record_volume(&e, &token_out, amount_received)?;
+            if received > spent {
+                token::Client::new(&e, &token_in).transfer(&this, &caller, &(received - spent));
+            }
+            token::Client::new(&e, &token_out).transfer(&this, &caller, &amount_received);
+
+            record_receipt(&e, &caller, &token_in, &token_out, spent, amount_received);
+            track_round_trip(&e, &caller, &token_in, &token_out);
+            SwapExecuted {
+                caller: caller.clone(),
+                token_in: token_in.clone(),
+                token_out: token_out.clone(),
+                amount_in: spent,
+                amount_out: amount_received,
+                recipient: caller.clone(),
+                protocol_fee,
+            }
+            .publish(&e);
+            SwapRouted {
+                router: result.venue,
+                caller,
+                token_in,
+                token_out,
+                amount_in: spent,
+                amount_out: amount_received,
+                path,
+            }
+            .publish(&e);
+            return Ok(spent);
+        }
+        Err(error)
+    }
+
+    /// Describe exactly which invocations the user's signature must cover for an operation
+    ///
+    /// Wallets can build the `SorobanAuthorizationEntry` tree from the result instead of
+    /// reverse-engineering it from failed simulations. The user signs the root call to this
+    /// contract and, for swaps, the custody transfer nested under it. Every deeper call (router,
+    /// pair, proxy) is authorized by this contract itself and needs no user signature.
+    ///
+    /// `approve_standing` only covers the standing order; the `token_in.approve(...)` allowance
+    /// it relies on is a separate transaction signed by the user.
+    pub fn required_auth(e: Env, operation: AuthOperation) -> Vec<AuthSpec> {
+        let this = e.current_contract_address();
+        match operation {
+            AuthOperation::Swap(params, amount_out_min, path, referrer) => vec![
+                &e,
+                auth_spec(
+                    &e,
+                    &this,
+                    "swap",
+                    (
+                        params.caller.clone(),
+                        params.token_in.clone(),
+                        params.token_out.clone(),
+                        params.amount,
+                        amount_out_min,
+                        params.deadline,
+                        params.deadline_delta,
+                        path,
+                        referrer,
+                    )
+                        .into_val(&e),
+                    0,
+                ),
+                custody_transfer_spec(&e, &params),
+            ],
+            AuthOperation::SwapAndDistribute(params, recipients, amount_out_min) => vec![
+                &e,
+                auth_spec(
+                    &e,
+                    &this,
+                    "swap_and_distribute",
+                    (
+                        params.caller.clone(),
+                        params.token_in.clone(),
+                        params.amount,
+                        params.token_out.clone(),
+                        recipients,
+                        amount_out_min,
+                        params.deadline,
+                        params.deadline_delta,
+                    )
+                        .into_val(&e),
+                    0,
+                ),
+                custody_transfer_spec(&e, &params),
+            ],
+            AuthOperation::SwapViaProxy(params, proxy, amount_out_min) => vec![
+                &e,
+                auth_spec(
+                    &e,
+                    &this,
+                    "swap_via_proxy",
+                    (
+                        params.caller.clone(),
+                        proxy,
+                        params.token_in.clone(),
+                        params.token_out.clone(),
+                        params.amount,
+                        amount_out_min,
+                        params.deadline,
+                        params.deadline_delta,
+                    )
+                        .into_val(&e),
+                    0,
+                ),
+                custody_transfer_spec(&e, &params),
+            ],
+            AuthOperation::SwapOn(params, amount_out_min, dex) => vec![
+                &e,
+                auth_spec(
+                    &e,
+                    &this,
+                    "swap_on",
+                    (
+                        params.caller.clone(),
+                        params.token_in.clone(),
+                        params.token_out.clone(),
+                        params.amount,
+                        amount_out_min,
+                        params.deadline,
+                        params.deadline_delta,
+                        dex,
+                    )
+                        .into_val(&e),
+                    0,
+                ),
+                custody_transfer_spec(&e, &params),
+            ],
+            AuthOperation::SwapSplit(params, legs, min_out) => vec![
+                &e,
+                auth_spec(&e, &this, "swap_split", (params.clone(), legs, min_out).into_val(&e), 0),
+                custody_transfer_spec(&e, &params),
+            ],
+            AuthOperation::ApproveStanding(caller, approval) => vec![
+                &e,
+                auth_spec(
+                    &e,
+                    &this,
+                    "approve_standing",
+                    (
+                        caller,
+                        approval.executor,
+                        approval.token_in,
+                        approval.token_out,
+                        approval.max_per_fill,
+                        approval.min_rate,
+                        approval.expiry,
+                    )
+                        .into_val(&e),
+                    0,
+                ),
+            ],
+            AuthOperation::ExecuteOrder(order) => vec![
+                &e,
+                auth_spec(&e, &this, "execute_order", (order.clone(),).into_val(&e), 0),
+                auth_spec(
+                    &e,
+                    &order.token_in,
+                    "transfer",
+                    (order.owner, this.clone(), order.amount_in).into_val(&e),
+                    1,
+                ),
+            ],
+        }
+    }
+
+    /// Swap `token_in` to `token_out` and split the output between several recipients
+    ///
+    /// Lets DAOs and teams convert treasury assets and pay multiple parties in one authorized
+    /// transaction. Each recipient is paired with its share in basis points; the shares must add
+    /// up to exactly 10_000. Rounding dust goes to the last recipient.
+    ///
+    /// ## Parameters:
+    /// - `caller`: The user executing the swap (must sign the transaction)
+    /// - `token_in`: Token being sold
+    /// - `amount`: Amount of `token_in` to swap
+    /// - `token_out`: Token being purchased and distributed
+    /// - `recipients`: `(recipient, share in bps)` pairs
+    /// - `amount_out_min`: Least total `token_out` distributed, net of the protocol fee
+    /// - `deadline`: Optional latest ledger timestamp at which the swap may execute
+    /// - `deadline_delta`: Optional swap deadline, in ledgers from now
+    ///
+    /// ## Returns:
+    /// Total amount of `token_out` distributed
+    pub fn swap_and_distribute(
+        e: Env,
+        caller: Address,
+        token_in: Address,
+        amount: i128,
+        token_out: Address,
+        recipients: Vec<(Address, Bps)>,
+        amount_out_min: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+    ) -> Result<i128, SoroswapError> {
+        caller.require_auth();
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_SWAP_AND_DISTRIBUTE)?;
+        check_nonnegative_amount(amount)?;
+        check_nonnegative_amount(amount_out_min)?;
+        check_terms_accepted(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "swap_and_distribute");
+
+        let mut total_bps: u32 = 0;
+        for (_, bps) in recipients.iter() {
+            total_bps = total_bps.saturating_add(bps.value());
+        }
+        if recipients.is_empty() || total_bps != BPS_DENOMINATOR {
+            return Err(SoroswapError::InvalidArgument);
+        }
+
+        let this = e.current_contract_address();
+        let path = default_path(&e, &token_in, &token_out);
+        let limits = SwapLimits { min_out: amount_out_min, deadline };
+        let total_swapped_amount = swap_into_custody(&e, &caller, &this, &path, amount, limits)?.amount_out;
+
+        // Pay every recipient its share; the last one also receives the rounding remainder
+        let token_out_client = token::Client::new(&e, &token_out);
+        let mut remaining = total_swapped_amount;
+        for (index, (recipient, bps)) in recipients.iter().enumerate() {
+            let share = if index as u32 == recipients.len() - 1 {
+                remaining
+            } else {
+                bps.apply(total_swapped_amount)
+            };
+            remaining -= share;
+            token_out_client.transfer(&this, &recipient, &share);
+
+            OutputDistributed {
+                caller: caller.clone(),
+                recipient,
+                token_out: token_out.clone(),
+                amount: share,
+            }
+            .publish(&e);
+        }
+
+        Ok(total_swapped_amount)
+    }
+
+    /// Approve a standing order: repeated swaps a bot can execute without fresh signatures
+    ///
+    /// The approval fixes who may fill it, the pair, the largest amount a single fill may sell,
+    /// the worst rate a fill accepts and the ledger after which it expires. The tokens themselves
+    /// are pulled with `transfer_from`, so the user also grants this contract a
+    /// `token_in.approve(...)` allowance; that allowance caps the total sold across all fills.
+    /// Replaces any previous standing approval of the caller.
+    ///
+    /// ## Parameters:
+    /// - `caller`: The user approving the standing order (must sign the transaction)
+    /// - `executor`: The bot allowed to submit fills (each fill needs its signature)
+    /// - `token_in`: Token sold by every fill
+    /// - `token_out`: Token bought by every fill, delivered to the caller
+    /// - `max_per_fill`: Largest `amount` accepted by a single `swap_standing`
+    /// - `min_rate`: Least `token_out` per unit of `token_in`, scaled by `RATE_PRECISION`
+    /// - `expiry`: Last ledger sequence at which fills are accepted
+    pub fn approve_standing(
+        e: Env,
+        caller: Address,
+        executor: Address,
+        token_in: Address,
+        token_out: Address,
+        max_per_fill: i128,
+        min_rate: i128,
+        expiry: u32,
+    ) -> Result<(), SoroswapError> {
+        caller.require_auth();
+        check_nonnegative_amount(max_per_fill)?;
+        if max_per_fill == 0 || min_rate <= 0 || token_in == token_out || expiry < e.ledger().sequence() {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        check_terms_accepted(&e, &caller)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "approve_standing");
+
+        set_standing_approval(
+            &e,
+            &caller,
+            &StandingApproval {
+                executor: executor.clone(),
+                token_in: token_in.clone(),
+                token_out: token_out.clone(),
+                max_per_fill,
+                min_rate,
+                expiry,
+            },
+        );
+
+        StandingApproved {
+            user: caller,
+            executor,
+            token_in,
+            token_out,
+            max_per_fill,
+            min_rate,
+            expiry,
+        }
+        .publish(&e);
+        Ok(())
+    }
+
+    /// Revoke the caller's standing approval
+    pub fn revoke_standing(e: Env, caller: Address) {
+        caller.require_auth();
+        extend_instance_ttl(&e);
+        remove_standing_approval(&e, &caller);
+        StandingRevoked { user: caller }.publish(&e);
+    }
+
+    /// Standing approval of a user, if any (it may already be expired)
+    pub fn standing_approval(e: Env, user: Address) -> Option<StandingApproval> {
+        get_standing_approval(&e, &user)
+    }
+
+    /// Amount of `token_in` that fills of the user's standing order can still sell
+    ///
+    /// This is the user's remaining token allowance to this contract, or 0 when there is no
+    /// standing approval or it has expired. A single fill is further capped by `max_per_fill`.
+    pub fn standing_remaining(e: Env, user: Address) -> i128 {
+        match get_standing_approval(&e, &user) {
+            Some(approval) if approval.expiry >= e.ledger().sequence() => {
+                token::Client::new(&e, &approval.token_in).allowance(&user, &e.current_contract_address())
+            }
+            _ => 0,
+        }
+    }
+
+    /// Let `operator` swap up to `max_amount` of the owner's `token_in` into `token_out` until
+    /// `expiry`, at no worse than `min_rate`
+    ///
+    /// Replaces any previous allowance of the operator for `token_in`. The owner must also grant
+    /// this contract a `token_in.approve(...)` allowance covering the swaps.
+    ///
+    /// ## Parameters:
+    /// - `owner`: The user whose tokens are swapped (must sign the transaction)
+    /// - `operator`: Account allowed to call `swap_for`
+    /// - `token_in`: Token the operator may sell
+    /// - `token_out`: Only token the operator may buy, delivered to the owner
+    /// - `max_amount`: Total amount of `token_in` the operator may sell
+    /// - `min_rate`: Least `token_out` per unit of `token_in`, scaled by `RATE_PRECISION`
+    /// - `expiry`: Last ledger sequence at which the operator can swap
+    pub fn approve_operator(
+        e: Env,
+        owner: Address,
+        operator: Address,
+        token_in: Address,
+        token_out: Address,
+        max_amount: i128,
+        min_rate: i128,
+        expiry: u32,
+    ) -> Result<(), SoroswapError> {
+        owner.require_auth();
+        check_nonnegative_amount(max_amount)?;
+        if max_amount == 0
+            || min_rate <= 0
+            || operator == owner
+            || token_in == token_out
+            || expiry < e.ledger().sequence()
+        {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        check_terms_accepted(&e, &owner)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "approve_operator");
+
+        let allowance = OperatorAllowance {
+            token_out: token_out.clone(),
+            remaining: max_amount,
+            min_rate,
+            expiry,
+        };
+        set_operator_allowance(&e, &owner, &operator, &token_in, &allowance);
+
+        OperatorApproved {
+            owner,
+            operator,
+            token_in,
+            token_out,
+            max_amount,
+            min_rate,
+            expiry,
+        }
+        .publish(&e);
+        Ok(())
+    }
+
+    /// Revoke an operator's allowance for `token`
+    pub fn revoke_operator(e: Env, owner: Address, operator: Address, token: Address) {
+        owner.require_auth();
+        extend_instance_ttl(&e);
+        remove_operator_allowance(&e, &owner, &operator, &token);
+        OperatorRevoked { owner, operator, token }.publish(&e);
+    }
+
+    /// What `operator` may still swap of the owner's `token`, if approved
+    pub fn operator_allowance(e: Env, owner: Address, operator: Address, token: Address) -> Option<OperatorAllowance> {
+        get_operator_allowance(&e, &owner, &operator, &token)
+    }
+
+    /// Swap the owner's tokens over the default route, as an approved operator
+    ///
+    /// Consumes `amount` of the operator's allowance for `token_in` and counts it against the
+    /// owner's daily limit. The output goes to the owner and must reach the allowance's
+    /// `min_rate`, which is passed to the venues as the swap's minimum. Fails with `Unauthorized`
+    /// when the operator has no live allowance or `token_out` is not the approved one, with
+    /// `InvalidArgument` when `amount` exceeds the allowance, and with `SlippageExceeded` below
+    /// the minimum.
+    ///
+    /// ## Parameters:
+    /// - `operator`: The approved operator (must sign the transaction)
+    /// - `owner`: The user whose tokens are swapped
+    /// - `token_in`: Token being sold
+    /// - `token_out`: Token being purchased, delivered to the owner
+    /// - `amount`: Amount of `token_in` to swap
+    /// - `deadline`: Optional latest ledger timestamp at which the swap may execute
+    /// - `deadline_delta`: Optional swap deadline, in ledgers from now
+    ///
+    /// ## Returns:
+    /// Amount of `token_out` delivered to the owner
+    pub fn swap_for(
+        e: Env,
+        operator: Address,
+        owner: Address,
+        token_in: Address,
+        token_out: Address,
+        amount: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+    ) -> Result<i128, SoroswapError> {
+        operator.require_auth();
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_SWAP_FOR)?;
+        check_nonnegative_amount(amount)?;
+        check_terms_accepted(&e, &owner)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "swap_for");
+
+        let mut allowance =
+            get_operator_allowance(&e, &owner, &operator, &token_in).ok_or(SoroswapError::Unauthorized)?;
+        if allowance.expiry < e.ledger().sequence() || allowance.token_out != token_out {
+            return Err(SoroswapError::Unauthorized);
+        }
+        if amount == 0 || amount > allowance.remaining {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        let min_out = amount
+            .checked_mul(allowance.min_rate)
+            .ok_or(SoroswapError::InvalidArgument)?
+            / RATE_PRECISION;
+        allowance.remaining -= amount;
+        set_operator_allowance(&e, &owner, &operator, &token_in, &allowance);
+        record_delegated_spend(&e, &owner, &token_in, amount)?;
+
+        // Take custody through the owner's token allowance; the operator signs nothing for them
+        let this = e.current_contract_address();
+        let token_in_client = token::Client::new(&e, &token_in);
+        let balance_before = token_in_client.balance(&this);
+        token_in_client.transfer_from(&this, &owner, &this, &amount);
+        let received = token_in_client.balance(&this) - balance_before;
+
+        let path = default_path(&e, &token_in, &token_out);
+        let limits = SwapLimits { min_out, deadline };
+        let total_swapped_amount = swap_from_custody(&e, &owner, &owner, &path, amount, received, limits)?.amount_out;
+        token::Client::new(&e, &token_out).transfer(&this, &owner, &total_swapped_amount);
+
+        Ok(total_swapped_amount)
+    }
+
+    /// Commit to a swap without revealing it (first phase of commit-reveal)
+    ///
+    /// `hash` is `commitment_hash(params, salt)`, computed off-chain so the parameters never leave
+    /// the caller before `reveal`. The commitment expires after `COMMITMENT_TTL` ledgers.
+    pub fn commit(e: Env, caller: Address, hash: BytesN<32>) -> Result<(), SoroswapError> {
+        caller.require_auth();
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_COMMIT_REVEAL)?;
+        check_terms_accepted(&e, &caller)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "commit");
+
+        set_commitment(&e, &caller, &hash, e.ledger().sequence());
+        SwapCommitted { caller, hash }.publish(&e);
+        Ok(())
+    }
+
+    /// Reveal and execute a committed swap (second phase of commit-reveal)
+    ///
+    /// Works like `swap` over the default route, with the commitment consumed. Fails with
+    /// `CommitmentNotFound` when no live commitment of the caller matches `params` and `salt`,
+    /// `RevealTooEarly` in the commitment's own ledger, and `SlippageExceeded` when less than
+    /// `min_out` is received.
+    ///
+    /// ## Returns:
+    /// Amount of `token_out` received
+    pub fn reveal(e: Env, caller: Address, params: CommittedSwap, salt: BytesN<32>) -> Result<i128, SoroswapError> {
+        caller.require_auth();
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_COMMIT_REVEAL)?;
+        check_nonnegative_amount(params.amount)?;
+        check_nonnegative_amount(params.min_out)?;
+        check_terms_accepted(&e, &caller)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "reveal");
+
+        let hash = swap_commitment_hash(&e, &params, &salt);
+        let committed = get_commitment(&e, &caller, &hash).ok_or(SoroswapError::CommitmentNotFound)?;
+        if committed >= e.ledger().sequence() {
+            return Err(SoroswapError::RevealTooEarly);
+        }
+        remove_commitment(&e, &caller, &hash);
+
+        let path = default_path(&e, &params.token_in, &params.token_out);
+        let deadline = intent_deadline(&e, committed + COMMITMENT_TTL);
+        let limits = SwapLimits { min_out: params.min_out, deadline };
+        let amount_out = swap_into_custody(&e, &caller, &caller, &path, params.amount, limits)?.amount_out;
+        token::Client::new(&e, &params.token_out).transfer(&e.current_contract_address(), &caller, &amount_out);
+        Ok(amount_out)
+    }
+
+    /// Hash `commit` expects for `params` and `salt`
+    ///
+    /// The SHA-256 of the parameters' XDR followed by the salt. Simulating this exposes the
+    /// parameters to the RPC provider; privacy-sensitive frontends compute it locally.
+    pub fn commitment_hash(e: Env, params: CommittedSwap, salt: BytesN<32>) -> BytesN<32> {
+        swap_commitment_hash(&e, &params, &salt)
+    }
+
+    /// Execute a swap order the owner signed off-chain, submitted by any relayer
+    ///
+    /// The relayer pays the transaction fees; the owner's authorization entry covers this call
+    /// with the exact order and the custody transfer of `amount_in` (`required_auth` describes
+    /// the tree), so account keys and smart-wallet policies are verified by the host alike.
+    /// The swap runs over the direct pair and the output goes to the owner. Fails with
+    /// `OrderExpired` past the order's expiry, `NonceUsed` when the nonce was executed or
+    /// cancelled, and `SlippageExceeded` when less than `min_amount_out` is received (a failing
+    /// order can be submitted again).
+    ///
+    /// ## Returns:
+    /// Amount of `token_out` delivered to the owner
+    pub fn execute_order(e: Env, order: SwapOrder) -> Result<i128, SoroswapError> {
+        order.owner.require_auth();
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_EXECUTE_ORDER)?;
+        check_nonnegative_amount(order.amount_in)?;
+        check_nonnegative_amount(order.min_amount_out)?;
+        if order.expiry < e.ledger().sequence() {
+            return Err(SoroswapError::OrderExpired);
+        }
+        if get_order_nonce_used(&e, &order.owner, order.nonce) {
+            return Err(SoroswapError::NonceUsed);
+        }
+        check_terms_accepted(&e, &order.owner)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "execute_order");
+        set_order_nonce_used(&e, &order.owner, order.nonce);
+
+        let path = default_path(&e, &order.token_in, &order.token_out);
+        let deadline = intent_deadline(&e, order.expiry);
+        let owner = &order.owner;
+        let limits = SwapLimits { min_out: order.min_amount_out, deadline };
+        let amount_out = swap_into_custody(&e, owner, owner, &path, order.amount_in, limits)?.amount_out;
+        token::Client::new(&e, &order.token_out).transfer(&e.current_contract_address(), &order.owner, &amount_out);
+
+        OrderExecuted {
+            owner: order.owner,
+            nonce: order.nonce,
+            amount_out,
+        }
+        .publish(&e);
+        Ok(amount_out)
+    }
+
+    /// Burn a signed-order nonce so the order can no longer execute
+    pub fn cancel_order(e: Env, owner: Address, nonce: u64) -> Result<(), SoroswapError> {
+        owner.require_auth();
+        if get_order_nonce_used(&e, &owner, nonce) {
+            return Err(SoroswapError::NonceUsed);
+        }
+        extend_instance_ttl(&e);
+        set_order_nonce_used(&e, &owner, nonce);
+        OrderCancelled { owner, nonce }.publish(&e);
+        Ok(())
+    }
+
+    /// Whether the owner's order `nonce` was executed or cancelled
+    pub fn order_nonce_used(e: Env, owner: Address, nonce: u64) -> bool {
+        get_order_nonce_used(&e, &owner, nonce)
+    }
+
+    /// Cap (or with `None` uncap) how much of `token` operators swap for the user per day
+    ///
+    /// Covers standing-order and `swap_for` fills. With an admin default also set, the lower
+    /// limit applies. The window opens at the first limited fill and resets once 24 hours have
+    /// passed by the ledger timestamp; fills above what is left fail with `DailyLimitExceeded`.
+    pub fn set_daily_limit(e: Env, user: Address, token: Address, limit: Option<i128>) -> Result<(), SoroswapError> {
+        user.require_auth();
+        if limit.is_some_and(|limit| limit < 0) {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        extend_instance_ttl(&e);
+        set_daily_limit(&e, &user, &token, limit);
+        DailyLimitUpdated { token, user: Some(user), limit }.publish(&e);
+        Ok(())
+    }
+
+    /// Set (or with `None` remove) the daily limit applying to every user's `token` (admin only)
+    pub fn set_default_daily_limit(e: Env, token: Address, limit: Option<i128>) -> Result<(), SoroswapError> {
+        get_admin(&e).require_auth();
+        if limit.is_some_and(|limit| limit < 0) {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        extend_instance_ttl(&e);
+        set_default_daily_limit(&e, &token, limit);
+        DailyLimitUpdated { token, user: None, limit }.publish(&e);
+        Ok(())
+    }
+
+    /// Daily limit applying to what operators swap of `token` for `user`, if any
+    pub fn daily_limit(e: Env, user: Address, token: Address) -> Option<i128> {
+        daily_limit(&e, &user, &token)
+    }
+
+    /// What operators can still swap of `token` for `user` in the current window, if limited
+    pub fn daily_remaining(e: Env, user: Address, token: Address) -> Option<i128> {
+        let limit = daily_limit(&e, &user, &token)?;
+        Some((limit - current_spend(&e, &user, &token).spent).max(0))
+    }
+
+    /// Execute one fill of a user's standing order
+    ///
+    /// Only the approval's `executor` can call it, and each fill must return at least `min_rate`
+    /// per unit sold; that minimum goes to the venue with the swap. This contract pulls `amount`
+    /// of `token_in` with `transfer_from` - it is the spender, and as the direct invoker of the
+    /// token its `require_auth` is satisfied implicitly. The swap then follows the regular custody
+    /// flow and the output goes to the user. The router deadline is
+    /// recomputed for each fill from the approval's `expiry`, so late fills are not sent with a
+    /// stale deadline and fills past the expiry cannot execute.
+    ///
+    /// ## Parameters:
+    /// - `user`: Owner of the standing order
+    /// - `amount`: Amount of `token_in` to sell, at most `max_per_fill`
+    ///
+    /// ## Returns:
+    /// Amount of `token_out` delivered to the user
+    pub fn swap_standing(e: Env, user: Address, amount: i128) -> Result<i128, SoroswapError> {
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_SWAP_STANDING)?;
+        check_nonnegative_amount(amount)?;
+        check_terms_accepted(&e, &user)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "swap_standing");
+
+        let approval = get_standing_approval(&e, &user).ok_or(SoroswapError::Unauthorized)?;
+        if approval.expiry < e.ledger().sequence() {
+            return Err(SoroswapError::Unauthorized);
+        }
+        approval.executor.require_auth();
+        if amount == 0 || amount > approval.max_per_fill {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        let min_out = amount
+            .checked_mul(approval.min_rate)
+            .ok_or(SoroswapError::InvalidArgument)?
+            / RATE_PRECISION;
+
+        record_delegated_spend(&e, &user, &approval.token_in, amount)?;
+
+        // Take custody through the user's allowance instead of a fresh signature
+        let this = e.current_contract_address();
+        let token_in_client = token::Client::new(&e, &approval.token_in);
+        let balance_before = token_in_client.balance(&this);
+        token_in_client.transfer_from(&this, &user, &this, &amount);
+        let received = token_in_client.balance(&this) - balance_before;
+
+        let total_swapped_amount = swap_from_custody(
+            &e,
+            &user,
+            &user,
+            &default_path(&e, &approval.token_in, &approval.token_out),
+            amount,
+            received,
+            SwapLimits {
+                min_out,
+                deadline: intent_deadline(&e, approval.expiry),
+            },
+        )?
+        .amount_out;
+
+        token::Client::new(&e, &approval.token_out).transfer(&this, &user, &total_swapped_amount);
+        report_step(&e, &user, STEP_ORDER_FILL);
+
+        Ok(total_swapped_amount)
+    }
+
+    /// Swap through a direct-proxy contract (e.g. `soroswap-simple`) - multi-level authorization example
+    ///
+    /// ## Why This Needs Nested Authorization:
+    /// Here this contract is the proxy's `caller`, so the call chain becomes:
+    /// ```
+    /// this contract → proxy.swap → router.swap_exact_tokens_for_tokens → token.transfer
+    /// ```
+    /// - `proxy.swap` calls `caller.require_auth()`: satisfied implicitly, this contract is the
+    ///   direct invoker
+    /// - The router calls `to.require_auth()` for this contract, but the router is invoked by the
+    ///   proxy, not by us - this call must be authorized explicitly
+    /// - The router then calls `token.transfer(from=this contract, ...)`, one level deeper still
+    ///
+    /// The authorization entry therefore mirrors the call tree: the router call at the top, with
+    /// the token transfer nested in its `sub_invocations`. A single-level entry (only the
+    /// transfer) fails at the router's `require_auth`.
+    ///
+    /// ## Parameters:
+    /// - `caller`: The user executing the swap (must sign the transaction)
+    /// - `proxy`: Direct-proxy swap contract to route through
+    /// - `token_in`: Token being sold
+    /// - `token_out`: Token being purchased
+    /// - `amount`: Amount of `token_in` to swap
+    /// - `amount_out_min`: Least `token_out` the caller accepts, net of the protocol fee. The
+    ///   proxy passes it on to the router grossed up by the fee
+    /// - `deadline`: Optional latest ledger timestamp at which the swap may execute
+    /// - `deadline_delta`: Optional swap deadline, in ledgers from now
+    ///
+    /// ## Returns:
+    /// Amount of `token_out` received from the swap, net of the protocol fee
+    pub fn swap_via_proxy(
+        e: Env,
+        caller: Address,
+        proxy: Address,
+        token_in: Address,
+        token_out: Address,
+        amount: i128,
+        amount_out_min: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+    ) -> Result<i128, SoroswapError> {
+        caller.require_auth();
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_SWAP_VIA_PROXY)?;
+        check_nonnegative_amount(amount)?;
+        check_nonnegative_amount(amount_out_min)?;
+        check_terms_accepted(&e, &caller)?;
+        // The proxy receives the resolved deadline and passes it on to the router
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "swap_via_proxy");
+        check_tokens_allowed(&e, &token_in, &token_out)?;
+
+        // Take custody: the proxy swaps on behalf of this contract, not the user
+        let this = e.current_contract_address();
+        let received = take_custody(&e, &token_in, &caller, amount);
+
+        // Rebuild the exact router call the proxy is going to make
+        let proxy_client = SwapProxyClient::new(&e, &proxy);
+        let router_address = proxy_client.router();
+        let pair_address = soroswap_adapter::pair_for(&e, &router_address, &token_in, &token_out)
+            .ok_or(SoroswapError::SwapFailed)?;
+        let mut path: Vec<Address> = Vec::new(&e);
+        path.push_back(token_in.clone());
+        path.push_back(token_out.clone());
+        let token_out_client = token::Client::new(&e, &token_out);
+        let balance_before = token_out_client.balance(&this);
+        let router_min = venue_min_out(&e, amount_out_min, None);
+
+        // Level 1: router.swap_exact_tokens_for_tokens(received, router_min, path, to=this contract, deadline)
+        // Level 2 (nested): token_in.transfer(from=this contract, to=pair, received)
+        // The entries only cover the next contract call, so the proxy call must follow directly
+        AuthBuilder::new(&e)
+            .call(
+                &router_address,
+                "swap_exact_tokens_for_tokens",
+                (received, router_min, path.clone(), this.clone(), deadline).into_val(&e),
+            )
+            .nested(AuthBuilder::new(&e).transfer(&token_in, &this, &pair_address, received))
+            .authorize();
+        proxy_client.swap(&this, &token_in, &token_out, &received, &router_min, &deadline, &None);
+        let swapped = token_out_client.balance(&this) - balance_before;
+        record_volume(&e, &token_in, amount)?;
+        record_volume(&e, &token_out, swapped)?;
+        let protocol_fee = take_protocol_fee(&e, &token_out, swapped);
+        let total_swapped_amount = swapped - protocol_fee;
+        if total_swapped_amount < amount_out_min {
+            return Err(SoroswapError::SlippageExceeded);
+        }
+
+        // Forward the output tokens from custody back to the original caller
+        token_out_client.transfer(&this, &caller, &total_swapped_amount);
+
+        record_receipt(&e, &caller, &token_in, &token_out, amount, total_swapped_amount);
+        track_round_trip(&e, &caller, &token_in, &token_out);
+
+        SwapExecuted {
+            caller: caller.clone(),
+            token_in: token_in.clone(),
+            token_out: token_out.clone(),
+            amount_in: amount,
+            amount_out: total_swapped_amount,
+            recipient: caller.clone(),
+            protocol_fee,
+        }
+        .publish(&e);
+        SwapRouted {
+            router: proxy,
+            caller,
+            token_in,
+            token_out,
+            amount_in: amount,
+            amount_out: total_swapped_amount,
+            path,
+        }
+        .publish(&e);
+
+        Ok(total_swapped_amount)
+    }
+
+    /// Post collateral backing the maker's guarantee quotes
+    ///
+    /// ## Returns:
+    /// Amount credited (what actually arrived)
+    pub fn post_collateral(e: Env, maker: Address, token: Address, amount: i128) -> Result<i128, SoroswapError> {
+        maker.require_auth();
+        check_nonnegative_amount(amount)?;
+        extend_instance_ttl(&e);
+
+        let received = take_custody(&e, &token, &maker, amount);
+        let collateral = get_maker_collateral(&e, &maker, &token) + received;
+        set_maker_collateral(&e, &maker, &token, collateral);
+
+        CollateralUpdated { maker, token, collateral }.publish(&e);
+        Ok(received)
+    }
+
+    /// Withdraw collateral the maker no longer wants at risk
+    pub fn withdraw_collateral(e: Env, maker: Address, token: Address, amount: i128) -> Result<(), SoroswapError> {
+        maker.require_auth();
+        check_nonnegative_amount(amount)?;
+        extend_instance_ttl(&e);
+
+        let collateral = get_maker_collateral(&e, &maker, &token);
+        if amount > collateral {
+            return Err(SoroswapError::InsufficientCollateral);
+        }
+        set_maker_collateral(&e, &maker, &token, collateral - amount);
+        token::Client::new(&e, &token).transfer(&e.current_contract_address(), &maker, &amount);
+
+        CollateralUpdated {
+            maker,
+            token,
+            collateral: collateral - amount,
+        }
+        .publish(&e);
+        Ok(())
+    }
+
+    /// Collateral a maker posted in `token`
+    pub fn maker_collateral(e: Env, maker: Address, token: Address) -> i128 {
+        get_maker_collateral(&e, &maker, &token)
+    }
+
+    /// Withdraw pull-delivered output credited to the user
+    ///
+    /// `PushOrPull::Pull` keeps a swap's output here for smart-wallet recipients that cannot
+    /// receive arbitrary transfers.
+    pub fn withdraw_claimable(e: Env, user: Address, token: Address, amount: i128) -> Result<(), SoroswapError> {
+        user.require_auth();
+        check_nonnegative_amount(amount)?;
+        extend_instance_ttl(&e);
+
+        let claimable = get_claimable(&e, &user, &token);
+        if amount > claimable {
+            return Err(SoroswapError::InsufficientClaimable);
+        }
+        set_claimable(&e, &user, &token, claimable - amount);
+        token::Client::new(&e, &token).transfer(&e.current_contract_address(), &user, &amount);
+
+        ClaimableUpdated {
+            user,
+            token,
+            claimable: claimable - amount,
+        }
+        .publish(&e);
+        Ok(())
+    }
+
+    /// Pull-delivered output the user can withdraw in `token`
+    pub fn claimable(e: Env, user: Address, token: Address) -> i128 {
+        get_claimable(&e, &user, &token)
+    }
+
+    /// Transfer every protocol fee accrued in `token` to the fee recipient (fee recipient only)
+    ///
+    /// Fails with `Unauthorized` while no fee was ever configured.
+    ///
+    /// ## Returns:
+    /// Amount claimed
+    pub fn claim_fees(e: Env, token: Address) -> Result<i128, SoroswapError> {
+        let recipient = get_protocol_fee(&e).ok_or(SoroswapError::Unauthorized)?.recipient;
+        recipient.require_auth();
+        extend_instance_ttl(&e);
+
+        let amount = get_accrued_fees(&e, &token);
+        if amount > 0 {
+            set_accrued_fees(&e, &token, 0);
+            token::Client::new(&e, &token).transfer(&e.current_contract_address(), &recipient, &amount);
+        }
+
+        FeesClaimed { recipient, token, amount }.publish(&e);
+        Ok(amount)
+    }
+
+    /// Protocol fees accrued in `token` and not yet claimed
+    pub fn accrued_fees(e: Env, token: Address) -> i128 {
+        get_accrued_fees(&e, &token)
+    }
+
+    /// Credit tokens sent to this contract outside any entrypoint to the protocol fees
+    ///
+    /// The excess is the balance above what the contract accounts for in `token`: the accrued
+    /// protocol fees and what it owes users (claimable outputs, referral fees and maker
+    /// collateral). Swaps hold no custody between invocations, so nothing else is counted.
+    /// Callable by anyone, since the excess can only go to the fee recipient, who withdraws it
+    /// with `claim_fees`.
+    ///
+    /// ## Returns:
+    /// Amount skimmed
+    pub fn skim(e: Env, token: Address) -> i128 {
+        extend_instance_ttl(&e);
+
+        let balance = token::Client::new(&e, &token).balance(&e.current_contract_address());
+        let accrued = get_accrued_fees(&e, &token);
+        let amount = balance - accrued - get_owed_total(&e, &token);
+        if amount <= 0 {
+            return 0;
+        }
+        set_accrued_fees(&e, &token, accrued + amount);
+
+        DonationSkimmed { token, amount }.publish(&e);
+        amount
+    }
+
+    /// Transfer every referral fee the referrer accrued in `token` to them
+    ///
+    /// ## Returns:
+    /// Amount claimed
+    pub fn claim_referral_fees(e: Env, referrer: Address, token: Address) -> i128 {
+        referrer.require_auth();
+        extend_instance_ttl(&e);
+
+        let amount = get_referral_fees(&e, &referrer, &token);
+        if amount > 0 {
+            set_referral_fees(&e, &referrer, &token, 0);
+            token::Client::new(&e, &token).transfer(&e.current_contract_address(), &referrer, &amount);
+        }
+
+        ReferralFeesClaimed { referrer, token, amount }.publish(&e);
+        amount
+    }
+
+    /// Referral fees `referrer` accrued in `token` and not yet claimed
+    pub fn referral_fees(e: Env, referrer: Address, token: Address) -> i128 {
+        get_referral_fees(&e, &referrer, &token)
+    }
+
+    /// Quote a guaranteed minimum output for `token_in -> token_out` swaps
+    ///
+    /// ## Parameters:
+    /// - `maker`: Maker backing the quote with its `token_out` collateral (must sign)
+    /// - `rate`: Guaranteed `token_out` per unit of `token_in`, scaled by 10^7
+    /// - `max_amount_in`: Total `token_in` the quote covers across swaps
+    /// - `max_cover_bps`: Largest share of a swap's guaranteed output the maker's collateral
+    ///   covers; the AMM leg must deliver at least the rest
+    /// - `expiry`: Last ledger sequence at which the quote is honored
+    pub fn set_guarantee_quote(
+        e: Env,
+        maker: Address,
+        token_in: Address,
+        token_out: Address,
+        rate: i128,
+        max_amount_in: i128,
+        max_cover_bps: Bps,
+        expiry: u32,
+    ) -> Result<(), SoroswapError> {
+        maker.require_auth();
+        if rate <= 0
+            || max_amount_in <= 0
+            || !max_cover_bps.is_valid()
+            || token_in == token_out
+            || expiry < e.ledger().sequence()
+        {
+            return Err(SoroswapError::InvalidArgument);
+        }
+        extend_instance_ttl(&e);
+
+        set_guarantee_quote(
+            &e,
+            &maker,
+            &token_in,
+            &token_out,
+            &GuaranteeQuote {
+                rate,
+                remaining_in: max_amount_in,
+                max_cover_bps,
+                expiry,
+            },
+        );
+        GuaranteeQuoted {
+            maker,
+            token_in,
+            token_out,
+            rate,
+            remaining_in: max_amount_in,
+            max_cover_bps,
+            expiry,
+        }
+        .publish(&e);
+        Ok(())
+    }
+
+    /// Withdraw a maker's guarantee quote
+    pub fn remove_guarantee_quote(e: Env, maker: Address, token_in: Address, token_out: Address) {
+        maker.require_auth();
+        extend_instance_ttl(&e);
+        remove_guarantee_quote(&e, &maker, &token_in, &token_out);
+    }
+
+    /// Guarantee quote of a maker for a pair, if any
+    pub fn guarantee_quote(e: Env, maker: Address, token_in: Address, token_out: Address) -> Option<GuaranteeQuote> {
+        get_guarantee_quote(&e, &maker, &token_in, &token_out)
+    }
+
+    /// Swap with the output guaranteed by a maker's quote
+    ///
+    /// The swap executes on the AMM like `swap`, then settles against the maker: the caller
+    /// receives exactly `amount * rate / 10^7` of `token_out`. If the AMM produced less, the
+    /// difference is drawn from the maker's collateral (the call fails with
+    /// `InsufficientCollateral` when it cannot cover it); if it produced more, the surplus is
+    /// added to the maker's collateral. The AMM leg is sent with a minimum of the guaranteed
+    /// output less the quote's `max_cover_bps`, so a sandwiched pool cannot drain the collateral.
+    ///
+    /// ## Parameters:
+    /// - `caller`: The user executing the swap (must sign the transaction)
+    /// - `maker`: Maker whose quote guarantees the output
+    /// - `token_in`: Token being sold
+    /// - `token_out`: Token being purchased
+    /// - `amount`: Amount of `token_in` to swap, at most what the quote still covers
+    /// - `deadline`: Optional latest ledger timestamp at which the swap may execute
+    /// - `deadline_delta`: Optional swap deadline, in ledgers from now
+    ///
+    /// ## Returns:
+    /// Amount of `token_out` delivered to the caller
+    pub fn swap_guaranteed(
+        e: Env,
+        caller: Address,
+        maker: Address,
+        token_in: Address,
+        token_out: Address,
+        amount: i128,
+        deadline: Option<u64>,
+        deadline_delta: Option<u32>,
+    ) -> Result<i128, SoroswapError> {
+        caller.require_auth();
+        check_not_paused(&e)?;
+        check_feature_enabled(&e, FEATURE_SWAP_GUARANTEED)?;
+        check_nonnegative_amount(amount)?;
+        check_terms_accepted(&e, &caller)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
+        extend_instance_ttl(&e);
+        record_call(&e, "swap_guaranteed");
+
+        let mut quote = get_guarantee_quote(&e, &maker, &token_in, &token_out).ok_or(SoroswapError::QuoteUnavailable)?;
+        if quote.expiry < e.ledger().sequence() || amount == 0 || amount > quote.remaining_in {
+            return Err(SoroswapError::QuoteUnavailable);
+        }
+        quote.remaining_in -= amount;
+        set_guarantee_quote(&e, &maker, &token_in, &token_out, &quote);
+        let guaranteed = amount.checked_mul(quote.rate).ok_or(SoroswapError::InvalidArgument)? / RATE_PRECISION;
+        let min_out = quote.max_cover_bps.complement().apply(guaranteed);
+
+        let path = default_path(&e, &token_in, &token_out);
+        let limits = SwapLimits { min_out, deadline };
+        let executed = swap_into_custody(&e, &caller, &caller, &path, amount, limits)?.amount_out;
+
+        let maker_delta = executed - guaranteed;
+        let collateral = get_maker_collateral(&e, &maker, &token_out) + maker_delta;
+        if collateral < 0 {
+            return Err(SoroswapError::InsufficientCollateral);
+        }
+        set_maker_collateral(&e, &maker, &token_out, collateral);
+        token::Client::new(&e, &token_out).transfer(&e.current_contract_address(), &caller, &guaranteed);
+
+        GuaranteeSettled {
+            maker,
+            caller,
+            token_out,
+            guaranteed,
+            executed,
+            maker_delta,
+        }
+        .publish(&e);
+        Ok(guaranteed)
     }
 }
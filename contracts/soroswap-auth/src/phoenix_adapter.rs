@@ -0,0 +1,101 @@
+#![allow(clippy::too_many_arguments)]
+
+use common::{auth::AuthBuilder, pay::SwapResult};
+use soroban_sdk::{contractclient, contracttype, token, vec, Address, Env, Vec};
+
+/// Result of a Phoenix pool's `simulate_swap`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SimulateSwapResponse {
+    /// Output after the commission
+    pub ask_amount: i128,
+    /// Commission kept by the pool, in the output token
+    pub commission_amount: i128,
+    /// Output lost to the pool's price moving during the swap
+    pub spread_amount: i128,
+    pub total_return: i128,
+}
+
+// Interface of the Phoenix factory, which indexes the pools by token pair
+#[allow(dead_code)]
+#[contractclient(name = "PhoenixFactoryClient")]
+pub trait PhoenixFactory {
+    fn query_for_pool_by_token_pair(e: Env, token_a: Address, token_b: Address) -> Address;
+}
+
+// Interface of a Phoenix constant-product pool
+#[allow(dead_code)]
+#[contractclient(name = "PhoenixPoolClient")]
+pub trait PhoenixPool {
+    fn simulate_swap(e: Env, offer_asset: Address, offer_amount: i128) -> SimulateSwapResponse;
+    fn swap(
+        e: Env,
+        sender: Address,
+        offer_asset: Address,
+        offer_amount: i128,
+        ask_asset_min_amount: Option<i128>,
+        max_spread_bps: Option<i64>,
+        deadline: Option<u64>,
+        max_allowed_fee_bps: Option<i64>,
+    ) -> i128;
+}
+
+/// Phoenix pool trading `token_a` against `token_b`, if the factory lists one
+pub fn pool_for(e: &Env, factory: &Address, token_a: &Address, token_b: &Address) -> Option<Address> {
+    match PhoenixFactoryClient::new(e, factory).try_query_for_pool_by_token_pair(token_a, token_b) {
+        Ok(Ok(pool)) => Some(pool),
+        _ => None,
+    }
+}
+
+/// Amounts of `token_in` and `token_out` when swapping `amount` over the pair's Phoenix pool,
+/// `None` when the factory lists no pool or the pool cannot simulate the swap
+pub fn quote(e: &Env, factory: &Address, token_in: &Address, token_out: &Address, amount: i128) -> Option<Vec<i128>> {
+    let pool = pool_for(e, factory, token_in, token_out)?;
+    match PhoenixPoolClient::new(e, &pool).try_simulate_swap(token_in, &amount) {
+        Ok(Ok(simulation)) => Some(vec![e, amount, simulation.ask_amount]),
+        _ => None,
+    }
+}
+
+/// Attempts a swap of `amount` of `token_in`, held by this contract, over the Phoenix pool of
+/// the pair for at least `min_out`, returning what it did on success
+///
+/// As with the Soroswap Router, the pool pulls the input itself with a nested `transfer` from
+/// this contract, which is authorized here. The pool's default spread limit applies besides
+/// `min_out`; the output is delivered back to this contract and measured as a balance delta.
+pub fn try_swap(
+    e: &Env,
+    factory: &Address,
+    token_in: &Address,
+    token_out: &Address,
+    amount: i128,
+    min_out: i128,
+    deadline: u64,
+) -> Option<SwapResult> {
+    let pool = pool_for(e, factory, token_in, token_out)?;
+    let pool_client = PhoenixPoolClient::new(e, &pool);
+    let simulation = match pool_client.try_simulate_swap(token_in, &amount) {
+        Ok(Ok(simulation)) => simulation,
+        _ => return None,
+    };
+
+    let this = e.current_contract_address();
+    let token_out_client = token::Client::new(e, token_out);
+    let balance_before = token_out_client.balance(&this);
+
+    // Covers the next contract call only, so the pool call must follow directly
+    AuthBuilder::new(e).transfer(token_in, &this, &pool, amount).authorize();
+    match pool_client.try_swap(&this, token_in, &amount, &Some(min_out), &None, &Some(deadline), &None) {
+        Ok(Ok(_)) => Some(SwapResult {
+            amount_in: amount,
+            amount_out: token_out_client.balance(&this) - balance_before,
+            path: vec![e, token_in.clone(), token_out.clone()],
+            pairs: vec![e, pool.clone()],
+            fees: vec![e, simulation.commission_amount],
+            protocol_fee: 0,
+            venue: pool,
+        }),
+        _ => None,
+    }
+}
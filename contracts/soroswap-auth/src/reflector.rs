@@ -0,0 +1,29 @@
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+/// Asset identifier of a Reflector feed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    /// Soroban token contract
+    Stellar(Address),
+    /// Off-chain symbol such as a fiat currency
+    Other(Symbol),
+}
+
+/// One price record of a Reflector feed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    /// Price in the feed's base asset, scaled by `decimals()`
+    pub price: i128,
+    /// Unix timestamp (seconds) of the record
+    pub timestamp: u64,
+}
+
+// Interface of a Reflector (SEP-40) price oracle
+#[allow(dead_code)]
+#[contractclient(name = "ReflectorClient")]
+pub trait Reflector {
+    fn decimals(e: Env) -> u32;
+    fn lastprice(e: Env, asset: Asset) -> Option<PriceData>;
+}
@@ -0,0 +1,258 @@
+use common::{
+    auth::AuthBuilder,
+    pay::SwapResult,
+    soroswap_pair::{amount_out, reserves_toward, Reserves, SoroswapPairClient, SOROSWAP_FEE},
+};
+use soroban_sdk::{token, vec, Address, Env, Vec};
+
+use crate::soroswap_router::SoroswapRouterClient;
+use crate::storage::{get_pair_address, set_pair_address};
+use crate::{record_execution, sorted_pair};
+
+/// Amounts of each token of `path` when swapping `amount` of the first on `router`, `None` when
+/// the router cannot quote the path
+pub fn quote(e: &Env, router: &Address, path: &Vec<Address>, amount: i128) -> Option<Vec<i128>> {
+    match SoroswapRouterClient::new(e, router).try_router_get_amounts_out(&amount, path) {
+        Ok(Ok(amounts)) => Some(amounts),
+        _ => None,
+    }
+}
+
+/// Input of the first token of `path` needed to receive `amount_out` of the last on `router`,
+/// `None` when the router cannot quote the path
+pub fn quote_in(e: &Env, router: &Address, path: &Vec<Address>, amount_out: i128) -> Option<i128> {
+    match SoroswapRouterClient::new(e, router).try_router_get_amounts_in(&amount_out, path) {
+        Ok(Ok(amounts)) => amounts.first(),
+        _ => None,
+    }
+}
+
+/// Pair `router` trades `token_a` against `token_b` through, cached per router
+///
+/// The first lookup asks the router (`router_pair_for`) and stores the answer; later swaps read
+/// it from storage instead of making the cross-contract call. `None` when the router cannot
+/// resolve the pair.
+pub fn pair_for(e: &Env, router: &Address, token_a: &Address, token_b: &Address) -> Option<Address> {
+    let (token_0, token_1) = sorted_pair(token_a, token_b);
+    if let Some(pair) = get_pair_address(e, router, token_0, token_1) {
+        return Some(pair);
+    }
+    let pair = SoroswapRouterClient::new(e, router).try_router_pair_for(token_a, token_b).ok()?.ok()?;
+    set_pair_address(e, router, token_0, token_1, &pair);
+    Some(pair)
+}
+
+/// Pair of `token_in -> token_out` with its reserves; `None` when the router knows no pair or it
+/// cannot be read
+fn direct_pair_state(e: &Env, router: &Address, token_in: &Address, token_out: &Address) -> Option<(Address, Reserves)> {
+    let pair = pair_for(e, router, token_in, token_out)?;
+    let reserves = reserves_toward(e, &pair, token_in)?;
+    Some((pair, reserves))
+}
+
+/// Output of swapping `amount` of `token_in` with the pair directly, computed from its reserves
+pub fn quote_direct(e: &Env, router: &Address, token_in: &Address, token_out: &Address, amount: i128) -> Option<Vec<i128>> {
+    let (_, reserves) = direct_pair_state(e, router, token_in, token_out)?;
+    Some(vec![e, amount, amount_out(amount, reserves.reserve_in, reserves.reserve_out)?])
+}
+
+/// Swaps `amount` of `token_in`, held by this contract, with the Soroswap pair directly,
+/// skipping the router
+///
+/// The output is computed locally from the pair's reserves; the input is then transferred into
+/// the pair (a direct call, so no authorization entry is needed) and the pair's `swap` sends the
+/// output to this contract. Returns `None` only while nothing has moved: once the input sits in
+/// the pair, a failing `swap` aborts the whole invocation so the transfer is reverted with it.
+pub fn try_swap_direct(
+    e: &Env,
+    router: &Address,
+    token_in: &Address,
+    token_out: &Address,
+    amount: i128,
+    min_out: i128,
+) -> Option<SwapResult> {
+    let (pair, reserves) = direct_pair_state(e, router, token_in, token_out)?;
+    if reserves.reserve_in <= 0 || reserves.reserve_out <= 0 {
+        return None;
+    }
+    // Checked before the input moves; a fee-on-transfer shortfall is caught after settlement
+    if amount_out(amount, reserves.reserve_in, reserves.reserve_out)? < min_out {
+        return None;
+    }
+
+    // The pair prices what it actually received, which is less for fee-on-transfer tokens
+    let this = e.current_contract_address();
+    let token_in_client = token::Client::new(e, token_in);
+    let pair_before = token_in_client.balance(&pair);
+    token_in_client.transfer(&this, &pair, &amount);
+    let amount_in = token_in_client.balance(&pair) - pair_before;
+    let quoted = amount_out(amount_in, reserves.reserve_in, reserves.reserve_out).unwrap_or(0);
+    let (amount_0_out, amount_1_out) = if reserves.in_is_token_0 { (0, quoted) } else { (quoted, 0) };
+
+    let token_out_client = token::Client::new(e, token_out);
+    let balance_before = token_out_client.balance(&this);
+    SoroswapPairClient::new(e, &pair).swap(&amount_0_out, &amount_1_out, &this);
+    Some(SwapResult {
+        amount_in: amount,
+        amount_out: token_out_client.balance(&this) - balance_before,
+        path: vec![e, token_in.clone(), token_out.clone()],
+        pairs: vec![e, pair.clone()],
+        fees: vec![e, SOROSWAP_FEE.apply(amount_in)],
+        protocol_fee: 0,
+        venue: pair,
+    })
+}
+
+/// Attempts the swap on a single Soroswap router, returning what it did on success
+///
+/// Every cross-contract call before the swap is a `try_` call, so a paused, archived or
+/// incompatible router yields `None` instead of aborting the whole invocation. The swapped
+/// tokens are delivered back to this contract; the result's `amount_in` is the amount swapped.
+pub fn try_swap(
+    e: &Env,
+    router_address: &Address,
+    path: &Vec<Address>,
+    amount: i128,
+    min_out: i128,
+    deadline: u64,
+) -> Option<SwapResult> {
+    let soroswap_router_client = SoroswapRouterClient::new(e, router_address);
+    let token_in = path.first()?;
+    let token_out = path.last()?;
+
+    // The router sends the input into the pair of the first hop
+    let pair_address = pair_for(e, router_address, &token_in, &path.get(1)?)?;
+    // Resolved before any funds move, so a missing hop falls through to the next venue
+    let pairs = hop_pairs(e, router_address, path, pair_address.clone())?;
+    let token_out_client = token::Client::new(e, &token_out);
+    let balance_before = token_out_client.balance(&e.current_contract_address());
+
+    // CRITICAL: Create authorization context for the sub-contract invocation
+    // This tells the Soroban runtime: "When the router calls
+    // token.transfer(from=this contract, to=pair, amount) with these exact arguments,
+    // I (the current contract) authorize it"
+    //
+    // The builder produces a SubContractInvocation specifying:
+    // - Which contract will be called (token_in)
+    // - Which function will be invoked ("transfer")
+    // - What arguments will be passed (this contract, the pair, the amount)
+    //
+    // This creates a secure authorization chain: caller → this contract → router → token
+    //
+    // The context only covers the next contract call, so the router call must follow directly
+    AuthBuilder::new(e)
+        .transfer(&token_in, &e.current_contract_address(), &pair_address, amount)
+        .authorize();
+
+    // Execute the swap through the router
+    // The router requires auth from `to`; as the direct invoker, this contract satisfies it,
+    // and the authorization context above covers the nested token transfer
+    match soroswap_router_client.try_swap_exact_tokens_for_tokens(
+        &amount,                        // Exact amount to swap
+        &min_out,                       // Minimum amount out; a shortfall reverts and the next venue is tried
+        path,                           // Swap route
+        &e.current_contract_address(),  // Recipient of output tokens (this contract, forwarded to the caller)
+        &deadline,                      // Deadline (max unless the caller provided `deadline_delta`)
+    ) {
+        // Use what actually arrived rather than the router's figure, which overstates the
+        // output of tokens that charge a fee on transfer
+        Ok(Ok(amounts)) => {
+            let realized = token_out_client.balance(&e.current_contract_address()) - balance_before;
+            if let (2, Some(quoted)) = (path.len(), amounts.last()) {
+                record_execution(e, &token_in, &token_out, quoted, realized);
+            }
+
+            Some(SwapResult {
+                amount_in: amount,
+                amount_out: realized,
+                path: path.clone(),
+                pairs,
+                fees: hop_fees(e, path, &amounts),
+                protocol_fee: 0,
+                venue: router_address.clone(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Pair and liquidity-provider fee of each hop of `path`, given the router's `amounts`
+///
+/// The first pair is passed in since callers already resolved it; the others are looked up.
+/// Returns `None` when a hop has no pair on `router`.
+pub fn hop_pairs_and_fees(
+    e: &Env,
+    router: &Address,
+    path: &Vec<Address>,
+    first_pair: Address,
+    amounts: &Vec<i128>,
+) -> Option<(Vec<Address>, Vec<i128>)> {
+    Some((hop_pairs(e, router, path, first_pair)?, hop_fees(e, path, amounts)))
+}
+
+/// Pair of each hop of `path` on `router`, or `None` when a hop has no pair
+fn hop_pairs(e: &Env, router: &Address, path: &Vec<Address>, first_pair: Address) -> Option<Vec<Address>> {
+    let mut pairs: Vec<Address> = vec![e, first_pair];
+    for hop in 1..path.len() - 1 {
+        pairs.push_back(pair_for(e, router, &path.get_unchecked(hop), &path.get_unchecked(hop + 1))?);
+    }
+    Some(pairs)
+}
+
+/// Liquidity-provider fee of each hop of `path`, given the router's `amounts`
+fn hop_fees(e: &Env, path: &Vec<Address>, amounts: &Vec<i128>) -> Vec<i128> {
+    let mut fees: Vec<i128> = Vec::new(e);
+    for hop in 0..path.len() - 1 {
+        fees.push_back(SOROSWAP_FEE.apply(amounts.get(hop).unwrap_or(0)));
+    }
+    fees
+}
+
+/// Attempts an exact-output swap on a single Soroswap router, spending at most `amount_in`
+///
+/// `amount_in` is the input quoted by `quote_in`; only that amount is authorized for the
+/// router's transfer into the first pair. The result's `amount_in` and `amount_out` are balance
+/// deltas of this contract. `None` when the router cannot execute the swap; nothing has moved
+/// then.
+pub fn try_swap_exact_out(
+    e: &Env,
+    router_address: &Address,
+    path: &Vec<Address>,
+    amount_out: i128,
+    amount_in: i128,
+    deadline: u64,
+) -> Option<SwapResult> {
+    let token_in = path.first()?;
+    let token_out = path.last()?;
+    let pair_address = pair_for(e, router_address, &token_in, &path.get(1)?)?;
+    let pairs = hop_pairs(e, router_address, path, pair_address.clone())?;
+
+    let this = e.current_contract_address();
+    let token_in_client = token::Client::new(e, &token_in);
+    let token_out_client = token::Client::new(e, &token_out);
+    let in_before = token_in_client.balance(&this);
+    let out_before = token_out_client.balance(&this);
+
+    // Covers the next contract call only, so the router call must follow directly
+    AuthBuilder::new(e)
+        .transfer(&token_in, &this, &pair_address, amount_in)
+        .authorize();
+    match SoroswapRouterClient::new(e, router_address).try_swap_tokens_for_exact_tokens(
+        &amount_out,
+        &amount_in,
+        path,
+        &this,
+        &deadline,
+    ) {
+        Ok(Ok(amounts)) => Some(SwapResult {
+            amount_in: in_before - token_in_client.balance(&this),
+            amount_out: token_out_client.balance(&this) - out_before,
+            path: path.clone(),
+            pairs,
+            fees: hop_fees(e, path, &amounts),
+            protocol_fee: 0,
+            venue: router_address.clone(),
+        }),
+        _ => None,
+    }
+}
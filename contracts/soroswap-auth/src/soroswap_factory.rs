@@ -0,0 +1,12 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+// The factory WASM is not vendored in `contracts/wasms`, so the client is declared from the
+// subset of the Soroswap Factory interface this contract reads.
+#[allow(dead_code)]
+#[contractclient(name = "SoroswapFactoryClient")]
+pub trait SoroswapFactory {
+    fn all_pairs_length(e: Env) -> u32;
+    fn all_pairs(e: Env, n: u32) -> Address;
+    fn get_pair(e: Env, token_a: Address, token_b: Address) -> Address;
+    fn pair_exists(e: Env, token_a: Address, token_b: Address) -> bool;
+}
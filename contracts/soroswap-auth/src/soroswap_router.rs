@@ -1,3 +1,5 @@
+#![allow(clippy::too_many_arguments)]
+
 soroban_sdk::contractimport!(file = "../wasms/soroswap_router.optimized.wasm");
 
 #[allow(dead_code)]
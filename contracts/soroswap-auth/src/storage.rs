@@ -1,10 +1,175 @@
-use soroban_sdk::{contracttype, Address, Env};
+use common::math::Bps;
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec};
+
+use crate::features::ALL_FEATURES;
+use crate::types::Dex;
 
 #[derive(Clone)]
 #[contracttype]
 
 enum DataKey {
     SoroswapRouterAddress,
+    RouterWasmHash,
+    FallbackRouters,
+    Admin,
+    PendingAdmin,
+    TermsVersion,
+    AcceptedTerms(Address),
+    ReceiptsEnabled,
+    ReceiptCount,
+    Receipt(u64),
+    UserReceiptCount(Address),
+    UserReceipt(Address, u32),
+    Features,
+    Paused,
+    StandingApproval(Address),
+    RoundTripWindow,
+    LastLeg(Address, Address, Address),
+    RoundTrips(Address, Address, Address),
+    Metrics(Symbol),
+    CurrentEntrypoint,
+    MakerCollateral(Address, Address),
+    Claimable(Address, Address),
+    PairExecutions(Address, Address),
+    Tutorial,
+    ProtocolFee,
+    AccruedFees(Address),
+    ReferralShare,
+    ReferralFees(Address, Address),
+    TokenListMode,
+    AllowedToken(Address),
+    DeniedToken(Address),
+    VolumeCap(Address),
+    LedgerVolume(Address, u32),
+    DailyLimit(Address, Address),
+    DefaultDailyLimit(Address),
+    DailySpend(Address, Address),
+    OperatorAllowance(Address, Address, Address),
+    UsedOrderNonce(Address, u64),
+    Commitment(Address, BytesN<32>),
+    Oracle,
+    MaxPriceImpact,
+    PhoenixFactory,
+    AquaRouter,
+    FallbackDexes,
+    PairAddress(Address, Address, Address),
+    Route(Address, Address),
+    QuoteBudget,
+    GuaranteeQuote(Address, Address, Address),
+    OwedTotal(Address),
+}
+
+/// Compact, retrievable record of an executed operation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Receipt {
+    pub id: u64,
+    pub caller: Address,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+    pub ledger: u32,
+}
+
+/// Envelope within which the user's executor may execute swaps on their behalf
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StandingApproval {
+    /// Only address allowed to submit fills
+    pub executor: Address,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub max_per_fill: i128,
+    /// Least `token_out` per unit of `token_in` a fill accepts, scaled by `RATE_PRECISION`
+    pub min_rate: i128,
+    /// Last ledger sequence at which fills are accepted
+    pub expiry: u32,
+}
+
+/// How much of a token an operator may still swap on the owner's behalf, into what, and until when
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorAllowance {
+    /// Only token the operator may buy
+    pub token_out: Address,
+    pub remaining: i128,
+    /// Least `token_out` per unit of `token_in` a swap accepts, scaled by `RATE_PRECISION`
+    pub min_rate: i128,
+    /// Last ledger sequence at which the operator can swap
+    pub expiry: u32,
+}
+
+/// Usage counters of one entrypoint
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntrypointMetrics {
+    /// Completed calls
+    pub calls: u64,
+    /// Ledger sequence of the latest completed call
+    pub last_ledger: u32,
+    /// Failures the completed calls caught and recovered from (a venue that failed before
+    /// another served the swap, a rejected tutorial report)
+    pub failures: u64,
+    /// `SoroswapError` code of the latest caught failure
+    pub last_error: Option<u32>,
+}
+
+/// Minimum output a maker guarantees for swaps of one pair, backed by posted collateral
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuaranteeQuote {
+    /// Guaranteed `token_out` per unit of `token_in`, scaled by `RATE_PRECISION`
+    pub rate: i128,
+    /// Amount of `token_in` still covered; each guaranteed swap consumes its input
+    pub remaining_in: i128,
+    /// Largest share of the guaranteed output the maker covers; the AMM leg must deliver the rest
+    pub max_cover_bps: Bps,
+    /// Last ledger sequence at which the quote is honored
+    pub expiry: u32,
+}
+
+/// Protocol fee taken from every router-served swap and who can claim it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    /// Share of the swap taken, at most `MAX_PROTOCOL_FEE`
+    pub fee: Bps,
+    pub recipient: Address,
+}
+
+/// Amount of a token operators swapped for a user in the current 24h window
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendWindow {
+    /// Ledger timestamp at which the window opened
+    pub start: u64,
+    pub spent: i128,
+}
+
+/// Price oracle bounding swap outputs, and how far below its price a swap may execute
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleConfig {
+    /// Reflector price feed
+    pub oracle: Address,
+    /// Feed used when the primary one is stale or cannot price the pair
+    pub fallback: Option<Address>,
+    pub max_deviation: Bps,
+    /// Oldest price accepted, in seconds of ledger time
+    pub max_age: u64,
+}
+
+/// Which tokens swaps accept
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TokenListMode {
+    /// Every token
+    Open,
+    /// Only tokens added with `add_token`
+    Allowlist,
+    /// Every token except those blocked with `block_token`
+    Denylist,
 }
 
 #[allow(dead_code)]
@@ -14,12 +179,28 @@ const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
 #[allow(dead_code)]
 const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
 
+const PERSISTENT_BUMP_AMOUNT: u32 = 60 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Default window within which a swap back counts as a round trip (about one hour)
+const DEFAULT_ROUND_TRIP_WINDOW: u32 = 720;
+/// How long round-trip counters live without new round trips
+const ROUND_TRIP_COUNTER_TTL: u32 = 30 * DAY_IN_LEDGERS;
+/// Ledgers within which a committed swap must be revealed (about one hour)
+pub const COMMITMENT_TTL: u32 = 720;
+
 pub fn extend_instance_ttl(e: &Env) {
     e.storage()
         .instance()
         .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 }
 
+fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
 // Soroswap Router Address
 pub fn set_soroswap_router_address(e: &Env, address: Address) {
     e.storage()
@@ -33,3 +214,858 @@ pub fn get_soroswap_router_address(e: &Env) -> Address {
         .get(&DataKey::SoroswapRouterAddress)
         .unwrap()
 }
+
+// Wasm hash the primary router ran when it was configured
+pub fn set_router_wasm_hash(e: &Env, hash: Option<BytesN<32>>) {
+    match hash {
+        Some(hash) => e.storage().instance().set(&DataKey::RouterWasmHash, &hash),
+        None => e.storage().instance().remove(&DataKey::RouterWasmHash),
+    }
+}
+
+pub fn get_router_wasm_hash(e: &Env) -> Option<BytesN<32>> {
+    e.storage().instance().get(&DataKey::RouterWasmHash)
+}
+
+// Fallback Routers (tried in order when the primary router fails)
+pub fn set_fallback_routers(e: &Env, routers: Vec<Address>) {
+    e.storage()
+        .instance()
+        .set(&DataKey::FallbackRouters, &routers);
+}
+
+pub fn get_fallback_routers(e: &Env) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::FallbackRouters)
+        .unwrap_or(Vec::new(e))
+}
+
+// Admin Address
+pub fn set_admin(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Admin, &address);
+}
+
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap()
+}
+
+// Admin proposed by the current admin, until it accepts
+pub fn set_pending_admin(e: &Env, address: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::PendingAdmin, address);
+}
+
+pub fn get_pending_admin(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::PendingAdmin)
+}
+
+pub fn remove_pending_admin(e: &Env) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::PendingAdmin);
+}
+
+// Terms of use version (0 means no acknowledgment is required)
+pub fn set_terms_version(e: &Env, version: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TermsVersion, &version);
+}
+
+pub fn get_terms_version(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::TermsVersion)
+        .unwrap_or(0)
+}
+
+// Terms version accepted by each user
+pub fn set_accepted_terms(e: &Env, user: &Address, version: u32) {
+    let key = DataKey::AcceptedTerms(user.clone());
+    e.storage().persistent().set(&key, &version);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_accepted_terms(e: &Env, user: &Address) -> u32 {
+    let key = DataKey::AcceptedTerms(user.clone());
+    let version = e.storage().persistent().get(&key);
+    if version.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    version.unwrap_or(0)
+}
+
+// Receipt persistence toggle
+pub fn set_receipts_enabled(e: &Env, enabled: bool) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ReceiptsEnabled, &enabled);
+}
+
+pub fn get_receipts_enabled(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&DataKey::ReceiptsEnabled)
+        .unwrap_or(false)
+}
+
+// Receipts (ids are sequential, starting at 0)
+pub fn set_receipt_count(e: &Env, count: u64) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ReceiptCount, &count);
+}
+
+pub fn get_receipt_count(e: &Env) -> u64 {
+    e.storage()
+        .instance()
+        .get(&DataKey::ReceiptCount)
+        .unwrap_or(0)
+}
+
+pub fn set_receipt(e: &Env, receipt: &Receipt) {
+    let key = DataKey::Receipt(receipt.id);
+    e.storage().persistent().set(&key, receipt);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_receipt(e: &Env, id: u64) -> Option<Receipt> {
+    let key = DataKey::Receipt(id);
+    let receipt = e.storage().persistent().get(&key);
+    if receipt.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    receipt
+}
+
+// Per-user receipt index (position -> receipt id)
+pub fn set_user_receipt_count(e: &Env, user: &Address, count: u32) {
+    let key = DataKey::UserReceiptCount(user.clone());
+    e.storage().persistent().set(&key, &count);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_user_receipt_count(e: &Env, user: &Address) -> u32 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::UserReceiptCount(user.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_user_receipt(e: &Env, user: &Address, index: u32, id: u64) {
+    let key = DataKey::UserReceipt(user.clone(), index);
+    e.storage().persistent().set(&key, &id);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_user_receipt(e: &Env, user: &Address, index: u32) -> Option<u64> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::UserReceipt(user.clone(), index))
+}
+
+// Emergency pause flag
+pub fn set_paused(e: &Env, paused: bool) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Paused, &paused);
+}
+
+pub fn get_paused(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&DataKey::Paused)
+        .unwrap_or(false)
+}
+
+// Enabled features bitmask (see `features.rs`)
+pub fn set_features(e: &Env, features: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Features, &features);
+}
+
+pub fn get_features(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::Features)
+        .unwrap_or(ALL_FEATURES)
+}
+
+// Standing order approval of each user
+pub fn set_standing_approval(e: &Env, user: &Address, approval: &StandingApproval) {
+    let key = DataKey::StandingApproval(user.clone());
+    e.storage().persistent().set(&key, approval);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_standing_approval(e: &Env, user: &Address) -> Option<StandingApproval> {
+    let key = DataKey::StandingApproval(user.clone());
+    let approval = e.storage().persistent().get(&key);
+    if approval.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    approval
+}
+
+pub fn remove_standing_approval(e: &Env, user: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::StandingApproval(user.clone()));
+}
+
+// Operator allowances (owner, operator, token_in)
+pub fn set_operator_allowance(
+    e: &Env,
+    owner: &Address,
+    operator: &Address,
+    token: &Address,
+    allowance: &OperatorAllowance,
+) {
+    let key = DataKey::OperatorAllowance(owner.clone(), operator.clone(), token.clone());
+    e.storage().persistent().set(&key, allowance);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_operator_allowance(
+    e: &Env,
+    owner: &Address,
+    operator: &Address,
+    token: &Address,
+) -> Option<OperatorAllowance> {
+    let key = DataKey::OperatorAllowance(owner.clone(), operator.clone(), token.clone());
+    let allowance = e.storage().persistent().get(&key);
+    if allowance.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    allowance
+}
+
+pub fn remove_operator_allowance(e: &Env, owner: &Address, operator: &Address, token: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::OperatorAllowance(owner.clone(), operator.clone(), token.clone()));
+}
+
+// Signed-order nonces each owner has executed or cancelled
+pub fn set_order_nonce_used(e: &Env, owner: &Address, nonce: u64) {
+    let key = DataKey::UsedOrderNonce(owner.clone(), nonce);
+    e.storage().persistent().set(&key, &true);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_order_nonce_used(e: &Env, owner: &Address, nonce: u64) -> bool {
+    e.storage()
+        .persistent()
+        .has(&DataKey::UsedOrderNonce(owner.clone(), nonce))
+}
+
+// Ledger at which a caller committed to a swap hash, in temporary storage until it expires
+pub fn set_commitment(e: &Env, caller: &Address, hash: &BytesN<32>, ledger: u32) {
+    let key = DataKey::Commitment(caller.clone(), hash.clone());
+    e.storage().temporary().set(&key, &ledger);
+    e.storage()
+        .temporary()
+        .extend_ttl(&key, COMMITMENT_TTL, COMMITMENT_TTL);
+}
+
+pub fn get_commitment(e: &Env, caller: &Address, hash: &BytesN<32>) -> Option<u32> {
+    e.storage()
+        .temporary()
+        .get(&DataKey::Commitment(caller.clone(), hash.clone()))
+}
+
+pub fn remove_commitment(e: &Env, caller: &Address, hash: &BytesN<32>) {
+    e.storage()
+        .temporary()
+        .remove(&DataKey::Commitment(caller.clone(), hash.clone()));
+}
+
+// Ledger window within which a swap back counts as a round trip
+pub fn set_round_trip_window(e: &Env, ledgers: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::RoundTripWindow, &ledgers);
+}
+
+pub fn get_round_trip_window(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::RoundTripWindow)
+        .unwrap_or(DEFAULT_ROUND_TRIP_WINDOW)
+}
+
+// Ledger of a user's last swap in each direction (user, token_in, token_out), kept in
+// temporary storage for the round-trip window only
+pub fn set_last_leg(e: &Env, user: &Address, token_in: &Address, token_out: &Address, ledger: u32) {
+    let key = DataKey::LastLeg(user.clone(), token_in.clone(), token_out.clone());
+    let window = get_round_trip_window(e);
+    e.storage().temporary().set(&key, &ledger);
+    e.storage().temporary().extend_ttl(&key, window, window);
+}
+
+pub fn get_last_leg(e: &Env, user: &Address, token_in: &Address, token_out: &Address) -> Option<u32> {
+    e.storage()
+        .temporary()
+        .get(&DataKey::LastLeg(user.clone(), token_in.clone(), token_out.clone()))
+}
+
+// Round trips per user and unordered pair (user, lower token, higher token), in temporary storage
+pub fn set_round_trips(e: &Env, user: &Address, token_a: &Address, token_b: &Address, count: u32) {
+    let key = DataKey::RoundTrips(user.clone(), token_a.clone(), token_b.clone());
+    e.storage().temporary().set(&key, &count);
+    e.storage()
+        .temporary()
+        .extend_ttl(&key, ROUND_TRIP_COUNTER_TTL, ROUND_TRIP_COUNTER_TTL);
+}
+
+pub fn get_round_trips(e: &Env, user: &Address, token_a: &Address, token_b: &Address) -> u32 {
+    e.storage()
+        .temporary()
+        .get(&DataKey::RoundTrips(user.clone(), token_a.clone(), token_b.clone()))
+        .unwrap_or(0)
+}
+
+// Persistent amount entries that are removed rather than stored as `None`
+fn set_optional_amount(e: &Env, key: DataKey, amount: Option<i128>) {
+    match amount {
+        Some(amount) => {
+            e.storage().persistent().set(&key, &amount);
+            extend_persistent_ttl(e, &key);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+fn get_optional_amount(e: &Env, key: DataKey) -> Option<i128> {
+    let amount = e.storage().persistent().get(&key);
+    if amount.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    amount
+}
+
+// Most of a token swapped (sold plus bought) in a single ledger (no cap while unset)
+pub fn set_volume_cap(e: &Env, token: &Address, cap: Option<i128>) {
+    set_optional_amount(e, DataKey::VolumeCap(token.clone()), cap);
+}
+
+pub fn get_volume_cap(e: &Env, token: &Address) -> Option<i128> {
+    get_optional_amount(e, DataKey::VolumeCap(token.clone()))
+}
+
+// Volume swapped per token in a ledger, in temporary storage; each ledger starts a new entry
+pub fn set_ledger_volume(e: &Env, token: &Address, ledger: u32, volume: i128) {
+    e.storage()
+        .temporary()
+        .set(&DataKey::LedgerVolume(token.clone(), ledger), &volume);
+}
+
+pub fn get_ledger_volume(e: &Env, token: &Address, ledger: u32) -> i128 {
+    e.storage()
+        .temporary()
+        .get(&DataKey::LedgerVolume(token.clone(), ledger))
+        .unwrap_or(0)
+}
+
+// Daily caps on what operators swap for a user: the user's own, per (user, token), and the
+// admin's default, per token
+pub fn set_daily_limit(e: &Env, user: &Address, token: &Address, limit: Option<i128>) {
+    set_optional_amount(e, DataKey::DailyLimit(user.clone(), token.clone()), limit);
+}
+
+pub fn get_daily_limit(e: &Env, user: &Address, token: &Address) -> Option<i128> {
+    get_optional_amount(e, DataKey::DailyLimit(user.clone(), token.clone()))
+}
+
+pub fn set_default_daily_limit(e: &Env, token: &Address, limit: Option<i128>) {
+    set_optional_amount(e, DataKey::DefaultDailyLimit(token.clone()), limit);
+}
+
+pub fn get_default_daily_limit(e: &Env, token: &Address) -> Option<i128> {
+    get_optional_amount(e, DataKey::DefaultDailyLimit(token.clone()))
+}
+
+// Operator spending per (user, token) in the current window
+pub fn set_daily_spend(e: &Env, user: &Address, token: &Address, window: &SpendWindow) {
+    let key = DataKey::DailySpend(user.clone(), token.clone());
+    e.storage().persistent().set(&key, window);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_daily_spend(e: &Env, user: &Address, token: &Address) -> Option<SpendWindow> {
+    let key = DataKey::DailySpend(user.clone(), token.clone());
+    let window = e.storage().persistent().get(&key);
+    if window.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    window
+}
+
+// Recent execution deltas per unordered pair (lower token, higher token), oldest first
+pub fn set_pair_executions(e: &Env, token_a: &Address, token_b: &Address, deltas: &Vec<i32>) {
+    let key = DataKey::PairExecutions(token_a.clone(), token_b.clone());
+    e.storage().persistent().set(&key, deltas);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_pair_executions(e: &Env, token_a: &Address, token_b: &Address) -> Vec<i32> {
+    let key = DataKey::PairExecutions(token_a.clone(), token_b.clone());
+    let deltas = e.storage().persistent().get(&key);
+    if deltas.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    deltas.unwrap_or(Vec::new(e))
+}
+
+// Tutorial contract recording workshop steps (tutorial mode is off while unset)
+pub fn set_tutorial(e: &Env, tutorial: &Option<Address>) {
+    match tutorial {
+        Some(tutorial) => e.storage().instance().set(&DataKey::Tutorial, tutorial),
+        None => e.storage().instance().remove(&DataKey::Tutorial),
+    }
+}
+
+pub fn get_tutorial(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::Tutorial)
+}
+
+// Phoenix factory indexing the pools `swap_on` can use (Phoenix is unavailable while unset)
+pub fn set_phoenix_factory(e: &Env, factory: &Option<Address>) {
+    match factory {
+        Some(factory) => e.storage().instance().set(&DataKey::PhoenixFactory, factory),
+        None => e.storage().instance().remove(&DataKey::PhoenixFactory),
+    }
+}
+
+pub fn get_phoenix_factory(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::PhoenixFactory)
+}
+
+// Aquarius router indexing the pools `swap_on` can use (Aquarius is unavailable while unset)
+pub fn set_aqua_router(e: &Env, router: &Option<Address>) {
+    match router {
+        Some(router) => e.storage().instance().set(&DataKey::AquaRouter, router),
+        None => e.storage().instance().remove(&DataKey::AquaRouter),
+    }
+}
+
+pub fn get_aqua_router(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::AquaRouter)
+}
+
+// Most venues a best-price swap quotes (every configured venue while unset)
+pub fn set_quote_budget(e: &Env, venues: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::QuoteBudget, &venues);
+}
+
+pub fn get_quote_budget(e: &Env) -> Option<u32> {
+    e.storage()
+        .instance()
+        .get(&DataKey::QuoteBudget)
+}
+
+// Exchanges tried, in order, for direct-pair swaps every Soroswap router failed
+pub fn set_fallback_dexes(e: &Env, dexes: &Vec<Dex>) {
+    e.storage()
+        .instance()
+        .set(&DataKey::FallbackDexes, dexes);
+}
+
+pub fn get_fallback_dexes(e: &Env) -> Vec<Dex> {
+    e.storage()
+        .instance()
+        .get(&DataKey::FallbackDexes)
+        .unwrap_or(Vec::new(e))
+}
+
+// Pair address each router resolves for a (sorted) token pair, saving its `router_pair_for` call
+pub fn set_pair_address(e: &Env, router: &Address, token_a: &Address, token_b: &Address, pair: &Address) {
+    let key = DataKey::PairAddress(router.clone(), token_a.clone(), token_b.clone());
+    e.storage().persistent().set(&key, pair);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_pair_address(e: &Env, router: &Address, token_a: &Address, token_b: &Address) -> Option<Address> {
+    let key = DataKey::PairAddress(router.clone(), token_a.clone(), token_b.clone());
+    let pair = e.storage().persistent().get(&key);
+    if pair.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    pair
+}
+
+pub fn remove_pair_address(e: &Env, router: &Address, token_a: &Address, token_b: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::PairAddress(router.clone(), token_a.clone(), token_b.clone()));
+}
+
+// Preferred route from a token to another, used by swaps that supply no path
+pub fn set_route(e: &Env, token_in: &Address, token_out: &Address, path: &Vec<Address>) {
+    let key = DataKey::Route(token_in.clone(), token_out.clone());
+    e.storage().persistent().set(&key, path);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_route(e: &Env, token_in: &Address, token_out: &Address) -> Option<Vec<Address>> {
+    let key = DataKey::Route(token_in.clone(), token_out.clone());
+    let path = e.storage().persistent().get(&key);
+    if path.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    path
+}
+
+pub fn remove_route(e: &Env, token_in: &Address, token_out: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::Route(token_in.clone(), token_out.clone()));
+}
+
+// Protocol fee (no fee is taken while unset)
+pub fn set_protocol_fee(e: &Env, fee: &FeeConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ProtocolFee, fee);
+}
+
+pub fn get_protocol_fee(e: &Env) -> Option<FeeConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::ProtocolFee)
+}
+
+// Price oracle guard (no oracle check while unset)
+pub fn set_oracle(e: &Env, config: &OracleConfig) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Oracle, config);
+}
+
+pub fn remove_oracle(e: &Env) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::Oracle);
+}
+
+pub fn get_oracle(e: &Env) -> Option<OracleConfig> {
+    e.storage()
+        .instance()
+        .get(&DataKey::Oracle)
+}
+
+// Largest price impact a swap may have (no check while unset)
+pub fn set_max_price_impact(e: &Env, max_impact: Bps) {
+    e.storage()
+        .instance()
+        .set(&DataKey::MaxPriceImpact, &max_impact);
+}
+
+pub fn get_max_price_impact(e: &Env) -> Option<Bps> {
+    e.storage()
+        .instance()
+        .get(&DataKey::MaxPriceImpact)
+}
+
+// Footprint estimates of the persistent entries created by user operations. Sizes are the XDR
+// length of key and value for a placeholder user, which is what rent is charged on.
+fn entry_size<V: ToXdr>(e: &Env, key: DataKey, value: V) -> u32 {
+    key.to_xdr(e).len() + value.to_xdr(e).len()
+}
+
+/// Size of the terms acceptance entry created on a user's first `accept_terms`
+pub fn accepted_terms_entry_size(e: &Env) -> u32 {
+    let user = e.current_contract_address();
+    entry_size(e, DataKey::AcceptedTerms(user), 0_u32)
+}
+
+/// Size of the receipt, user index and (first receipt only) user count entries of a swap
+pub fn receipt_entries_size(e: &Env) -> u32 {
+    let user = e.current_contract_address();
+    let receipt = Receipt {
+        id: 0,
+        caller: user.clone(),
+        token_in: user.clone(),
+        token_out: user.clone(),
+        amount_in: 0,
+        amount_out: 0,
+        ledger: 0,
+    };
+    entry_size(e, DataKey::Receipt(0), receipt)
+        + entry_size(e, DataKey::UserReceipt(user.clone(), 0), 0_u64)
+        + entry_size(e, DataKey::UserReceiptCount(user), 0_u32)
+}
+
+/// Size of the cached pair address and execution window entries of a pair's first swap
+pub fn pair_entries_size(e: &Env) -> u32 {
+    let token = e.current_contract_address();
+    entry_size(e, DataKey::PairAddress(token.clone(), token.clone(), token.clone()), token.clone())
+        + entry_size(e, DataKey::PairExecutions(token.clone(), token), Vec::from_array(e, [0_i32]))
+}
+
+/// Size of the accrued fee entry of the first protocol fee taken in a token
+pub fn accrued_fees_entry_size(e: &Env) -> u32 {
+    entry_size(e, DataKey::AccruedFees(e.current_contract_address()), 0_i128)
+}
+
+/// Size of the daily spend entry of a user's first operator swap of a token
+pub fn daily_spend_entry_size(e: &Env) -> u32 {
+    let user = e.current_contract_address();
+    let window = SpendWindow { start: 0, spent: 0 };
+    entry_size(e, DataKey::DailySpend(user.clone(), user), window)
+}
+
+/// Size of the used-nonce entry of an executed signed order
+pub fn order_nonce_entry_size(e: &Env) -> u32 {
+    entry_size(e, DataKey::UsedOrderNonce(e.current_contract_address(), 0), true)
+}
+
+/// Size of the standing approval entry created by `approve_standing`
+pub fn standing_approval_entry_size(e: &Env) -> u32 {
+    let user = e.current_contract_address();
+    let approval = StandingApproval {
+        executor: user.clone(),
+        token_in: user.clone(),
+        token_out: user.clone(),
+        max_per_fill: 0,
+        min_rate: 0,
+        expiry: 0,
+    };
+    entry_size(e, DataKey::StandingApproval(user), approval)
+}
+
+/// Size of the allowance entry created by a first `approve_operator` per operator and token
+pub fn operator_allowance_entry_size(e: &Env) -> u32 {
+    let user = e.current_contract_address();
+    let allowance = OperatorAllowance {
+        token_out: user.clone(),
+        remaining: 0,
+        min_rate: 0,
+        expiry: 0,
+    };
+    entry_size(e, DataKey::OperatorAllowance(user.clone(), user.clone(), user), allowance)
+}
+
+// Usage counters of each user-facing entrypoint, in instance storage: one small entry per
+// entrypoint, living as long as the contract
+pub fn set_metrics(e: &Env, entrypoint: &Symbol, metrics: &EntrypointMetrics) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Metrics(entrypoint.clone()), metrics);
+}
+
+pub fn get_metrics(e: &Env, entrypoint: &Symbol) -> Option<EntrypointMetrics> {
+    e.storage()
+        .instance()
+        .get(&DataKey::Metrics(entrypoint.clone()))
+}
+
+// Entrypoint of the call in progress, which caught failures are counted against
+pub fn set_current_entrypoint(e: &Env, entrypoint: &Symbol) {
+    e.storage()
+        .instance()
+        .set(&DataKey::CurrentEntrypoint, entrypoint);
+}
+
+pub fn get_current_entrypoint(e: &Env) -> Option<Symbol> {
+    e.storage()
+        .instance()
+        .get(&DataKey::CurrentEntrypoint)
+}
+
+// Token balance this contract owes to users (claimable outputs, referral fees and maker
+// collateral), kept up to date by their setters
+fn add_owed_total(e: &Env, token: &Address, previous: Option<i128>, amount: i128) {
+    let delta = amount - previous.unwrap_or(0);
+    if delta != 0 {
+        let key = DataKey::OwedTotal(token.clone());
+        e.storage().persistent().set(&key, &(get_owed_total(e, token) + delta));
+        extend_persistent_ttl(e, &key);
+    }
+}
+
+pub fn get_owed_total(e: &Env, token: &Address) -> i128 {
+    let key = DataKey::OwedTotal(token.clone());
+    let amount = e.storage().persistent().get(&key);
+    if amount.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    amount.unwrap_or(0)
+}
+
+// Pull-delivered outputs each user can withdraw, per token
+pub fn set_claimable(e: &Env, user: &Address, token: &Address, amount: i128) {
+    let key = DataKey::Claimable(user.clone(), token.clone());
+    add_owed_total(e, token, e.storage().persistent().get(&key), amount);
+    e.storage().persistent().set(&key, &amount);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_claimable(e: &Env, user: &Address, token: &Address) -> i128 {
+    let key = DataKey::Claimable(user.clone(), token.clone());
+    let amount = e.storage().persistent().get(&key);
+    if amount.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    amount.unwrap_or(0)
+}
+
+// Protocol fees held by this contract until the fee recipient claims them, per token
+pub fn set_accrued_fees(e: &Env, token: &Address, amount: i128) {
+    let key = DataKey::AccruedFees(token.clone());
+    e.storage().persistent().set(&key, &amount);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_accrued_fees(e: &Env, token: &Address) -> i128 {
+    let key = DataKey::AccruedFees(token.clone());
+    let amount = e.storage().persistent().get(&key);
+    if amount.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    amount.unwrap_or(0)
+}
+
+// Share of the protocol fee credited to the referrer of a swap (none while unset)
+pub fn set_referral_share(e: &Env, share: Bps) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ReferralShare, &share);
+}
+
+pub fn get_referral_share(e: &Env) -> Bps {
+    e.storage()
+        .instance()
+        .get(&DataKey::ReferralShare)
+        .unwrap_or(Bps::ZERO)
+}
+
+// Referral fees each referrer can claim, per token
+pub fn set_referral_fees(e: &Env, referrer: &Address, token: &Address, amount: i128) {
+    let key = DataKey::ReferralFees(referrer.clone(), token.clone());
+    add_owed_total(e, token, e.storage().persistent().get(&key), amount);
+    e.storage().persistent().set(&key, &amount);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_referral_fees(e: &Env, referrer: &Address, token: &Address) -> i128 {
+    let key = DataKey::ReferralFees(referrer.clone(), token.clone());
+    let amount = e.storage().persistent().get(&key);
+    if amount.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    amount.unwrap_or(0)
+}
+
+// Token list enforced on swaps (both lists are kept, only the mode's one applies)
+pub fn set_token_list_mode(e: &Env, mode: TokenListMode) {
+    e.storage()
+        .instance()
+        .set(&DataKey::TokenListMode, &mode);
+}
+
+pub fn get_token_list_mode(e: &Env) -> TokenListMode {
+    e.storage()
+        .instance()
+        .get(&DataKey::TokenListMode)
+        .unwrap_or(TokenListMode::Open)
+}
+
+pub fn set_token_allowed(e: &Env, token: &Address) {
+    let key = DataKey::AllowedToken(token.clone());
+    e.storage().persistent().set(&key, &true);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn remove_token_allowed(e: &Env, token: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::AllowedToken(token.clone()));
+}
+
+pub fn get_token_allowed(e: &Env, token: &Address) -> bool {
+    let key = DataKey::AllowedToken(token.clone());
+    let allowed = e.storage().persistent().has(&key);
+    if allowed {
+        extend_persistent_ttl(e, &key);
+    }
+    allowed
+}
+
+pub fn set_token_denied(e: &Env, token: &Address) {
+    let key = DataKey::DeniedToken(token.clone());
+    e.storage().persistent().set(&key, &true);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn remove_token_denied(e: &Env, token: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::DeniedToken(token.clone()));
+}
+
+pub fn get_token_denied(e: &Env, token: &Address) -> bool {
+    let key = DataKey::DeniedToken(token.clone());
+    let denied = e.storage().persistent().has(&key);
+    if denied {
+        extend_persistent_ttl(e, &key);
+    }
+    denied
+}
+
+// Collateral each maker posted, per token
+pub fn set_maker_collateral(e: &Env, maker: &Address, token: &Address, amount: i128) {
+    let key = DataKey::MakerCollateral(maker.clone(), token.clone());
+    add_owed_total(e, token, e.storage().persistent().get(&key), amount);
+    e.storage().persistent().set(&key, &amount);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_maker_collateral(e: &Env, maker: &Address, token: &Address) -> i128 {
+    let key = DataKey::MakerCollateral(maker.clone(), token.clone());
+    let amount = e.storage().persistent().get(&key);
+    if amount.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    amount.unwrap_or(0)
+}
+
+// Guarantee quote of each maker for each (token_in, token_out) pair
+pub fn set_guarantee_quote(e: &Env, maker: &Address, token_in: &Address, token_out: &Address, quote: &GuaranteeQuote) {
+    let key = DataKey::GuaranteeQuote(maker.clone(), token_in.clone(), token_out.clone());
+    e.storage().persistent().set(&key, quote);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_guarantee_quote(e: &Env, maker: &Address, token_in: &Address, token_out: &Address) -> Option<GuaranteeQuote> {
+    let key = DataKey::GuaranteeQuote(maker.clone(), token_in.clone(), token_out.clone());
+    let quote = e.storage().persistent().get(&key);
+    if quote.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    quote
+}
+
+pub fn remove_guarantee_quote(e: &Env, maker: &Address, token_in: &Address, token_out: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::GuaranteeQuote(maker.clone(), token_in.clone(), token_out.clone()));
+}
@@ -0,0 +1,18 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+// Interface of a direct-proxy swap contract such as `soroswap-simple`
+#[allow(dead_code)]
+#[contractclient(name = "SwapProxyClient")]
+pub trait SwapProxy {
+    fn router(e: Env) -> Address;
+    fn swap(
+        e: Env,
+        caller: Address,
+        token_in: Address,
+        token_out: Address,
+        amount: i128,
+        amount_out_min: i128,
+        deadline: u64,
+        deadline_delta: Option<u32>,
+    ) -> i128;
+}
@@ -0,0 +1,182 @@
+extern crate std;
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype,
+    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    token, vec,
+    Address, Env, IntoVal, Vec,
+};
+
+use common::auth::AuthBuilder;
+
+use crate::{SoroswapAuth, SoroswapAuthClient};
+
+#[contracttype]
+enum MockKey {
+    Pair,
+    Router,
+}
+
+/// Router paying out 1:1, with the same `require_auth` calls as the Soroswap Router
+#[contract]
+struct MockRouter;
+
+#[contractimpl]
+impl MockRouter {
+    pub fn __constructor(e: Env, pair: Address) {
+        e.storage().instance().set(&MockKey::Pair, &pair);
+    }
+
+    pub fn router_pair_for(e: Env, _token_a: Address, _token_b: Address) -> Address {
+        e.storage().instance().get(&MockKey::Pair).unwrap()
+    }
+
+    pub fn swap_exact_tokens_for_tokens(
+        e: Env,
+        amount_in: i128,
+        _amount_out_min: i128,
+        path: Vec<Address>,
+        to: Address,
+        _deadline: u64,
+    ) -> Vec<i128> {
+        to.require_auth();
+        let pair: Address = e.storage().instance().get(&MockKey::Pair).unwrap();
+        token::Client::new(&e, &path.get(0).unwrap()).transfer(&to, &pair, &amount_in);
+        token::Client::new(&e, &path.get(1).unwrap()).transfer(&e.current_contract_address(), &to, &amount_in);
+        vec![&e, amount_in, amount_in]
+    }
+}
+
+/// Direct-proxy swap contract in the shape of `soroswap-simple`
+#[contract]
+struct MockProxy;
+
+#[contractimpl]
+impl MockProxy {
+    pub fn __constructor(e: Env, router: Address) {
+        e.storage().instance().set(&MockKey::Router, &router);
+    }
+
+    pub fn router(e: Env) -> Address {
+        e.storage().instance().get(&MockKey::Router).unwrap()
+    }
+
+    pub fn swap(
+        e: Env,
+        caller: Address,
+        token_in: Address,
+        token_out: Address,
+        amount: i128,
+        amount_out_min: i128,
+        deadline: u64,
+        _deadline_delta: Option<u32>,
+    ) -> i128 {
+        caller.require_auth();
+        let path = vec![&e, token_in, token_out];
+        MockRouterClient::new(&e, &Self::router(e.clone()))
+            .swap_exact_tokens_for_tokens(&amount, &amount_out_min, &path, &caller, &deadline)
+            .get(1)
+            .unwrap()
+    }
+}
+
+/// Swaps through the proxy authorizing only the transfer into the pair, as a single-level entry
+#[contract]
+struct SingleLevelCaller;
+
+#[contractimpl]
+impl SingleLevelCaller {
+    pub fn swap(e: Env, proxy: Address, pair: Address, token_in: Address, token_out: Address, amount: i128) -> i128 {
+        let this = e.current_contract_address();
+        AuthBuilder::new(&e).transfer(&token_in, &this, &pair, amount).authorize();
+        MockProxyClient::new(&e, &proxy).swap(&this, &token_in, &token_out, &amount, &0, &0, &None)
+    }
+}
+
+struct Setup {
+    e: Env,
+    contract: Address,
+    proxy: Address,
+    pair: Address,
+    token_in: Address,
+    token_out: Address,
+}
+
+fn setup() -> Setup {
+    let e = Env::default();
+    let pair = Address::generate(&e);
+    let router = e.register(MockRouter, (pair.clone(),));
+    let proxy = e.register(MockProxy, (router.clone(),));
+    let contract = e.register(SoroswapAuth, (Address::generate(&e), router.clone(), Vec::<Address>::new(&e)));
+
+    let token_admin = Address::generate(&e);
+    let token_in = e.register_stellar_asset_contract_v2(token_admin.clone()).address();
+    let token_out = e.register_stellar_asset_contract_v2(token_admin).address();
+    e.mock_all_auths();
+    token::StellarAssetClient::new(&e, &token_out).mint(&router, &1_000_000);
+
+    Setup { e, contract, proxy, pair, token_in, token_out }
+}
+
+#[test]
+fn swap_via_proxy_authorizes_nested_router_call() {
+    let s = setup();
+    let caller = Address::generate(&s.e);
+    let amount = 1_000_i128;
+    token::StellarAssetClient::new(&s.e, &s.token_in).mint(&caller, &amount);
+
+    // Only the user's own signature is mocked: the contract's authorization of the router call
+    // and its nested transfer must come from the entries `swap_via_proxy` builds
+    s.e.mock_auths(&[MockAuth {
+        address: &caller,
+        invoke: &MockAuthInvoke {
+            contract: &s.contract,
+            fn_name: "swap_via_proxy",
+            args: (
+                caller.clone(),
+                s.proxy.clone(),
+                s.token_in.clone(),
+                s.token_out.clone(),
+                amount,
+                amount,
+                None::<u64>,
+                Some(100_u32),
+            )
+                .into_val(&s.e),
+            sub_invokes: &[MockAuthInvoke {
+                contract: &s.token_in,
+                fn_name: "transfer",
+                args: (caller.clone(), s.contract.clone(), amount).into_val(&s.e),
+                sub_invokes: &[],
+            }],
+        },
+    }]);
+
+    let client = SoroswapAuthClient::new(&s.e, &s.contract);
+    let received = client.swap_via_proxy(&caller, &s.proxy, &s.token_in, &s.token_out, &amount, &amount, &None, &Some(100));
+
+    assert_eq!(received, amount);
+    assert_eq!(token::Client::new(&s.e, &s.token_out).balance(&caller), amount);
+    assert_eq!(token::Client::new(&s.e, &s.token_in).balance(&s.pair), amount);
+}
+
+#[test]
+fn single_level_entry_fails_at_router_auth() {
+    let s = setup();
+    let amount = 1_000_i128;
+    let caller = s.e.register(SingleLevelCaller, ());
+    token::StellarAssetClient::new(&s.e, &s.token_in).mint(&caller, &amount);
+    s.e.set_auths(&[]);
+
+    let result = SingleLevelCallerClient::new(&s.e, &caller).try_swap(
+        &s.proxy,
+        &s.pair,
+        &s.token_in,
+        &s.token_out,
+        &amount,
+    );
+
+    // Rejected by the router's `to.require_auth()`: no entry covers the router call itself
+    assert!(result.is_err());
+    assert_eq!(token::Client::new(&s.e, &s.token_in).balance(&caller), amount);
+}
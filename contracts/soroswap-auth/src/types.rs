@@ -0,0 +1,229 @@
+use common::{math::Bps, pay::SwapResult};
+use soroban_sdk::{contracttype, Address, Symbol, Val, Vec};
+
+use crate::storage::{FeeConfig, OracleConfig, Receipt, StandingApproval, TokenListMode};
+
+/// Tokens and current reserves of a Soroswap pair
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PairReserves {
+    pub pair: Address,
+    pub token_0: Address,
+    pub token_1: Address,
+    pub reserve_0: i128,
+    pub reserve_1: i128,
+}
+
+/// Realized output against the router's quoted output over a pair's recent direct swaps
+///
+/// Deltas are `(realized - quoted) / quoted` in basis points; negative means the swap delivered
+/// less than quoted (e.g. fee-on-transfer tokens).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PairQuality {
+    /// Swaps in the window
+    pub samples: u32,
+    /// Swaps that delivered less than quoted
+    pub negative_samples: u32,
+    pub mean_delta_bps: i32,
+    pub worst_delta_bps: i32,
+}
+
+/// One page of a user's receipts, newest first
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HistoryPage {
+    pub receipts: Vec<Receipt>,
+    /// Cursor of the next (older) page, `None` once the oldest receipt has been returned
+    pub next_cursor: Option<u32>,
+}
+
+/// Arguments shared by every swap entrypoint
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapParams {
+    pub caller: Address,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub amount: i128,
+    /// Latest ledger timestamp at which the swap may execute
+    pub deadline: Option<u64>,
+    pub deadline_delta: Option<u32>,
+}
+
+/// How a swap's output reaches the caller
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PushOrPull {
+    /// Transferred to the caller right away
+    Push,
+    /// Credited to the caller's claimable balance, withdrawn later with `withdraw_claimable`
+    Pull,
+}
+
+/// One swap of a `swap_batch`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapRequest {
+    pub token_in: Address,
+    pub token_out: Address,
+    /// Amount of `token_in` to swap
+    pub amount: i128,
+    /// Least `token_out` this swap accepts, net of the protocol fee
+    pub amount_out_min: i128,
+}
+
+/// Swap the owner signs off-chain for a relayer to submit with `execute_order`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapOrder {
+    pub owner: Address,
+    pub token_in: Address,
+    pub token_out: Address,
+    /// Amount of `token_in` to swap
+    pub amount_in: i128,
+    /// Least `token_out` the owner accepts
+    pub min_amount_out: i128,
+    /// Owner-chosen identifier; each nonce executes at most once
+    pub nonce: u64,
+    /// Last ledger sequence at which the order can execute
+    pub expiry: u32,
+}
+
+/// Swap hidden behind a `commit` hash until `reveal`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommittedSwap {
+    pub token_in: Address,
+    pub token_out: Address,
+    /// Amount of `token_in` to swap
+    pub amount: i128,
+    /// Least `token_out` the caller accepts
+    pub min_out: i128,
+}
+
+/// Preview of a swap, as returned by `simulate_swap`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapSimulation {
+    /// Router that would serve the swap
+    pub router: Address,
+    pub path: Vec<Address>,
+    /// Pair of each hop
+    pub pairs: Vec<Address>,
+    pub amount_in: i128,
+    /// Expected output, net of `protocol_fee`
+    pub amount_out: i128,
+    /// Liquidity-provider fee of each hop, in the hop's input token
+    pub fees: Vec<i128>,
+    /// Protocol fee, in the last token of `path`
+    pub protocol_fee: i128,
+    /// Price impact against the pools' spot prices in basis points, when it can be measured
+    pub price_impact: Option<u32>,
+}
+
+/// Exchange a `swap_on` is executed on
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Dex {
+    /// Soroswap, through the configured routers
+    Soroswap,
+    /// Soroswap, swapping with the pair contract directly instead of through the router, for
+    /// direct pairs
+    SoroswapDirect,
+    /// Phoenix, through the pool the configured factory lists for the pair
+    Phoenix,
+    /// Aquarius, through the best-quoting constant-product pool the configured router lists
+    /// for the pair
+    Aqua,
+    /// Whichever configured exchange quotes the highest output
+    Best,
+}
+
+/// One leg of a `swap_split`, executed on its own venue with its share of the input
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitLeg {
+    pub dex: Dex,
+    /// Optional multi-hop route, as for `swap`; only Soroswap serves multi-hop legs
+    pub path: Option<Vec<Address>>,
+    /// Share of the input swapped by this leg
+    pub weight: Bps,
+}
+
+/// What a `swap_split` did
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitSwapResult {
+    /// Output of all legs combined, net of protocol fees
+    pub amount_out: i128,
+    /// Result of each leg, in leg order
+    pub legs: Vec<SwapResult>,
+}
+
+/// Operation whose required authorization is described by `required_auth`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AuthOperation {
+    /// Swap parameters, the minimum output, the optional multi-hop path and the optional referrer
+    Swap(SwapParams, i128, Option<Vec<Address>>, Option<Address>),
+    /// Swap parameters, the `(recipient, share in bps)` pairs and the minimum total output
+    SwapAndDistribute(SwapParams, Vec<(Address, Bps)>, i128),
+    /// Swap parameters, the proxy routed through and the minimum output
+    SwapViaProxy(SwapParams, Address, i128),
+    /// Swap parameters, the minimum output and the exchange swapped on
+    SwapOn(SwapParams, i128, Dex),
+    /// Swap parameters, the legs and the combined minimum output
+    SwapSplit(SwapParams, Vec<SplitLeg>, i128),
+    /// Approving user and the standing approval to store
+    ApproveStanding(Address, StandingApproval),
+    /// Signed order a relayer submits
+    ExecuteOrder(SwapOrder),
+}
+
+/// One invocation the user's signature must cover
+///
+/// The specs are the tree flattened in depth-first order: a spec with `depth` 1 is a
+/// sub-invocation of the closest preceding spec with `depth` 0.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthSpec {
+    pub contract: Address,
+    pub fn_name: Symbol,
+    pub args: Vec<Val>,
+    pub depth: u32,
+}
+
+/// Persistent state an operation creates, for budgeting state rent
+///
+/// Reports the worst case: a first-time user creating every entry.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RentEstimate {
+    pub new_entries: u32,
+    /// Approximate XDR size of the new entries (keys and values)
+    pub approx_bytes: u32,
+}
+
+/// Configuration change applied by `admin_batch`, setting the named value
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdminOp {
+    Router(Address),
+    FallbackRouters(Vec<Address>),
+    Features(u32),
+    TermsVersion(u32),
+    ReceiptsEnabled(bool),
+    RoundTripWindow(u32),
+    Tutorial(Option<Address>),
+    Paused(bool),
+    Fee(FeeConfig),
+    ReferralShare(Bps),
+    TokenListMode(TokenListMode),
+    Oracle(OracleConfig),
+    MaxPriceImpact(Bps),
+    PhoenixFactory(Option<Address>),
+    AquaRouter(Option<Address>),
+    QuoteBudget(u32),
+    FallbackDexes(Vec<Dex>),
+}
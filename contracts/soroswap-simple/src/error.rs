@@ -17,5 +17,7 @@ pub enum SoroswapError {
     /// Invalid parameters
     InvalidArgument = 6,
 
-    NegativeNotAllowed= 7
+    NegativeNotAllowed= 7,
+    /// The caller's swap deadline has already passed
+    DeadlineExpired = 8,
 }
@@ -0,0 +1,26 @@
+use soroban_sdk::{contractevent, Address};
+
+/// Emitted when the admin replaces the Soroswap Router
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RouterUpdated {
+    pub router: Address,
+}
+
+/// Emitted when the admin proposes its successor
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposed {
+    #[topic]
+    pub admin: Address,
+    pub pending_admin: Address,
+}
+
+/// Emitted when the proposed admin accepts and takes over
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminAccepted {
+    #[topic]
+    pub previous_admin: Address,
+    pub admin: Address,
+}
@@ -1,4 +1,7 @@
 #![no_std]
+// `swap` takes more arguments than clippy allows by default, and the argument helpers
+// `contractimpl` generates for it cannot carry a per-function allow
+#![allow(clippy::too_many_arguments)]
 //! # Soroswap Simple - Direct Proxy Pattern
 //!
 //! This contract demonstrates the **direct proxy pattern** for integrating with Soroswap Router.
@@ -13,7 +16,16 @@
 //! The user's signature (`caller.require_auth()`) authorizes the Soroswap Router to transfer
 //! tokens directly from the user's account. This contract simply orchestrates the call but
 //! never takes custody of tokens, so no additional auth context is required.
+//!
+//! ## Administration:
+//! The admin set at deployment can point the contract at a new router with `set_router`, e.g.
+//! after a Soroswap upgrade. The admin role moves in two steps (`propose_admin`, then
+//! `accept_admin` by the new admin).
 
+use common::{
+    deadline,
+    soroswap_pair::{amount_out, reserves_toward},
+};
 use soroban_sdk::{
     contract, contractimpl, Address, Env, Vec,
 };
@@ -21,12 +33,15 @@ use soroban_sdk::{
 mod soroswap_router;
 mod storage;
 mod error;
+mod events;
 
 use soroswap_router::SoroswapRouterClient;
 use storage::{
-    extend_instance_ttl, get_soroswap_router_address, set_soroswap_router_address,
+    extend_instance_ttl, get_admin, get_pending_admin, get_soroswap_router_address,
+    remove_pending_admin, set_admin, set_pending_admin, set_soroswap_router_address,
 };
 use error::SoroswapError;
+use events::{AdminAccepted, AdminProposed, RouterUpdated};
 
 /// Validates that the amount is non-negative
 ///
@@ -39,18 +54,107 @@ pub fn check_nonnegative_amount(amount: i128) -> Result<(), SoroswapError> {
     }
 }
 
+/// Resolves the deadline passed to the Soroswap Router (see `common::deadline`)
+///
+/// `deadline` is the caller's ledger timestamp in seconds; a `deadline_delta` in ledgers from now
+/// can tighten it, and the earlier of the two applies. The router would reject an expired
+/// deadline too; checking it first returns a clear error instead of a failed cross-contract call.
+pub fn resolve_deadline(e: &Env, deadline: u64, deadline_delta: Option<u32>) -> Result<u64, SoroswapError> {
+    if deadline::is_expired(e, deadline) {
+        return Err(SoroswapError::DeadlineExpired);
+    }
+    let resolved = deadline::resolve_deadline(e, deadline_delta).ok_or(SoroswapError::InvalidArgument)?;
+    Ok(deadline.min(resolved))
+}
+
 #[contract]
 struct SoroswapSimple;
 
 #[contractimpl]
 impl SoroswapSimple {
-    /// Initialize the contract with the Soroswap Router address
+    /// Initialize the contract with the admin and the Soroswap Router address
     ///
-    /// This address is stored and used for all subsequent swap operations
-    pub fn __constructor(e: Env, router_address: Address) {
+    /// The router address is stored and used for all subsequent swap operations until the admin
+    /// replaces it
+    pub fn __constructor(e: Env, admin: Address, router_address: Address) {
+        set_admin(&e, admin);
         set_soroswap_router_address(&e, router_address);
     }
 
+    /// Replace the Soroswap Router address (admin only)
+    pub fn set_router(e: Env, router_address: Address) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_soroswap_router_address(&e, router_address.clone());
+
+        RouterUpdated { router: router_address }.publish(&e);
+    }
+
+    /// Admin allowed to replace the router
+    pub fn admin(e: Env) -> Address {
+        get_admin(&e)
+    }
+
+    /// Propose a new admin (admin only)
+    ///
+    /// Nothing changes until the proposed address calls `accept_admin`, so a mistyped address
+    /// cannot lock the admin out; proposing again replaces the pending proposal.
+    pub fn propose_admin(e: Env, new_admin: Address) {
+        let admin = get_admin(&e);
+        admin.require_auth();
+        extend_instance_ttl(&e);
+        set_pending_admin(&e, &new_admin);
+
+        AdminProposed {
+            admin,
+            pending_admin: new_admin,
+        }
+        .publish(&e);
+    }
+
+    /// Take over as admin (proposed admin only)
+    pub fn accept_admin(e: Env) -> Result<(), SoroswapError> {
+        let pending_admin = get_pending_admin(&e).ok_or(SoroswapError::Unauthorized)?;
+        pending_admin.require_auth();
+        extend_instance_ttl(&e);
+
+        let previous_admin = get_admin(&e);
+        set_admin(&e, pending_admin.clone());
+        remove_pending_admin(&e);
+
+        AdminAccepted {
+            previous_admin,
+            admin: pending_admin,
+        }
+        .publish(&e);
+        Ok(())
+    }
+
+    /// Admin proposed by `propose_admin` and not accepted yet
+    pub fn pending_admin(e: Env) -> Option<Address> {
+        get_pending_admin(&e)
+    }
+
+    /// The Soroswap Router address this contract swaps through
+    pub fn router(e: Env) -> Address {
+        get_soroswap_router_address(&e)
+    }
+
+    /// Expected `token_out` for swapping `amount` of `token_in` over the direct pair
+    ///
+    /// Read-only. Computed from the pair's reserves with the router's formula, which costs a
+    /// pair read instead of a router quote. Fails with `InvalidArgument` when the pair does not
+    /// exist or holds no liquidity.
+    pub fn quote(e: Env, token_in: Address, token_out: Address, amount: i128) -> Result<i128, SoroswapError> {
+        check_nonnegative_amount(amount)?;
+        let pair = SoroswapRouterClient::new(&e, &get_soroswap_router_address(&e))
+            .try_router_pair_for(&token_in, &token_out)
+            .map_err(|_| SoroswapError::InvalidArgument)?
+            .map_err(|_| SoroswapError::InvalidArgument)?;
+        let reserves = reserves_toward(&e, &pair, &token_in).ok_or(SoroswapError::InvalidArgument)?;
+        amount_out(amount, reserves.reserve_in, reserves.reserve_out).ok_or(SoroswapError::InvalidArgument)
+    }
+
     /// Execute a token swap via Soroswap Router as a direct proxy
     ///
     /// ## Authorization Flow:
@@ -63,13 +167,28 @@ impl SoroswapSimple {
     /// - `token_in`: Token being sold
     /// - `token_out`: Token being purchased
     /// - `amount`: Amount of `token_in` to swap
+    /// - `amount_out_min`: Least `token_out` the caller accepts; the router reverts below it
+    /// - `deadline`: Latest ledger timestamp at which the swap may execute, so a delayed
+    ///   transaction cannot fill at a stale price
+    /// - `deadline_delta`: Optional tighter deadline, in ledgers from now
     ///
     /// ## Returns:
     /// Amount of `token_out` received from the swap
-    pub fn swap(e: Env, caller: Address, token_in: Address, token_out: Address, amount: i128) -> Result<i128, SoroswapError> {
+    pub fn swap(
+        e: Env,
+        caller: Address,
+        token_in: Address,
+        token_out: Address,
+        amount: i128,
+        amount_out_min: i128,
+        deadline: u64,
+        deadline_delta: Option<u32>,
+    ) -> Result<i128, SoroswapError> {
         // Verify the caller has signed this transaction
         caller.require_auth();
         check_nonnegative_amount(amount)?;
+        check_nonnegative_amount(amount_out_min)?;
+        let deadline = resolve_deadline(&e, deadline, deadline_delta)?;
         extend_instance_ttl(&e);
 
         // Get the stored Soroswap Router address and create client
@@ -85,11 +204,11 @@ impl SoroswapSimple {
         // The caller's signature authorizes the router to transfer tokens directly
         // from their account - this contract never takes custody
         let swap_result = soroswap_router_client.swap_exact_tokens_for_tokens(
-            &amount,     // Exact amount to swap
-            &0,          // Minimum amount out (0 for simplicity; use slippage calculation in production)
-            &path,       // Swap route
-            &caller,     // Recipient of output tokens (same as sender in this case)
-            &u64::MAX,   // Deadline (max for simplicity; use actual timestamp in production)
+            &amount,         // Exact amount to swap
+            &amount_out_min, // Minimum amount out, protecting the caller from slippage
+            &path,           // Swap route
+            &caller,         // Recipient of output tokens (same as sender in this case)
+            &deadline,       // Earlier of the caller's deadline and `deadline_delta`
         );
 
         // Return the amount of token_out received
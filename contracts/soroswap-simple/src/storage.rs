@@ -5,6 +5,8 @@ use soroban_sdk::{contracttype, Address, Env};
 
 enum DataKey {
     SoroswapRouterAddress,
+    Admin,
+    PendingAdmin,
 }
 
 #[allow(dead_code)]
@@ -33,3 +35,36 @@ pub fn get_soroswap_router_address(e: &Env) -> Address {
         .get(&DataKey::SoroswapRouterAddress)
         .unwrap()
 }
+
+// Admin Address (may replace the router)
+pub fn set_admin(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Admin, &address);
+}
+
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap()
+}
+
+// Admin proposed by the current admin, until it accepts
+pub fn set_pending_admin(e: &Env, address: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::PendingAdmin, address);
+}
+
+pub fn get_pending_admin(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::PendingAdmin)
+}
+
+pub fn remove_pending_admin(e: &Env) {
+    e.storage()
+        .instance()
+        .remove(&DataKey::PendingAdmin);
+}
@@ -0,0 +1,13 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TokenListError {
+    /// Invalid parameters
+    InvalidArgument = 1,
+    /// Risk flags outside `ALL_RISK_FLAGS`
+    UnknownRiskFlag = 2,
+    /// Removal of a token that is not listed
+    NotListed = 3,
+}
@@ -0,0 +1,11 @@
+use soroban_sdk::contractevent;
+
+/// Emitted once per batch update, so caches can be invalidated by version
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListUpdated {
+    #[topic]
+    pub version: u32,
+    pub upserted: u32,
+    pub removed: u32,
+}
@@ -0,0 +1,20 @@
+//! Risk flags attached to listed tokens
+//!
+//! Stored as a bitmask in each token's metadata. Frontends warn on set flags; integrations may
+//! refuse tokens carrying some of them (e.g. fee-on-transfer tokens in exact-output flows).
+
+/// Transfers deliver less than the amount sent
+pub const RISK_FEE_ON_TRANSFER: u32 = 1 << 0;
+
+/// Balances change without transfers
+pub const RISK_REBASING: u32 = 1 << 1;
+
+/// An issuer can freeze or claw back balances
+pub const RISK_CLAWBACK: u32 = 1 << 2;
+
+/// Supply can be minted at will by a single account
+pub const RISK_UNCAPPED_MINT: u32 = 1 << 3;
+
+/// Every known flag
+pub const ALL_RISK_FLAGS: u32 =
+    RISK_FEE_ON_TRANSFER | RISK_REBASING | RISK_CLAWBACK | RISK_UNCAPPED_MINT;
@@ -0,0 +1,145 @@
+#![no_std]
+//! # Token List - Curated Token Metadata
+//!
+//! The canonical list of tokens the workshop contracts and frontend work with. A curator
+//! appointed by the admin maintains per-token metadata: symbol, decimals, the hash of the logo
+//! and risk flags (see `flags`).
+//!
+//! ## Versioning:
+//! Every batch update bumps the list `version` and emits a single `ListUpdated` event. Frontends
+//! cache the list and only re-fetch it when the version they hold is stale.
+
+use soroban_sdk::{Address, Env, Vec, contract, contractimpl};
+
+mod storage;
+mod error;
+mod events;
+pub mod flags;
+
+use storage::{
+    extend_instance_ttl, get_admin, get_curator, get_metadata, get_tokens, get_version,
+    remove_metadata, set_admin, set_curator, set_metadata, set_tokens, set_version, TokenMetadata,
+};
+use error::TokenListError;
+use events::ListUpdated;
+use flags::ALL_RISK_FLAGS;
+
+/// Largest page returned by `tokens`
+const MAX_PAGE_SIZE: u32 = 50;
+
+#[contract]
+struct TokenList;
+
+#[contractimpl]
+impl TokenList {
+    /// Initialize an empty list (version 0)
+    ///
+    /// ## Parameters:
+    /// - `admin`: Account appointing the curator
+    /// - `curator`: Account maintaining the list
+    pub fn __constructor(e: Env, admin: Address, curator: Address) {
+        set_admin(&e, admin);
+        set_curator(&e, curator);
+    }
+
+    /// Replace the curator (admin only)
+    pub fn set_curator(e: Env, curator: Address) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_curator(&e, curator);
+    }
+
+    /// Apply a batch of changes as one new list version (curator only)
+    ///
+    /// Removals are applied after the upserts. Listing an already listed token replaces its
+    /// metadata and keeps its position in the list.
+    ///
+    /// ## Parameters:
+    /// - `upserts`: `(token, metadata)` pairs to list or update
+    /// - `removals`: Tokens to delist
+    ///
+    /// ## Returns:
+    /// The new list version
+    pub fn update(
+        e: Env,
+        upserts: Vec<(Address, TokenMetadata)>,
+        removals: Vec<Address>,
+    ) -> Result<u32, TokenListError> {
+        get_curator(&e).require_auth();
+        extend_instance_ttl(&e);
+        if upserts.is_empty() && removals.is_empty() {
+            return Err(TokenListError::InvalidArgument);
+        }
+
+        let mut tokens = get_tokens(&e);
+        for (token, metadata) in upserts.iter() {
+            if metadata.risk_flags & !ALL_RISK_FLAGS != 0 {
+                return Err(TokenListError::UnknownRiskFlag);
+            }
+            if !tokens.contains(&token) {
+                tokens.push_back(token.clone());
+            }
+            set_metadata(&e, &token, &metadata);
+        }
+        for token in removals.iter() {
+            let index = tokens.first_index_of(&token).ok_or(TokenListError::NotListed)?;
+            tokens.remove(index);
+            remove_metadata(&e, &token);
+        }
+        set_tokens(&e, &tokens);
+
+        let version = get_version(&e) + 1;
+        set_version(&e, version);
+
+        ListUpdated {
+            version,
+            upserted: upserts.len(),
+            removed: removals.len(),
+        }
+        .publish(&e);
+        Ok(version)
+    }
+
+    /// Current list version; it changes with every update
+    pub fn version(e: Env) -> u32 {
+        get_version(&e)
+    }
+
+    /// Metadata of a token, if it is listed
+    pub fn token(e: Env, token: Address) -> Option<TokenMetadata> {
+        get_metadata(&e, &token)
+    }
+
+    /// Whether a token is listed
+    pub fn is_listed(e: Env, token: Address) -> bool {
+        get_metadata(&e, &token).is_some()
+    }
+
+    /// Number of listed tokens
+    pub fn token_count(e: Env) -> u32 {
+        get_tokens(&e).len()
+    }
+
+    /// Page through the listed tokens and their metadata, in listing order
+    ///
+    /// ## Parameters:
+    /// - `start`: Index of the first token to return
+    /// - `limit`: Number of tokens to return, capped at 50
+    pub fn tokens(e: Env, start: u32, limit: u32) -> Vec<(Address, TokenMetadata)> {
+        let tokens = get_tokens(&e);
+        let end = tokens.len().min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+        let mut page = Vec::new(&e);
+        for index in start..end {
+            let token = tokens.get_unchecked(index);
+            if let Some(metadata) = get_metadata(&e, &token) {
+                page.push_back((token, metadata));
+            }
+        }
+        page
+    }
+
+    /// Account maintaining the list
+    pub fn curator(e: Env) -> Address {
+        get_curator(&e)
+    }
+}
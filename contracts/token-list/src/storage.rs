@@ -0,0 +1,126 @@
+use soroban_sdk::{contracttype, Address, BytesN, Env, String, Vec};
+
+#[derive(Clone)]
+#[contracttype]
+
+enum DataKey {
+    Admin,
+    Curator,
+    Version,
+    Tokens,
+    Metadata(Address),
+}
+
+/// Curated metadata of a listed token
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMetadata {
+    pub symbol: String,
+    pub decimals: u32,
+    /// SHA-256 of the token's logo, so frontends can verify the icon they fetch
+    pub icon_hash: BytesN<32>,
+    /// Bitmask of `flags::RISK_*`
+    pub risk_flags: u32,
+}
+
+#[allow(dead_code)]
+const DAY_IN_LEDGERS: u32 = 17280;
+#[allow(dead_code)]
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+#[allow(dead_code)]
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+const PERSISTENT_BUMP_AMOUNT: u32 = 60 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+pub fn extend_instance_ttl(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Admin Address (appoints the curator)
+pub fn set_admin(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Admin, &address);
+}
+
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap()
+}
+
+// Curator Address (maintains the list)
+pub fn set_curator(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Curator, &address);
+}
+
+pub fn get_curator(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Curator)
+        .unwrap()
+}
+
+// List version, bumped by every update
+pub fn set_version(e: &Env, version: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Version, &version);
+}
+
+pub fn get_version(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::Version)
+        .unwrap_or(0)
+}
+
+// Listed tokens, in listing order
+pub fn set_tokens(e: &Env, tokens: &Vec<Address>) {
+    let key = DataKey::Tokens;
+    e.storage().persistent().set(&key, tokens);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_tokens(e: &Env) -> Vec<Address> {
+    let key = DataKey::Tokens;
+    let tokens = e.storage().persistent().get(&key);
+    if tokens.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    tokens.unwrap_or(Vec::new(e))
+}
+
+// Metadata per listed token
+pub fn set_metadata(e: &Env, token: &Address, metadata: &TokenMetadata) {
+    let key = DataKey::Metadata(token.clone());
+    e.storage().persistent().set(&key, metadata);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_metadata(e: &Env, token: &Address) -> Option<TokenMetadata> {
+    let key = DataKey::Metadata(token.clone());
+    let metadata = e.storage().persistent().get(&key);
+    if metadata.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    metadata
+}
+
+pub fn remove_metadata(e: &Env, token: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::Metadata(token.clone()));
+}
@@ -0,0 +1,11 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TutorialError {
+    /// Invalid parameters
+    InvalidArgument = 1,
+    /// Caller is not an allowed reporter
+    Unauthorized = 2,
+}
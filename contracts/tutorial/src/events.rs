@@ -0,0 +1,20 @@
+use soroban_sdk::{contractevent, Address};
+
+/// Emitted when a participant joins the tutorial
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParticipantJoined {
+    #[topic]
+    pub participant: Address,
+}
+
+/// Emitted the first time a participant completes a step
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StepCompleted {
+    #[topic]
+    pub participant: Address,
+    pub step: u32,
+    /// Every step completed so far, as a bitmask
+    pub steps: u32,
+}
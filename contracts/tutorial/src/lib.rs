@@ -0,0 +1,106 @@
+#![no_std]
+//! # Tutorial - Workshop Progress Recorder
+//!
+//! Participants `join` the tutorial, then exercise the workshop contracts as usual. Contracts in
+//! tutorial mode (those configured with this contract's address and listed as reporters here)
+//! report every successful step: simple swap, path swap, zap, withdraw and order fill. The
+//! `progress` view tells the badge-minting flow who completed what.
+//!
+//! Steps are the bitmask constants of `common::tutorial`. Reports for addresses that never
+//! joined are ignored, so tutorial mode costs nothing for regular users.
+
+use common::tutorial::ALL_STEPS;
+use soroban_sdk::{Address, Env, Vec, contract, contractimpl};
+
+mod storage;
+mod error;
+mod events;
+mod types;
+
+use storage::{
+    extend_instance_ttl, get_admin, get_reporters, get_steps, set_admin, set_reporters, set_steps,
+};
+use error::TutorialError;
+use events::{ParticipantJoined, StepCompleted};
+use types::Progress;
+
+#[contract]
+struct Tutorial;
+
+#[contractimpl]
+impl Tutorial {
+    /// Initialize the tutorial
+    ///
+    /// ## Parameters:
+    /// - `admin`: Account maintaining the reporters
+    /// - `reporters`: Contracts allowed to record steps
+    pub fn __constructor(e: Env, admin: Address, reporters: Vec<Address>) {
+        set_admin(&e, admin);
+        set_reporters(&e, &reporters);
+    }
+
+    /// Replace the contracts allowed to record steps (admin only)
+    pub fn set_reporters(e: Env, reporters: Vec<Address>) {
+        get_admin(&e).require_auth();
+        extend_instance_ttl(&e);
+        set_reporters(&e, &reporters);
+    }
+
+    /// Register the caller as a workshop participant; joining again keeps their progress
+    pub fn join(e: Env, participant: Address) {
+        participant.require_auth();
+        extend_instance_ttl(&e);
+        if get_steps(&e, &participant).is_none() {
+            set_steps(&e, &participant, 0);
+            ParticipantJoined { participant }.publish(&e);
+        }
+    }
+
+    /// Record that `participant` completed `step` (reporters only)
+    ///
+    /// `reporter` is the reporting contract, whose authorization is implicit when it calls
+    /// directly. `step` must be a single step constant.
+    ///
+    /// ## Returns:
+    /// Whether the step was recorded: `false` when the address never joined
+    pub fn record(e: Env, reporter: Address, participant: Address, step: u32) -> Result<bool, TutorialError> {
+        reporter.require_auth();
+        if !get_reporters(&e).contains(&reporter) {
+            return Err(TutorialError::Unauthorized);
+        }
+        if step & !ALL_STEPS != 0 || step.count_ones() != 1 {
+            return Err(TutorialError::InvalidArgument);
+        }
+        extend_instance_ttl(&e);
+
+        let steps = match get_steps(&e, &participant) {
+            Some(steps) => steps,
+            None => return Ok(false),
+        };
+        if steps & step == 0 {
+            set_steps(&e, &participant, steps | step);
+            StepCompleted {
+                participant,
+                step,
+                steps: steps | step,
+            }
+            .publish(&e);
+        }
+        Ok(true)
+    }
+
+    /// A participant's progress through the tutorial
+    pub fn progress(e: Env, participant: Address) -> Progress {
+        let steps = get_steps(&e, &participant);
+        Progress {
+            joined: steps.is_some(),
+            steps: steps.unwrap_or(0),
+            completed: steps == Some(ALL_STEPS),
+        }
+    }
+
+    /// Contracts allowed to record steps
+    pub fn reporters(e: Env) -> Vec<Address> {
+        get_reporters(&e)
+    }
+}
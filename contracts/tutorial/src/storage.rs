@@ -0,0 +1,76 @@
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+#[derive(Clone)]
+#[contracttype]
+
+enum DataKey {
+    Admin,
+    Reporters,
+    Participant(Address),
+}
+
+#[allow(dead_code)]
+const DAY_IN_LEDGERS: u32 = 17280;
+#[allow(dead_code)]
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+#[allow(dead_code)]
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+const PERSISTENT_BUMP_AMOUNT: u32 = 60 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+pub fn extend_instance_ttl(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Admin Address (maintains the reporters)
+pub fn set_admin(e: &Env, address: Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Admin, &address);
+}
+
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap()
+}
+
+// Contracts allowed to record steps
+pub fn set_reporters(e: &Env, reporters: &Vec<Address>) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Reporters, reporters);
+}
+
+pub fn get_reporters(e: &Env) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get(&DataKey::Reporters)
+        .unwrap_or(Vec::new(e))
+}
+
+// Completed steps of each participant, as a bitmask (no entry until they join)
+pub fn set_steps(e: &Env, participant: &Address, steps: u32) {
+    let key = DataKey::Participant(participant.clone());
+    e.storage().persistent().set(&key, &steps);
+    extend_persistent_ttl(e, &key);
+}
+
+pub fn get_steps(e: &Env, participant: &Address) -> Option<u32> {
+    let key = DataKey::Participant(participant.clone());
+    let steps = e.storage().persistent().get(&key);
+    if steps.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    steps
+}
@@ -0,0 +1,12 @@
+use soroban_sdk::contracttype;
+
+/// A participant's progress through the tutorial
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Progress {
+    pub joined: bool,
+    /// Completed steps as a bitmask of the `common::tutorial` step constants
+    pub steps: u32,
+    /// Whether every step is completed
+    pub completed: bool,
+}
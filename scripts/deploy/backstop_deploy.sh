@@ -0,0 +1 @@
+stellar contract deploy --source-account me --wasm ./target/wasm32v1-none/release/backstop.wasm  -- --admin GCAXSAYRQTFEYXXWYY5QV7TBZSZSSWLX2VGGIEAMU6UGNZNIW6NQXS37 --cover_token <COVER_TOKEN_ID> --loss_tolerance_bps '[100]' --max_payout_bps '[5000]'
@@ -0,0 +1 @@
+stellar contract deploy --source-account me --wasm ./target/wasm32v1-none/release/bond.wasm  -- --admin GCAXSAYRQTFEYXXWYY5QV7TBZSZSSWLX2VGGIEAMU6UGNZNIW6NQXS37 --vault_address CDM7U3IQTUE65ZUFOBLV7NI46GYHNBSXAYAXJ3W3EK4Z7S2RE2EYIBDW --oracle <ORACLE_ID> --payment_token <PAYMENT_TOKEN_ID> --treasury GCAXSAYRQTFEYXXWYY5QV7TBZSZSSWLX2VGGIEAMU6UGNZNIW6NQXS37 --terms '{"base_discount_bps":[200],"max_discount_bps":[800],"discount_growth_period":17280,"capacity":"1000000000000","vesting_period":120960}'
@@ -0,0 +1 @@
+stellar contract deploy --source-account me --wasm ./target/wasm32v1-none/release/claim_token.wasm  -- --admin <DEFINDEX_ZAP_CONTRACT_ID> --name "Zap Deposit Claim" --symbol ZCLAIM --decimals 7
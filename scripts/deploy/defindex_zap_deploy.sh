@@ -1 +1 @@
-stellar contract deploy --source-account me --wasm ./target/wasm32v1-none/release/defindex_zap.wasm  -- --vault_address CDM7U3IQTUE65ZUFOBLV7NI46GYHNBSXAYAXJ3W3EK4Z7S2RE2EYIBDW --router_address CCMAPXWVZD4USEKDWRYS7DA4Y3D7E2SDMGBFJUCEXTC7VN6CUBGWPFUS --underlying_asset CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC
\ No newline at end of file
+stellar contract deploy --source-account me --wasm ./target/wasm32v1-none/release/defindex_zap.wasm  -- --admin GCAXSAYRQTFEYXXWYY5QV7TBZSZSSWLX2VGGIEAMU6UGNZNIW6NQXS37 --vault_address CDM7U3IQTUE65ZUFOBLV7NI46GYHNBSXAYAXJ3W3EK4Z7S2RE2EYIBDW --router_address CCMAPXWVZD4USEKDWRYS7DA4Y3D7E2SDMGBFJUCEXTC7VN6CUBGWPFUS --underlying_asset CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC
\ No newline at end of file
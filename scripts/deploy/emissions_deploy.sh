@@ -0,0 +1 @@
+stellar contract deploy --source-account me --wasm ./target/wasm32v1-none/release/emissions.wasm  -- --admin GCAXSAYRQTFEYXXWYY5QV7TBZSZSSWLX2VGGIEAMU6UGNZNIW6NQXS37 --reward_token <REWARD_TOKEN_ID> --epoch_length 120960 --initial_rate 1000000 --decay_bps '[200]'
@@ -0,0 +1 @@
+stellar contract deploy --source-account me --wasm ./target/wasm32v1-none/release/faucet.wasm  -- --admin GCAXSAYRQTFEYXXWYY5QV7TBZSZSSWLX2VGGIEAMU6UGNZNIW6NQXS37 --router "CCMAPXWVZD4USEKDWRYS7DA4Y3D7E2SDMGBFJUCEXTC7VN6CUBGWPFUS" --drips '[{"token":"CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC","amount":"1000000000"}]' --cooldown 17280
\ No newline at end of file
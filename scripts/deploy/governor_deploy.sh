@@ -0,0 +1 @@
+stellar contract deploy --source-account me --wasm ./target/wasm32v1-none/release/governor.wasm  -- --voting_token <VOTING_TOKEN_ID> --voting_period 51840 --timelock_delay 34560 --quorum 1000000000 --proposal_threshold 10000000 --share_source <DEFINDEX_ZAP_CONTRACT_ID>
@@ -0,0 +1 @@
+stellar contract deploy --source-account me --wasm ./target/wasm32v1-none/release/scoreboard.wasm  -- --admin GCAXSAYRQTFEYXXWYY5QV7TBZSZSSWLX2VGGIEAMU6UGNZNIW6NQXS37 --oracle <ORACLE_ID> --sources '["<SOROSWAP_AUTH_CONTRACT_ID>", "<DEFINDEX_ZAP_CONTRACT_ID>"]' --assets '["<USDC_ID>", "<XLM_ID>"]' --end_ledger <END_LEDGER>
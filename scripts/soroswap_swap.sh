@@ -1 +1,2 @@
-stellar contract invoke --id CB3OFRLI5TS7WHN4ELPG44P2NG47SIMEDLX4FIS5ZW27VBBJMMMVM4QK --source me --network testnet -- swap --caller GCAXSAYRQTFEYXXWYY5QV7TBZSZSSWLX2VGGIEAMU6UGNZNIW6NQXS37 --token_in CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC --token_out CDWEFYYHMGEZEFC5TBUDXM3IJJ7K7W5BDGE765UIYQEV4JFWDOLSTOEK --amount 100000000
\ No newline at end of file
+QUOTE=$(stellar contract invoke --id CB3OFRLI5TS7WHN4ELPG44P2NG47SIMEDLX4FIS5ZW27VBBJMMMVM4QK --source me --send no --network testnet -- quote --token_in CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC --token_out CDWEFYYHMGEZEFC5TBUDXM3IJJ7K7W5BDGE765UIYQEV4JFWDOLSTOEK --amount 100000000 | tr -d '"')
+stellar contract invoke --id CB3OFRLI5TS7WHN4ELPG44P2NG47SIMEDLX4FIS5ZW27VBBJMMMVM4QK --source me --network testnet -- swap --caller GCAXSAYRQTFEYXXWYY5QV7TBZSZSSWLX2VGGIEAMU6UGNZNIW6NQXS37 --token_in CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC --token_out CDWEFYYHMGEZEFC5TBUDXM3IJJ7K7W5BDGE765UIYQEV4JFWDOLSTOEK --amount 100000000 --amount_out_min $((QUOTE * 99 / 100)) --deadline_delta 20
\ No newline at end of file